@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Fingerprints of X.509 certificates and IKEv2 public keys.
+
+use crate::{hash::Algorithm, Result};
+
+/// A certificate or public key fingerprint.
+pub struct Fingerprint {
+    pub algo: Algorithm,
+    pub digest: Vec<u8>,
+    /// The subject of a certificate.
+    pub subject: Option<String>,
+}
+
+/// Return the SHA-256 fingerprint of a DER or PEM certificate, or the
+/// IKEv2 SHA-1 hash of the public key of a certificate or key.
+#[cfg(feature = "x509")]
+pub fn fingerprint(data: &[u8], ikev2: bool) -> Result<Fingerprint> {
+    use bubblebabble::{ikev2_key_hash, CertificateFingerprint};
+
+    if ikev2 {
+        Ok(Fingerprint {
+            algo: Algorithm::Sha1,
+            digest: ikev2_key_hash(data)?.to_vec(),
+            subject: CertificateFingerprint::sha1(data)
+                .ok()
+                .map(|fpr| fpr.subject),
+        })
+    } else {
+        let fpr = CertificateFingerprint::sha256(data)?;
+        Ok(Fingerprint {
+            algo: Algorithm::Sha256,
+            digest: fpr.digest,
+            subject: Some(fpr.subject),
+        })
+    }
+}
+
+#[cfg(not(feature = "x509"))]
+pub fn fingerprint(_data: &[u8], _ikev2: bool) -> Result<Fingerprint> {
+    Err("certificates are not supported, enable the x509 feature".into())
+}
+
+#[cfg(all(test, feature = "x509"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        let cert = include_bytes!("../../tests/data/cert.pem");
+        let key = include_bytes!("../../tests/data/pub.pem");
+
+        let fpr = fingerprint(cert, false).unwrap();
+        assert_eq!(fpr.algo, Algorithm::Sha256);
+        assert_eq!(fpr.subject.as_deref(), Some("CN=example.com, O=Example"));
+        assert!(fingerprint(key, false).is_err());
+
+        let ikev2 = fingerprint(cert, true).unwrap();
+        assert_eq!(ikev2.algo, Algorithm::Sha1);
+        assert_eq!(ikev2.subject, fpr.subject);
+        let raw = fingerprint(key, true).unwrap();
+        assert_eq!(raw.digest, ikev2.digest);
+        assert_eq!(raw.subject, None);
+    }
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Detection of duplicate files by their content digest.
+
+use crate::{hash::Algorithm, tree, Result};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Files below a directory that have the same content.
+#[derive(Debug)]
+pub struct Group {
+    pub digest: Vec<u8>,
+    pub size: u64,
+    pub files: Vec<PathBuf>,
+}
+
+/// Return the groups of files below `dir` with identical content.
+///
+/// Only files with the same size are hashed, so unique files are not
+/// read at all.  The groups are sorted by digest and the files of each
+/// group by path.
+pub fn duplicates(algo: Algorithm, dir: &Path) -> Result<Vec<Group>> {
+    let mut sizes = BTreeMap::<u64, Vec<PathBuf>>::new();
+    for path in tree::files(dir)? {
+        let file = dir.join(&path);
+        let size = fs::metadata(&file)
+            .map_err(|err| format!("{}: {}", file.display(), err))?
+            .len();
+        sizes.entry(size).or_default().push(path);
+    }
+
+    let mut digests = BTreeMap::<Vec<u8>, Group>::new();
+    for (size, files) in sizes.into_iter().filter(|(_, files)| files.len() > 1) {
+        for path in files {
+            let digest = algo.digest_file(&dir.join(&path))?;
+            digests
+                .entry(digest.clone())
+                .or_insert_with(|| Group {
+                    digest,
+                    size,
+                    files: Vec::new(),
+                })
+                .files
+                .push(path);
+        }
+    }
+
+    Ok(digests
+        .into_values()
+        .filter(|group| group.files.len() > 1)
+        .collect())
+}
+
+#[cfg(all(test, feature = "sha2"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duplicates() {
+        let dir = std::env::temp_dir().join(format!("bubblebabble-dedupe-{}", std::process::id()));
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        for (name, data) in &[
+            ("a", "Pineapple"),
+            ("b", "Pineapple"),
+            ("sub/c", "Pineapple"),
+            ("d", "Pineapplf"),
+            ("e", "apple"),
+        ] {
+            fs::write(dir.join(name), data).unwrap();
+        }
+
+        let groups = duplicates(Algorithm::Sha256, &dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let groups = groups.unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].size, 9);
+        assert_eq!(
+            groups[0].digest,
+            Algorithm::Sha256.digest(&b"Pineapple"[..]).unwrap()
+        );
+        assert_eq!(
+            groups[0].files,
+            [Path::new("a"), Path::new("b"), Path::new("sub/c")]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Resolve git objects by running the `git` command.
+
+use crate::Result;
+use data_encoding::HEXLOWER_PERMISSIVE;
+use std::{path::Path, process::Command};
+
+/// A resolved git object.
+pub struct Object {
+    /// The object ID, a SHA-1 or SHA-256 hash depending on the repository.
+    pub id: Vec<u8>,
+    /// The object type, e.g. `commit`, `tag`, `tree`, or `blob`.
+    pub kind: String,
+}
+
+impl Object {
+    /// Resolve a revision, or hash the file if `rev` is a path.
+    pub fn resolve(rev: &str) -> Result<Self> {
+        let object = format!("{}^{{object}}", rev);
+        match git(&["rev-parse", "--verify", "--quiet", &object]) {
+            Ok(id) => {
+                let kind = git(&["cat-file", "-t", &id])?;
+                Self::new(&id, kind)
+            }
+            Err(_) if Path::new(rev).is_file() => {
+                let id = git(&["hash-object", "--", rev])?;
+                Self::new(&id, "blob".into())
+            }
+            Err(err) => Err(format!("{}: {}", rev, err).into()),
+        }
+    }
+
+    fn new(id: &str, kind: String) -> Result<Self> {
+        let id = HEXLOWER_PERMISSIVE
+            .decode(id.as_bytes())
+            .map_err(|err| format!("invalid object ID {}: {}", id, err))?;
+        Ok(Self { id, kind })
+    }
+}
+
+/// Run git and return the trimmed output.
+fn git(args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|err| format!("failed to run git: {}", err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => "unknown revision or path".into(),
+            stderr => stderr.trim_start_matches("fatal: ").into(),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,135 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Hash algorithms for fingerprinting files.
+
+use crate::Result;
+use clap::{builder::PossibleValue, ValueEnum};
+use std::{
+    fmt,
+    fs::File,
+    io::{self, Read},
+    path::Path,
+};
+
+/// Supported hash algorithms, each of them is behind a cargo feature.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    #[default]
+    Sha256,
+    Sha512,
+    Blake3,
+}
+
+impl Algorithm {
+    pub fn name(self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "md5",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Sha256 => "sha256",
+            Algorithm::Sha512 => "sha512",
+            Algorithm::Blake3 => "blake3",
+        }
+    }
+
+    fn feature(self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "md5",
+            Algorithm::Sha1 => "sha1",
+            Algorithm::Sha256 | Algorithm::Sha512 => "sha2",
+            Algorithm::Blake3 => "blake3",
+        }
+    }
+
+    /// Hash all data from `reader` and return the digest.
+    #[allow(unused_mut, unused_variables)]
+    pub fn digest(self, mut reader: impl Read) -> Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "md5")]
+            Algorithm::Md5 => digest::<md5::Md5>(reader),
+            #[cfg(feature = "sha1")]
+            Algorithm::Sha1 => digest::<sha1::Sha1>(reader),
+            #[cfg(feature = "sha2")]
+            Algorithm::Sha256 => digest::<sha2::Sha256>(reader),
+            #[cfg(feature = "sha2")]
+            Algorithm::Sha512 => digest::<sha2::Sha512>(reader),
+            #[cfg(feature = "blake3")]
+            Algorithm::Blake3 => {
+                let mut hasher = blake3::Hasher::new();
+                io::copy(&mut reader, &mut hasher)?;
+                Ok(hasher.finalize().as_bytes().to_vec())
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(format!(
+                "{} is not supported, enable the {} feature",
+                self,
+                self.feature()
+            )
+            .into()),
+        }
+    }
+
+    /// Hash the file at `path`, or stdin if it is `-`.
+    pub fn digest_file(self, path: &Path) -> Result<Vec<u8>> {
+        if path == Path::new("-") {
+            self.digest(io::stdin().lock())
+        } else {
+            File::open(path)
+                .map_err(Into::into)
+                .and_then(|file| self.digest(file))
+                .map_err(|err| format!("{}: {}", path.display(), err).into())
+        }
+    }
+}
+
+#[cfg(any(feature = "md5", feature = "sha1", feature = "sha2"))]
+fn digest<D: digest::Digest + io::Write>(mut reader: impl Read) -> Result<Vec<u8>> {
+    let mut hasher = D::new();
+    io::copy(&mut reader, &mut hasher)?;
+    Ok(hasher.finalize().to_vec())
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl ValueEnum for Algorithm {
+    fn value_variants<'a>() -> &'a [Self] {
+        &[
+            Algorithm::Md5,
+            Algorithm::Sha1,
+            Algorithm::Sha256,
+            Algorithm::Sha512,
+            Algorithm::Blake3,
+        ]
+    }
+
+    fn to_possible_value(&self) -> Option<PossibleValue> {
+        Some(PossibleValue::new(self.name()))
+    }
+}
@@ -0,0 +1,1120 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Command-line interface for the Bubble Babble Binary Data Encoding.
+
+mod cert;
+mod clipboard;
+mod dedupe;
+mod git;
+mod hash;
+mod monitor;
+mod qr;
+mod readback;
+mod ssh;
+mod stream;
+mod table;
+mod tree;
+
+use crate::{hash::Algorithm, ssh::Fingerprint, stream::Wrap, table::Table};
+use bubblebabble::*;
+use clap::{Parser, Subcommand, ValueEnum};
+use data_encoding::{BASE64, HEXLOWER_PERMISSIVE};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    env, error, fs,
+    io::{self, IsTerminal, Read, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::Duration,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+/// Convert data to and from the Bubble Babble encoding.
+#[derive(Parser)]
+#[command(version, about)]
+struct Args {
+    #[command(flatten)]
+    style: Style,
+
+    /// Input format of the data to encode, arguments are raw and stdin
+    /// is detected by default
+    #[arg(short, long, value_enum)]
+    input: Option<Input>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Data to encode, read from stdin if omitted
+    data: Vec<String>,
+}
+
+/// Options that control how Babble strings are generated and printed.
+#[derive(clap::Args)]
+struct Style {
+    /// Use the stable Babble format without checksum
+    #[arg(short, long, global = true)]
+    stable: bool,
+
+    /// Print a QR code of the Babble string
+    #[arg(long, global = true)]
+    qr: bool,
+
+    /// Write a QR code of the Babble string as PNG image
+    #[arg(long, global = true, value_name = "FILE")]
+    qr_png: Option<PathBuf>,
+
+    /// Use the raw bytes instead of the Babble string for QR codes
+    #[arg(long, global = true)]
+    qr_bytes: bool,
+
+    /// Print the OpenSSH randomart of the data below the Babble string
+    #[arg(long, global = true)]
+    randomart: bool,
+
+    /// Print JSON objects instead of text
+    #[arg(long, global = true, conflicts_with = "qr")]
+    json: bool,
+
+    /// Wrap encoded lines after COLS characters, 0 disables wrapping
+    #[arg(short, long, global = true, value_name = "COLS", default_value_t = 0)]
+    wrap: usize,
+
+    /// Separator between the words
+    #[arg(long, global = true, value_name = "CHAR", default_value_t = '-', value_parser = parse_separator)]
+    separator: char,
+
+    /// Split the words into groups of N words separated by a space
+    #[arg(long, global = true, value_name = "N", default_value_t = 0)]
+    group: usize,
+
+    /// Print the words in uppercase letters
+    #[arg(long, global = true)]
+    uppercase: bool,
+
+    /// Alphabet of the words, `standard` or `VOWELS:CONSONANTS`
+    #[arg(long, global = true, default_value = "standard")]
+    alphabet: Alphabet,
+
+    /// Copy the Babble strings to the system clipboard
+    #[arg(long, global = true)]
+    copy: bool,
+
+    /// End each output line with NUL instead of a newline
+    #[arg(short, long, global = true)]
+    zero: bool,
+
+    /// Print nothing for `check`, `verify`, and `diff`, only exit with
+    /// the status
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// Babble strings collected for `--copy`
+    #[arg(skip)]
+    copied: RefCell<Vec<String>>,
+}
+
+impl Style {
+    /// Return the library formatting options.
+    fn options(&self) -> Options {
+        // The separator is checked by parse_separator
+        Options::new()
+            .separator(self.separator)
+            .unwrap_or_default()
+            .stable(self.stable)
+            .group(self.group)
+            .uppercase(self.uppercase)
+            .alphabet(self.alphabet)
+    }
+
+    /// Return the terminator of output lines.
+    fn end(&self) -> char {
+        if self.zero {
+            '\0'
+        } else {
+            '\n'
+        }
+    }
+
+    /// Remember `babble` to copy it to the clipboard when done.
+    fn copy(&self, babble: &str) {
+        if self.copy {
+            self.copied.borrow_mut().push(babble.to_string());
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Decode Babble strings back to bytes
+    Decode {
+        /// Output format of the decoded bytes
+        #[arg(short, long, value_enum, default_value_t = Output::Hex)]
+        output: Output,
+
+        /// Babble strings to decode, read from stdin if omitted
+        babble: Vec<String>,
+    },
+    /// Print a hexdump-style view with offsets and Babble words
+    Dump {
+        /// Number of words per line
+        #[arg(short = 'n', long, default_value_t = 9)]
+        words: usize,
+
+        /// Print a gutter with the printable ASCII characters
+        #[arg(long)]
+        ascii: bool,
+
+        /// Input file, read from stdin if omitted or `-`
+        file: Option<PathBuf>,
+    },
+    /// Hash files and print the digests as Babble
+    ///
+    /// The digests of several files are printed with their names and
+    /// files that cannot be read are skipped with an error.
+    Hash {
+        /// Hash algorithm
+        #[arg(short, long, value_enum, default_value_t)]
+        algo: Algorithm,
+
+        /// Print `BABBLE  FILE` lines like sha256sum
+        #[arg(short, long)]
+        reverse: bool,
+
+        /// Files to hash, read from stdin if omitted or `-`
+        files: Vec<PathBuf>,
+    },
+    /// Check files against `BABBLE  FILE` lines from `hash -r`
+    ///
+    /// The digests are compared, so the lines may be written in the
+    /// stable format, in uppercase, or with other separators.
+    Check {
+        /// Hash algorithm
+        #[arg(short, long, value_enum, default_value_t)]
+        algo: Algorithm,
+
+        /// Files with checksum lines, read from stdin if omitted or `-`
+        sums: Vec<PathBuf>,
+    },
+    /// Hash all files in a directory and print a combined digest
+    ///
+    /// The combined digest is computed over the relative path and the
+    /// digest of each file in sorted order.
+    Tree {
+        /// Hash algorithm
+        #[arg(short, long, value_enum, default_value_t)]
+        algo: Algorithm,
+
+        /// Directory to walk
+        dir: PathBuf,
+    },
+    /// Find files with identical content in a directory
+    ///
+    /// Each group of duplicates is printed under the Babble of its
+    /// digest, followed by the relative paths of the files.
+    Dedupe {
+        /// Hash algorithm
+        #[arg(short, long, value_enum, default_value_t)]
+        algo: Algorithm,
+
+        /// Directory to walk
+        dir: PathBuf,
+    },
+    /// Verify that the input matches an expected Babble string
+    Verify {
+        /// Expected Babble string, read from stdin if omitted
+        #[arg(short, long)]
+        expect: Option<String>,
+
+        /// Hash the input with the algorithm before encoding it
+        #[arg(short, long, value_enum)]
+        algo: Option<Algorithm>,
+
+        /// Input file, read from stdin if omitted or `-`
+        file: Option<PathBuf>,
+    },
+    /// Read a file fingerprint aloud and confirm it group by group
+    Readback {
+        /// Hash algorithm
+        #[arg(short, long, value_enum, default_value_t)]
+        algo: Algorithm,
+
+        /// File to fingerprint
+        file: PathBuf,
+    },
+    /// Compare two Babble strings word by word
+    Diff {
+        /// Colorize matching and differing words
+        #[arg(long, value_enum, default_value_t = Color::Auto)]
+        color: Color,
+
+        a: String,
+        b: String,
+    },
+    /// Print the object IDs of git revisions or files as Babble
+    Git {
+        /// Revisions or paths to resolve
+        #[arg(default_value = "HEAD")]
+        revs: Vec<String>,
+    },
+    /// Print SSH public key fingerprints like `ssh-keygen -B`
+    Fingerprint {
+        /// Public key files, read from stdin if omitted or `-`
+        files: Vec<PathBuf>,
+    },
+    /// Print the host key fingerprints of a `known_hosts` file
+    ///
+    /// The output can be saved as a snapshot and compared later to
+    /// detect changed host keys.
+    KnownHosts {
+        /// Only print the keys of the host, matching patterns and hashed names
+        #[arg(long)]
+        host: Option<String>,
+
+        /// Compare the fingerprints with a previously saved output
+        #[arg(short, long, value_name = "SNAPSHOT")]
+        compare: Option<PathBuf>,
+
+        /// Path of the file, defaults to `~/.ssh/known_hosts`
+        file: Option<PathBuf>,
+    },
+    /// Print the fingerprints of X.509 certificates
+    ///
+    /// The fingerprint is the SHA-256 digest of the certificate, or with
+    /// `--ikev2` the SHA-1 digest of its public key that identifies IKEv2
+    /// peers and CAs.
+    Cert {
+        /// Print the IKEv2 hash of the public key, also of raw public keys
+        #[arg(long)]
+        ikev2: bool,
+
+        /// DER or PEM files, read from stdin if omitted or `-`
+        files: Vec<PathBuf>,
+    },
+    /// Monitor the SSH host keys or TLS certificates of remote hosts
+    ///
+    /// The fingerprints are compared with the pinned ones in FILE every
+    /// interval and the command fails as soon as one of them changes.
+    /// Keys that are not pinned yet are added to FILE.  The digests are
+    /// compared, so the pins match with any output format.  Hosts that
+    /// cannot be fetched are reported as FAILED and alerted, and the
+    /// command fails after the last check.
+    ///
+    /// The keys are fetched with `ssh-keyscan` and `openssl s_client`
+    /// and the webhook is posted with `curl`, which must be installed
+    /// in the PATH.
+    Monitor {
+        /// File with the pinned fingerprints, created if it does not exist
+        #[arg(long, value_name = "FILE")]
+        pin: PathBuf,
+
+        /// Seconds between two checks
+        #[arg(long, value_name = "SECS", default_value_t = 300)]
+        interval: u64,
+
+        /// Stop after N checks, 0 checks until a fingerprint changes
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        count: usize,
+
+        /// POST a JSON alert to the URL with `curl` when a fingerprint
+        /// changes or a host cannot be fetched
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+
+        /// Hosts as `[ssh://|tls://]host[:port]`, SSH is the default
+        #[arg(required = true)]
+        hosts: Vec<String>,
+    },
+    /// Add a Babble column to a CSV or TSV table
+    ///
+    /// The data of the column is read in the format of `--input` and
+    /// its Babble is appended to each row.
+    Table {
+        /// Header name or 1-based number of the column with the data
+        #[arg(short, long)]
+        column: String,
+
+        /// Header of the added column
+        #[arg(long, default_value = "babble")]
+        name: String,
+
+        /// Read and write tab-separated values
+        #[arg(long)]
+        tsv: bool,
+
+        /// The table has no header row
+        #[arg(long)]
+        no_header: bool,
+
+        /// Input file, read from stdin if omitted or `-`
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Input {
+    /// Detect hex or base64 and fall back to raw
+    Auto,
+    Hex,
+    Base64,
+    Raw,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Output {
+    Hex,
+    Base64,
+    Raw,
+}
+
+fn main() {
+    let args = Args::parse();
+
+    match run(args) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        // The output was closed early, e.g. by `head`
+        Err(err)
+            if err
+                .downcast_ref::<io::Error>()
+                .is_some_and(|err| err.kind() == io::ErrorKind::BrokenPipe) => {}
+        Err(err) => {
+            eprintln!("bubblebabble: {}", err);
+            process::exit(1);
+        }
+    }
+}
+
+/// Run the command and return `false` if a verification failed.
+fn run(args: Args) -> Result<bool> {
+    let style = &args.style;
+    let ok = match args.command {
+        None => encode(&args.data, args.input, style).map(|_| true)?,
+        Some(Command::Decode { output, babble }) => decode(&babble, output, style).map(|_| true)?,
+        Some(Command::Dump { words, ascii, file }) => {
+            dump(words, ascii, file, style).map(|_| true)?
+        }
+        Some(Command::Hash {
+            algo,
+            reverse,
+            files,
+        }) => hash(algo, reverse, &files, style)?,
+        Some(Command::Check { algo, sums }) => check(algo, &sums, style)?,
+        Some(Command::Tree { algo, dir }) => tree(algo, &dir, style).map(|_| true)?,
+        Some(Command::Dedupe { algo, dir }) => dedupe(algo, &dir, style).map(|_| true)?,
+        Some(Command::Verify { expect, algo, file }) => verify(expect, algo, file, style)?,
+        Some(Command::Readback { algo, file }) => readback(algo, &file, style)?,
+        Some(Command::Diff { color, a, b }) => diff(&a, &b, color, style)?,
+        Some(Command::Git { revs }) => git(&revs, style).map(|_| true)?,
+        Some(Command::Fingerprint { files }) => fingerprint(&files, style).map(|_| true)?,
+        Some(Command::KnownHosts {
+            host,
+            compare,
+            file,
+        }) => known_hosts(host.as_deref(), compare.as_deref(), file, style)?,
+        Some(Command::Cert { ikev2, files }) => cert(ikev2, &files, style).map(|_| true)?,
+        Some(Command::Monitor {
+            pin,
+            interval,
+            count,
+            webhook,
+            hosts,
+        }) => monitor(&pin, interval, count, webhook.as_deref(), &hosts, style)?,
+        Some(Command::Table {
+            column,
+            name,
+            tsv,
+            no_header,
+            file,
+        }) => {
+            let table = Table {
+                column: &column,
+                name: &name,
+                delimiter: if tsv { b'\t' } else { b',' },
+                header: !no_header,
+                end: style.end() as u8,
+            };
+            let input = args.input.unwrap_or(Input::Auto);
+            table_file(&table, file, input, style).map(|_| true)?
+        }
+    };
+
+    let copied = style.copied.borrow();
+    if !copied.is_empty() {
+        clipboard::copy(&copied.join("\n"))?;
+    }
+
+    Ok(ok)
+}
+
+/// Read the file at `path`, or stdin if it is `-`.
+fn read_path(path: &Path) -> Result<Vec<u8>> {
+    if path.as_os_str() == "-" {
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        Ok(bytes)
+    } else {
+        fs::read(path).map_err(|err| format!("{}: {}", path.display(), err).into())
+    }
+}
+
+fn parse_separator(s: &str) -> std::result::Result<char, String> {
+    match s.parse::<char>() {
+        Ok(c) if Options::new().separator(c).is_ok() => Ok(c),
+        _ => Err("must be a single non-alphanumeric character".into()),
+    }
+}
+
+fn babble(bytes: &[u8], style: &Style) -> String {
+    style.options().encode(bytes)
+}
+
+/// Return `true` if `babble` is the Babble of `bytes`, written in the
+/// current style or with any other case, separator, group, or format.
+fn babble_matches(babble: &str, bytes: &[u8], style: &Style) -> bool {
+    let words = babble
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .to_ascii_lowercase();
+
+    babble_eq(&words, bytes)
+        || debabble_stable(&words).is_ok_and(|stable| stable == bytes)
+        || style
+            .options()
+            .decode(babble)
+            .is_ok_and(|decoded| decoded == bytes)
+}
+
+/// Print the Babble string of `bytes`, followed by `name` if specified.
+fn print(bytes: &[u8], algo: Option<Algorithm>, name: Option<&Path>, style: &Style) -> Result<()> {
+    let babble = babble(bytes, style);
+    style.copy(&babble);
+    if style.json {
+        let (bubble, stable) = (bubblebabble(bytes), stablebabble(bytes));
+        let words = if style.stable { &stable } else { &bubble };
+        let mut json = serde_json::json!({
+            "input_len": bytes.len(),
+            "algorithm": algo.map(Algorithm::name),
+            "babble": bubble,
+            "stablebabble": stable,
+            "words": words.split('-').collect::<Vec<_>>(),
+        });
+        if !style.options().is_plain() {
+            json["formatted"] = babble.clone().into();
+        }
+        if let Some(name) = name {
+            json["file"] = name.to_string_lossy().into();
+        }
+        if style.randomart {
+            json["randomart"] = randomart(bytes, "", &footer(algo)).into();
+        }
+        print!("{}{}", json, style.end());
+    } else {
+        let mut out = Wrap::new(io::stdout().lock(), style.wrap);
+        out.write_str(&babble)?;
+        if let Some(name) = name {
+            out.write_str(&format!("  {}", name.display()))?;
+        }
+        out.finish(style.end())?;
+        if style.randomart {
+            print!("{}{}", randomart(bytes, "", &footer(algo)), style.end());
+        }
+    }
+
+    let data = if style.qr_bytes {
+        bytes
+    } else {
+        babble.as_bytes()
+    };
+    if style.qr {
+        print!("{}", qr::render(data)?);
+    }
+    if let Some(path) = &style.qr_png {
+        qr::write_png(data, path)?;
+    }
+
+    Ok(())
+}
+
+/// Return the randomart footer of the hash algorithm.
+fn footer(algo: Option<Algorithm>) -> String {
+    algo.map(Algorithm::name).unwrap_or_default().to_uppercase()
+}
+
+fn encode(data: &[String], input: Option<Input>, style: &Style) -> Result<()> {
+    if data.is_empty() {
+        stream::encode(io::stdin().lock(), input.unwrap_or(Input::Auto), style)?;
+    } else {
+        // Arguments are text, so only decode them when asked to
+        let input = input.unwrap_or(Input::Raw);
+        for s in data {
+            print(&parse_input(s.as_bytes(), input)?, None, None, style)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_input(data: &[u8], input: Input) -> Result<Vec<u8>> {
+    // Text input may be wrapped over multiple lines, e.g. by `xxd -p`
+    let text = std::str::from_utf8(data)
+        .ok()
+        .map(|s| s.split_whitespace().collect::<String>());
+    let hex = |text: &str| HEXLOWER_PERMISSIVE.decode(text.as_bytes());
+    let base64 = |text: &str| BASE64.decode(text.as_bytes());
+
+    match (input, text) {
+        (Input::Raw, _) => Ok(data.to_vec()),
+        (Input::Hex, Some(text)) => {
+            hex(&text).map_err(|err| format!("invalid hex input: {}", err).into())
+        }
+        (Input::Base64, Some(text)) => {
+            base64(&text).map_err(|err| format!("invalid base64 input: {}", err).into())
+        }
+        (_, None) if input != Input::Auto => Err("input is not valid text".into()),
+        (_, Some(text)) if !text.is_empty() => Ok(hex(&text)
+            .or_else(|_| base64(&text))
+            .unwrap_or_else(|_| data.to_vec())),
+        _ => Ok(data.to_vec()),
+    }
+}
+
+fn decode(babble: &[String], output: Output, style: &Style) -> Result<()> {
+    if babble.is_empty() {
+        return stream::decode(io::stdin().lock(), None, output, style);
+    }
+
+    for s in babble {
+        stream::decode(s.as_bytes(), Some(s), output, style)?;
+    }
+
+    Ok(())
+}
+
+fn dump(words: usize, ascii: bool, file: Option<PathBuf>, style: &Style) -> Result<()> {
+    let dump = Dump::new()
+        .words(words)
+        .ascii(ascii)
+        .options(style.options());
+
+    match file.filter(|path| path.as_os_str() != "-") {
+        Some(path) => {
+            let file =
+                fs::File::open(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            stream::dump(file, &dump)
+        }
+        None => stream::dump(io::stdin().lock(), &dump),
+    }
+}
+
+fn table_file(table: &Table, file: Option<PathBuf>, input: Input, style: &Style) -> Result<()> {
+    let out = io::stdout().lock();
+    match file.filter(|path| path.as_os_str() != "-") {
+        Some(path) => {
+            let file =
+                fs::File::open(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            table.annotate(file, out, input, &style.options())
+        }
+        None => table.annotate(io::stdin().lock(), out, input, &style.options()),
+    }
+}
+
+fn hash(algo: Algorithm, reverse: bool, files: &[PathBuf], style: &Style) -> Result<bool> {
+    let stdin = [PathBuf::from("-")];
+    let files = if files.is_empty() { &stdin } else { files };
+    let mut unreadable = 0;
+
+    for path in files {
+        let digest = match algo.digest_file(path) {
+            Ok(digest) => digest,
+            Err(err) => {
+                eprintln!("bubblebabble: {}", err);
+                unreadable += 1;
+                continue;
+            }
+        };
+        // Like sha256sum, the digests of several files are named
+        let name = Some(path.as_path()).filter(|_| reverse || style.json || files.len() > 1);
+        print(&digest, Some(algo), name, style)?;
+    }
+
+    Ok(unreadable == 0)
+}
+
+fn check(algo: Algorithm, sums: &[PathBuf], style: &Style) -> Result<bool> {
+    let stdin = [PathBuf::from("-")];
+    let sums = if sums.is_empty() { &stdin } else { sums };
+    let (mut failed, mut unreadable) = (0, 0);
+
+    for path in sums {
+        let lines = read_path(path)?;
+
+        for (i, line) in String::from_utf8_lossy(&lines).lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (expect, file) = match line.split_once("  ").or_else(|| line.split_once(" *")) {
+                Some(fields) => fields,
+                None => {
+                    return Err(
+                        format!("{}:{}: invalid checksum line", path.display(), i + 1).into(),
+                    )
+                }
+            };
+
+            match algo.digest_file(Path::new(file)) {
+                Ok(digest) if babble_matches(expect, &digest, style) => {
+                    if !style.quiet {
+                        print!("{}: OK{}", file, style.end());
+                    }
+                }
+                Ok(_) => {
+                    if !style.quiet {
+                        print!("{}: FAILED{}", file, style.end());
+                    }
+                    failed += 1;
+                }
+                Err(err) => {
+                    if !style.quiet {
+                        eprintln!("bubblebabble: {}", err);
+                        print!("{}: FAILED open or read{}", file, style.end());
+                    }
+                    unreadable += 1;
+                }
+            }
+        }
+    }
+
+    if style.quiet {
+        return Ok(failed == 0 && unreadable == 0);
+    }
+    if unreadable > 0 {
+        eprintln!(
+            "bubblebabble: WARNING: {} listed file{} could not be read",
+            unreadable,
+            if unreadable == 1 { "" } else { "s" }
+        );
+    }
+    if failed > 0 {
+        eprintln!(
+            "bubblebabble: WARNING: {} computed checksum{} did NOT match",
+            failed,
+            if failed == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(failed == 0 && unreadable == 0)
+}
+
+fn tree(algo: Algorithm, dir: &Path, style: &Style) -> Result<()> {
+    let mut combined = Vec::new();
+
+    for path in tree::files(dir)? {
+        let name = tree::name(&path)?;
+        let digest = algo.digest_file(&dir.join(&path))?;
+        print(&digest, Some(algo), Some(&path), style)?;
+
+        combined.extend_from_slice(name.as_bytes());
+        combined.push(0);
+        combined.extend_from_slice(&digest);
+    }
+
+    let digest = algo.digest(&combined[..])?;
+    let name = format!("{}/", dir.display().to_string().trim_end_matches('/'));
+    print(&digest, Some(algo), Some(Path::new(&name)), style)
+}
+
+fn dedupe(algo: Algorithm, dir: &Path, style: &Style) -> Result<()> {
+    for group in dedupe::duplicates(algo, dir)? {
+        let babble = babble(&group.digest, style);
+        style.copy(&babble);
+        if style.json {
+            let json = serde_json::json!({
+                "algorithm": algo.name(),
+                "babble": babble,
+                "size": group.size,
+                "files": group
+                    .files
+                    .iter()
+                    .map(|path| path.to_string_lossy())
+                    .collect::<Vec<_>>(),
+            });
+            print!("{}{}", json, style.end());
+        } else {
+            print!(
+                "{} ({} files, {} bytes){}",
+                babble,
+                group.files.len(),
+                group.size,
+                style.end()
+            );
+            for path in &group.files {
+                print!("    {}{}", path.display(), style.end());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn cert(ikev2: bool, files: &[PathBuf], style: &Style) -> Result<()> {
+    let stdin = [PathBuf::from("-")];
+    let files = if files.is_empty() { &stdin } else { files };
+
+    for path in files {
+        let fpr = cert::fingerprint(&read_path(path)?, ikev2)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        let name = match fpr.subject {
+            Some(subject) => format!("{} ({})", path.display(), subject),
+            None => path.display().to_string(),
+        };
+        print(&fpr.digest, Some(fpr.algo), Some(Path::new(&name)), style)?;
+    }
+
+    Ok(())
+}
+
+fn git(revs: &[String], style: &Style) -> Result<()> {
+    for rev in revs {
+        let object = git::Object::resolve(rev)?;
+        let algo = match object.id.len() {
+            32 => Algorithm::Sha256,
+            _ => Algorithm::Sha1,
+        };
+        let name = format!("{} ({})", rev, object.kind);
+        print(&object.id, Some(algo), Some(Path::new(&name)), style)?;
+    }
+
+    Ok(())
+}
+
+fn fingerprint(files: &[PathBuf], style: &Style) -> Result<()> {
+    let stdin = [PathBuf::from("-")];
+    let files = if files.is_empty() { &stdin } else { files };
+
+    for path in files {
+        let keys = read_path(path)?;
+
+        for line in String::from_utf8_lossy(&keys).lines() {
+            let fpr = match Fingerprint::parse(line) {
+                Some(fpr) => fpr.map_err(|err| format!("{}: {}", path.display(), err))?,
+                None => continue,
+            };
+
+            // ssh-keygen always uses SHA-1 for Bubble Babble fingerprints
+            let babble = babble(&fpr.digest, style);
+            style.copy(&babble);
+            print!("{}{}", fpr.line(&babble), style.end());
+            if style.randomart {
+                let title = format!("{} {}", fpr.name, fpr.bits);
+                print!("{}{}", randomart(&fpr.digest, &title, "SHA1"), style.end());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn known_hosts(
+    host: Option<&str>,
+    compare: Option<&Path>,
+    file: Option<PathBuf>,
+    style: &Style,
+) -> Result<bool> {
+    let file = match file {
+        Some(file) => file,
+        None => PathBuf::from(env::var_os("HOME").ok_or("HOME is not set")?)
+            .join(".ssh")
+            .join("known_hosts"),
+    };
+    let text = read_path(&file)?;
+
+    // Snapshot lines are the output of a previous run, the last
+    // field is the fingerprint and the other fields identify the key.
+    let mut snapshot = BTreeMap::new();
+    if let Some(path) = compare {
+        for line in String::from_utf8_lossy(&read_path(path)?).lines() {
+            if let Some((entry, babble)) = line.trim().rsplit_once(' ') {
+                snapshot.insert(entry.to_string(), babble.to_string());
+            }
+        }
+    }
+    let mut changed = 0;
+
+    let keys = ssh::host_keys(&String::from_utf8_lossy(&text), host)
+        .map_err(|err| format!("{}: {}", file.display(), err))?;
+
+    for key in keys {
+        let hosts = host.unwrap_or(&key.hosts);
+        let name = match &key.marker {
+            Some(marker) => format!("{} {} {}", marker, hosts, key.key_type),
+            None => format!("{} {}", hosts, key.key_type),
+        };
+        let babble = babble(&key.digest, style);
+        style.copy(&babble);
+
+        if compare.is_none() {
+            print!("{} {}{}", name, babble, style.end());
+            continue;
+        }
+        match snapshot.remove(&name) {
+            Some(old) if old == babble => print!("{} {}: OK{}", name, babble, style.end()),
+            Some(old) => {
+                print!("{} {}: CHANGED, was {}{}", name, babble, old, style.end());
+                changed += 1;
+            }
+            None => print!("{} {}: NEW{}", name, babble, style.end()),
+        }
+    }
+
+    if host.is_none() {
+        for name in snapshot.keys() {
+            print!("{}: REMOVED{}", name, style.end());
+        }
+    }
+    if changed > 0 {
+        eprintln!(
+            "bubblebabble: WARNING: {} host key{} changed",
+            changed,
+            if changed == 1 { "" } else { "s" }
+        );
+    }
+
+    Ok(changed == 0)
+}
+
+fn monitor(
+    pin: &Path,
+    interval: u64,
+    count: usize,
+    webhook: Option<&str>,
+    hosts: &[String],
+    style: &Style,
+) -> Result<bool> {
+    let targets = hosts
+        .iter()
+        .map(|host| monitor::Target::parse(host))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Pins are `NAME BABBLE` lines like the snapshots of `known-hosts`,
+    // new ones are written in the plain format to match in any style
+    let mut pins = BTreeMap::new();
+    if pin.exists() {
+        for line in String::from_utf8_lossy(&read_path(pin)?).lines() {
+            if let Some((name, babble)) = line.trim().rsplit_once(' ') {
+                pins.insert(name.to_string(), babble.to_string());
+            }
+        }
+    }
+
+    let alert = |json: serde_json::Value| {
+        if let Some(url) = webhook {
+            if let Err(err) = monitor::webhook(url, &json) {
+                eprintln!("bubblebabble: {}: {}", url, err);
+            }
+        }
+    };
+    let mut failed = false;
+
+    for round in 1.. {
+        let mut changed = 0;
+        let mut new = String::new();
+
+        for target in &targets {
+            let keys = match target.fetch() {
+                Ok(keys) => keys,
+                Err(err) => {
+                    print!("{}: FAILED{}", target, style.end());
+                    eprintln!("bubblebabble: {}: {}", target, err);
+                    alert(serde_json::json!({
+                        "host": target.to_string(),
+                        "error": err.to_string(),
+                    }));
+                    failed = true;
+                    continue;
+                }
+            };
+            for (name, algo, digest) in keys {
+                let babble = babble(&digest, style);
+                match pins.get(&name) {
+                    Some(old) if babble_matches(old, &digest, style) => {
+                        if round == 1 {
+                            print!("{} {}: OK{}", name, babble, style.end());
+                        }
+                    }
+                    Some(old) => {
+                        print!("{} {}: CHANGED, was {}{}", name, babble, old, style.end());
+                        eprintln!("bubblebabble: WARNING: {} changed", name);
+                        alert(serde_json::json!({
+                            "host": name,
+                            "algorithm": algo.name(),
+                            "babble": babble,
+                            "pinned": old,
+                        }));
+                        changed += 1;
+                    }
+                    None => {
+                        print!("{} {}: NEW{}", name, babble, style.end());
+                        let pin = bubblebabble(&digest);
+                        new.push_str(&format!("{} {}\n", name, pin));
+                        pins.insert(name, pin);
+                    }
+                }
+            }
+        }
+
+        if !new.is_empty() {
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(pin)
+                .and_then(|mut file| file.write_all(new.as_bytes()))
+                .map_err(|err| format!("{}: {}", pin.display(), err))?;
+        }
+        if changed > 0 {
+            return Ok(false);
+        }
+        if round == count {
+            break;
+        }
+        thread::sleep(Duration::from_secs(interval));
+    }
+
+    Ok(!failed)
+}
+
+fn verify(
+    expect: Option<String>,
+    algo: Option<Algorithm>,
+    file: Option<PathBuf>,
+    style: &Style,
+) -> Result<bool> {
+    let file = file.unwrap_or_else(|| PathBuf::from("-"));
+    let expect = match expect {
+        Some(expect) => expect,
+        None if file.as_os_str() == "-" => {
+            return Err("cannot read both the expected value and the input from stdin".into())
+        }
+        None => {
+            let mut expect = String::new();
+            io::stdin().read_to_string(&mut expect)?;
+            expect
+        }
+    };
+    let bytes = match algo {
+        Some(algo) => algo.digest_file(&file)?,
+        None => read_path(&file)?,
+    };
+    let expect = expect.trim();
+    let actual = babble(&bytes, style);
+
+    if style.quiet {
+        return Ok(expect == actual);
+    }
+    if expect == actual {
+        print!("{}: OK{}", file.display(), style.end());
+        return Ok(true);
+    }
+
+    print!("{}: FAILED{}", file.display(), style.end());
+    for (i, word) in bubblebabble::diff(expect, &actual)
+        .words()
+        .iter()
+        .enumerate()
+    {
+        if !word.is_match() {
+            print!(
+                "  word {}: expected {}, got {}{}",
+                i + 1,
+                word.a.as_deref().unwrap_or("nothing"),
+                word.b.as_deref().unwrap_or("nothing"),
+                style.end()
+            );
+        }
+    }
+
+    Ok(false)
+}
+
+fn readback(algo: Algorithm, file: &Path, style: &Style) -> Result<bool> {
+    if file.as_os_str() == "-" {
+        return Err("cannot read both the answers and the input from stdin".into());
+    }
+
+    let babble = babble(&algo.digest_file(file)?, style);
+    let words = babble.split([style.separator, ' ']).collect::<Vec<_>>();
+    readback::readback(&words, style.group, io::stdin().lock(), io::stdout())
+}
+
+/// Align the words of two Babble strings by their position.
+fn diff(a: &str, b: &str, color: Color, style: &Style) -> Result<bool> {
+    let diff = bubblebabble::diff(a, b);
+    if style.quiet {
+        return Ok(diff.is_match());
+    }
+
+    let color = match color {
+        Color::Auto => io::stdout().is_terminal(),
+        Color::Always => true,
+        Color::Never => false,
+    };
+
+    for (i, word) in diff.words().iter().enumerate() {
+        let line = format!(
+            "{:>3}  {:<5}  {:<5}",
+            i + 1,
+            word.a.as_deref().unwrap_or(""),
+            word.b.as_deref().unwrap_or("")
+        );
+        let (marker, code) = if word.is_match() {
+            (' ', "32")
+        } else {
+            ('!', "31")
+        };
+        if color {
+            print!(
+                "\x1b[{}m{} {}\x1b[0m{}",
+                code,
+                marker,
+                line.trim_end(),
+                style.end()
+            );
+        } else {
+            print!("{} {}{}", marker, line.trim_end(), style.end());
+        }
+    }
+
+    Ok(diff.is_match())
+}
@@ -0,0 +1,205 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Fetch remote host keys with `ssh-keyscan` and `openssl s_client`.
+//!
+//! The tools and `curl` for the webhook are run from the `PATH`.
+
+use crate::{cert, hash::Algorithm, ssh, Result};
+use std::{
+    fmt,
+    process::{Command, Stdio},
+};
+
+/// A host to monitor, `ssh://host:port` or `tls://host:port`.
+#[derive(Debug, PartialEq)]
+pub struct Target {
+    pub tls: bool,
+    pub host: String,
+    pub port: u16,
+}
+
+impl Target {
+    /// Parse `[ssh://|tls://]host[:port]`, SSH is the default.
+    pub fn parse(s: &str) -> Result<Self> {
+        let (tls, rest) = match s.split_once("://") {
+            Some(("ssh", rest)) => (false, rest),
+            Some(("tls", rest)) => (true, rest),
+            Some((scheme, _)) => return Err(format!("{}: unsupported scheme {}", s, scheme).into()),
+            None => (false, s),
+        };
+        let (host, port) = match rest.rsplit_once(':') {
+            // IPv6 addresses need brackets to specify a port
+            Some((host, port)) if !host.contains(':') || host.ends_with(']') => {
+                let port = port
+                    .parse()
+                    .map_err(|_| format!("{}: invalid port {}", s, port))?;
+                (host, port)
+            }
+            _ => (rest, if tls { 443 } else { 22 }),
+        };
+        let host = host.trim_start_matches('[').trim_end_matches(']');
+        if host.is_empty() {
+            return Err(format!("{}: missing host", s).into());
+        }
+
+        Ok(Target {
+            tls,
+            host: host.to_string(),
+            port,
+        })
+    }
+
+    /// Return `host:port`, with brackets around IPv6 addresses.
+    pub fn address(&self) -> String {
+        if self.host.contains(':') {
+            format!("[{}]:{}", self.host, self.port)
+        } else {
+            format!("{}:{}", self.host, self.port)
+        }
+    }
+
+    /// Fetch the keys of the host and return their names and digests.
+    ///
+    /// SSH host keys are hashed with SHA-1 like `ssh-keygen -B` and TLS
+    /// certificates with SHA-256.
+    pub fn fetch(&self) -> Result<Vec<(String, Algorithm, Vec<u8>)>> {
+        if self.tls {
+            let connect = self.address();
+            let output = run(
+                "openssl",
+                &["s_client", "-connect", &connect, "-servername", &self.host],
+            )?;
+            let pem = first_pem(&output).ok_or("no certificate")?;
+            let fpr = cert::fingerprint(pem.as_bytes(), false)?;
+            return Ok(vec![(format!("{} x509", self), fpr.algo, fpr.digest)]);
+        }
+
+        let port = self.port.to_string();
+        let output = run("ssh-keyscan", &["-p", &port, &self.host])?;
+        let mut keys = ssh::host_keys(&output, None)?
+            .into_iter()
+            .map(|key| {
+                let name = format!("{} {}", self, key.key_type);
+                (name, Algorithm::Sha1, key.digest)
+            })
+            .collect::<Vec<_>>();
+        if keys.is_empty() {
+            return Err("no host keys".into());
+        }
+        keys.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(keys)
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let scheme = if self.tls { "tls" } else { "ssh" };
+        write!(f, "{}://{}", scheme, self.address())
+    }
+}
+
+/// Post a JSON alert to `url` with `curl`.
+pub fn webhook(url: &str, json: &serde_json::Value) -> Result<()> {
+    let body = json.to_string();
+    run(
+        "curl",
+        &[
+            "-fsS",
+            "-X",
+            "POST",
+            "-H",
+            "Content-Type: application/json",
+            "-d",
+            &body,
+            url,
+        ],
+    )
+    .map(|_| ())
+}
+
+/// Return the first PEM certificate of the `openssl s_client` output.
+fn first_pem(output: &str) -> Option<&str> {
+    const END: &str = "-----END CERTIFICATE-----";
+    let start = output.find("-----BEGIN CERTIFICATE-----")?;
+    let end = output[start..].find(END)? + start + END.len();
+    Some(&output[start..end])
+}
+
+/// Run a command without input and return its output.
+///
+/// The error of a failed command includes its standard error.
+fn run(cmd: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(cmd)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| format!("failed to run {}: {}", cmd, err))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(match stderr.trim() {
+            "" => format!("{} failed with {}", cmd, output.status),
+            stderr => format!("{} failed with {}: {}", cmd, output.status, stderr),
+        }
+        .into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target() {
+        let tests = [
+            ("example.com", "ssh://example.com:22"),
+            ("ssh://example.com:2222", "ssh://example.com:2222"),
+            ("tls://example.com", "tls://example.com:443"),
+            ("tls://[2001:db8::1]:8443", "tls://[2001:db8::1]:8443"),
+            ("2001:db8::1", "ssh://[2001:db8::1]:22"),
+        ];
+        for (s, target) in tests.iter() {
+            assert_eq!(Target::parse(s).unwrap().to_string(), *target);
+        }
+        assert_eq!(
+            Target::parse("tls://[2001:db8::1]").unwrap().address(),
+            "[2001:db8::1]:443"
+        );
+        assert_eq!(
+            Target::parse("tls://example.com").unwrap().address(),
+            "example.com:443"
+        );
+        assert!(Target::parse("http://example.com").is_err());
+        assert!(Target::parse("example.com:ssh").is_err());
+
+        let output = "CONNECTED\n-----BEGIN CERTIFICATE-----\nMII\n-----END CERTIFICATE-----\n";
+        assert_eq!(
+            first_pem(output),
+            Some("-----BEGIN CERTIFICATE-----\nMII\n-----END CERTIFICATE-----")
+        );
+    }
+}
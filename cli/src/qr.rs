@@ -0,0 +1,66 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! QR codes for pairing flows where one device scans the output.
+
+use crate::Result;
+use std::path::Path;
+
+/// Render `data` as a QR code for the terminal.
+#[cfg(feature = "qr")]
+pub fn render(data: &[u8]) -> Result<String> {
+    use qrcode::{render::unicode::Dense1x2, QrCode};
+
+    let code = QrCode::new(data)?;
+    let mut image = code
+        .render::<Dense1x2>()
+        .dark_color(Dense1x2::Light)
+        .light_color(Dense1x2::Dark)
+        .build();
+    image.push('\n');
+
+    Ok(image)
+}
+
+/// Write `data` as a QR code to a PNG image at `path`.
+#[cfg(feature = "qr")]
+pub fn write_png(data: &[u8], path: &Path) -> Result<()> {
+    let code = qrcode::QrCode::new(data)?;
+    code.render::<image::Luma<u8>>()
+        .build()
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "qr"))]
+pub fn render(_data: &[u8]) -> Result<String> {
+    Err("QR codes are not supported, enable the qr feature".into())
+}
+
+#[cfg(not(feature = "qr"))]
+pub fn write_png(_data: &[u8], _path: &Path) -> Result<()> {
+    render(&[]).map(|_| ())
+}
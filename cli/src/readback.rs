@@ -0,0 +1,100 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Guided verification of a Babble string that is read aloud.
+
+use crate::Result;
+use std::io::{BufRead, Write};
+
+/// Show `words` in groups of `group` and ask if each group matches.
+///
+/// Enter or `y` confirm a group, `n` marks it as mismatching.  Returns
+/// `true` if all groups were confirmed.
+pub fn readback(
+    words: &[&str],
+    group: usize,
+    mut input: impl BufRead,
+    mut out: impl Write,
+) -> Result<bool> {
+    let groups = words.chunks(group.max(1)).collect::<Vec<_>>();
+    let mut mismatches = Vec::new();
+    let mut line = String::new();
+
+    for (i, words) in groups.iter().enumerate() {
+        let words = words.join(" ");
+        loop {
+            write!(out, "[{}/{}] {} [Y/n] ", i + 1, groups.len(), words)?;
+            out.flush()?;
+
+            line.clear();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                return Err("verification aborted".into());
+            }
+            match line.trim().to_ascii_lowercase().as_str() {
+                "" | "y" | "yes" => break,
+                "n" | "no" => {
+                    mismatches.push((i + 1, words));
+                    break;
+                }
+                _ => writeln!(out, "Please answer y or n.")?,
+            }
+        }
+    }
+
+    writeln!(out)?;
+    if mismatches.is_empty() {
+        writeln!(out, "All {} groups match.", groups.len())?;
+    } else {
+        writeln!(
+            out,
+            "{} of {} groups did NOT match:",
+            mismatches.len(),
+            groups.len()
+        )?;
+        for (i, words) in &mismatches {
+            writeln!(out, "  group {}: {}", i, words)?;
+        }
+    }
+
+    Ok(mismatches.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readback() {
+        let words = ["xigak", "nyryk", "humil", "bosek", "sonax"];
+        let mut out = Vec::new();
+
+        assert!(readback(&words, 2, &b"\ny\nyes\n"[..], &mut out).unwrap());
+        assert!(!readback(&words, 2, &b"y\nfoo\nn\ny\n"[..], &mut out).unwrap());
+        assert!(readback(&words, 2, &b"y\n"[..], &mut out).is_err());
+
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("1 of 3 groups did NOT match:\n  group 2: humil bosek\n"));
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! OpenSSH public keys for `ssh-keygen -B` compatible fingerprints.
+
+#[cfg(feature = "ssh")]
+use crate::hash::Algorithm;
+use crate::Result;
+#[cfg(feature = "ssh")]
+use bubblebabble::{ssh_parse_line, KnownHosts};
+
+/// A public key fingerprint in the format of `ssh-keygen -B`.
+pub struct Fingerprint {
+    /// Size of the key in bits.
+    pub bits: usize,
+    /// SHA-1 digest of the key blob.
+    pub digest: Vec<u8>,
+    pub comment: String,
+    /// Key type as printed by `ssh-keygen`, like `ED25519`.
+    pub name: &'static str,
+}
+
+impl Fingerprint {
+    /// Parse a public key line, returning `None` for empty lines and comments.
+    ///
+    /// Lines of `authorized_keys` and `known_hosts` files are accepted.
+    #[cfg(feature = "ssh")]
+    pub fn parse(line: &str) -> Option<Result<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+
+        Some(ssh_parse_line(line).map_err(Into::into).and_then(|key| {
+            let data = key.key_data();
+            let mpint = data
+                .rsa()
+                .map(|rsa| &rsa.n)
+                .or(data.dsa().map(|dsa| &dsa.p));
+            let bits = match (mpint, key.algorithm().as_str()) {
+                (Some(mpint), _) => mpint.as_positive_bytes().map_or(0, mpint_bits),
+                (None, "ecdsa-sha2-nistp384") => 384,
+                (None, "ecdsa-sha2-nistp521") => 521,
+                (None, _) => 256,
+            };
+
+            Ok(Fingerprint {
+                bits,
+                digest: Algorithm::Sha1.digest(&key.to_bytes()?[..])?,
+                comment: key.comment().to_string(),
+                name: name(key.algorithm().as_str()),
+            })
+        }))
+    }
+
+    #[cfg(not(feature = "ssh"))]
+    pub fn parse(_line: &str) -> Option<Result<Self>> {
+        Some(Err(
+            "SSH keys are not supported, enable the ssh feature".into()
+        ))
+    }
+
+    /// Return the `ssh-keygen -B` line with the Babble of the digest.
+    pub fn line(&self, babble: &str) -> String {
+        let comment = match self.comment.as_str() {
+            "" => "no comment",
+            comment => comment,
+        };
+        format!("{} {} {} ({})", self.bits, babble, comment, self.name)
+    }
+}
+
+/// A host key of a `known_hosts` file or of `ssh-keyscan`.
+pub struct HostKey {
+    /// Optional marker, `@cert-authority` or `@revoked`.
+    pub marker: Option<String>,
+    /// Comma-separated host patterns or the hashed host name.
+    pub hosts: String,
+    pub key_type: String,
+    /// SHA-1 digest of the key blob.
+    pub digest: Vec<u8>,
+}
+
+/// Return the host keys of a `known_hosts` file, optionally only for `host`.
+///
+/// Host names are matched by the library, including wildcards, negated
+/// patterns, and hashed host names.
+#[cfg(feature = "ssh")]
+pub fn host_keys(text: &str, host: Option<&str>) -> Result<Vec<HostKey>> {
+    let known_hosts = text.parse::<KnownHosts>()?;
+    let entries: Vec<_> = match host {
+        Some(host) => known_hosts.find(host).collect(),
+        None => known_hosts.entries().collect(),
+    };
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let key = entry.public_key();
+            Ok(HostKey {
+                marker: entry.marker().map(|marker| marker.as_str().to_string()),
+                hosts: entry.host_patterns().to_string(),
+                key_type: key.algorithm().as_str().to_string(),
+                digest: Algorithm::Sha1.digest(&key.to_bytes()?[..])?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "ssh"))]
+pub fn host_keys(_text: &str, _host: Option<&str>) -> Result<Vec<HostKey>> {
+    Err("SSH keys are not supported, enable the ssh feature".into())
+}
+
+/// Return the name of a key type as printed by `ssh-keygen`.
+#[cfg(feature = "ssh")]
+fn name(algorithm: &str) -> &'static str {
+    match algorithm {
+        "ssh-rsa" => "RSA",
+        "ssh-dss" => "DSA",
+        "ecdsa-sha2-nistp256" | "ecdsa-sha2-nistp384" | "ecdsa-sha2-nistp521" => "ECDSA",
+        "ssh-ed25519" => "ED25519",
+        "sk-ecdsa-sha2-nistp256@openssh.com" => "ECDSA-SK",
+        "sk-ssh-ed25519@openssh.com" => "ED25519-SK",
+        _ => "unknown",
+    }
+}
+
+#[cfg(feature = "ssh")]
+fn mpint_bits(mpint: &[u8]) -> usize {
+    match mpint.iter().position(|&b| b != 0) {
+        Some(i) => ((mpint.len() - i) * 8) - mpint[i].leading_zeros() as usize,
+        None => 0,
+    }
+}
+
+#[cfg(all(test, feature = "ssh", feature = "sha1"))]
+mod tests {
+    use super::*;
+    use bubblebabble::{bubblebabble, ssh_babble};
+
+    /// Output of `ssh-keygen -B` for the keys of `tests/data/keys.pub`.
+    const KEYGEN: &[&str] = &[
+        "256 xefog-gazeg-limug-ridig-tileh-nodyc-baguk-gizih-lomym-rugeh-puxex \
+         ed25519@example.com (ED25519)",
+        "2048 xemes-bygen-gufys-dahiv-ryseh-cihag-tonit-fafec-vohet-hycot-gyxax \
+         rsa@example.com (RSA)",
+        "384 xocif-pidap-tenil-nizut-nucon-sezyt-bizat-pogov-cuhig-cuduc-vexix \
+         ecdsa@example.com (ECDSA)",
+        "1024 xodom-sybar-cubaf-kuten-recyv-dasof-cuhud-denyz-gafug-gyfuc-suxux \
+         dsa@example.com (DSA)",
+    ];
+
+    #[test]
+    fn test_fingerprint() {
+        let keys = include_str!("../../tests/data/keys.pub");
+        let lines = keys.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), KEYGEN.len());
+
+        for (line, keygen) in lines.iter().zip(KEYGEN) {
+            let fpr = Fingerprint::parse(line).unwrap().unwrap();
+            let babble = bubblebabble(&fpr.digest);
+            assert_eq!(fpr.line(&babble), *keygen);
+
+            let key = ssh_parse_line(line).unwrap();
+            assert_eq!(ssh_babble(&key).unwrap(), babble);
+        }
+
+        // Without a comment and with `authorized_keys` options
+        let (key, _) = lines[0].rsplit_once(' ').unwrap();
+        let fpr = Fingerprint::parse(&format!("no-pty {}", key))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            fpr.line(&bubblebabble(&fpr.digest)),
+            "256 xefog-gazeg-limug-ridig-tileh-nodyc-baguk-gizih-lomym-rugeh-puxex \
+             no comment (ED25519)"
+        );
+        assert!(Fingerprint::parse("# comment").is_none());
+        assert!(Fingerprint::parse("ssh-ed25519 AAAA").unwrap().is_err());
+    }
+}
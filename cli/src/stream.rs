@@ -0,0 +1,395 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Streaming conversion of arbitrarily large inputs in constant memory.
+
+use crate::{parse_input, print, Input, Output, Result, Style};
+use bubblebabble::{BabbleError, Decoder, Dump, Encoder};
+use data_encoding::{BASE64, HEXLOWER, HEXLOWER_PERMISSIVE};
+use std::io::{self, BufWriter, Read, Write};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Encode all data from `reader` and print the Babble text.
+pub fn encode(mut reader: impl Read, input: Input, style: &Style) -> Result<()> {
+    let mut chunk = vec![0; CHUNK_SIZE];
+    let mut len = read_chunk(&mut reader, &mut chunk)?;
+
+    // Large inputs are streamed in the format of the first chunk and
+    // fail if a later chunk is not valid in this format
+    let detected = input == Input::Auto;
+    let input = match input {
+        Input::Auto if len == CHUNK_SIZE => detect(&chunk[..len]),
+        input => input,
+    };
+
+    // Small inputs and structured output are handled in memory
+    if len < CHUNK_SIZE
+        || input == Input::Auto
+        || style.json
+        || style.qr
+        || style.qr_png.is_some()
+        || style.randomart
+        || style.copy
+        || !style.options().is_plain()
+    {
+        let mut bytes = chunk[..len].to_vec();
+        reader.read_to_end(&mut bytes)?;
+        return print(&parse_input(&bytes, input)?, None, None, style);
+    }
+
+    let mut input = InputDecoder::new(input);
+    let mut encoder = if style.stable {
+        Encoder::stable()
+    } else {
+        Encoder::new()
+    };
+    let mut out = Wrap::new(io::stdout().lock(), style.wrap);
+    let mut babble = String::new();
+
+    while len > 0 {
+        let bytes = input.decode(&chunk[..len]).map_err(|err| match detected {
+            true => format!("{}, use --input raw to encode it as text", err).into(),
+            false => err,
+        })?;
+        encoder.update(&bytes, &mut babble);
+        out.write_str(&babble)?;
+        babble.clear();
+        len = read_chunk(&mut reader, &mut chunk)?;
+    }
+
+    input.finish()?;
+    encoder.finish(&mut babble);
+    out.write_str(&babble)?;
+    out.finish(style.end())
+}
+
+/// Decode the Babble text from `reader` and print the data.
+///
+/// Whitespace is ignored, so wrapped lines are joined, and consecutive
+/// strings are separated where one ends and the next starts with `x`.
+/// Strings with custom formatting options are decoded line by line.
+pub fn decode(
+    mut reader: impl Read,
+    name: Option<&str>,
+    output: Output,
+    style: &Style,
+) -> Result<()> {
+    let context = |err: BabbleError| match name {
+        Some(name) => format!("{}: {}", name, err),
+        None => err.to_string(),
+    };
+    let stable = style.stable;
+    let mut out = OutputEncoder::new(output, style.end());
+
+    let options = style.options();
+    if !options.is_plain() {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            out.write(&options.decode(line).map_err(context)?)?;
+            out.end()?;
+        }
+        return Ok(());
+    }
+
+    let mut chunk = vec![0; CHUNK_SIZE];
+    let mut decoder = None;
+    let mut segment = String::new();
+    let mut bytes = Vec::new();
+    let mut last = '\0';
+
+    loop {
+        let len = read_chunk(&mut reader, &mut chunk)?;
+
+        for &b in &chunk[..len] {
+            let c = match b {
+                b if b.is_ascii_whitespace() => continue,
+                b if b.is_ascii() => b as char,
+                _ => return Err("invalid non-ASCII input".into()),
+            };
+
+            // Two `x` characters can only occur between two strings
+            if last == 'x' && c == 'x' {
+                let mut decoder = decoder.take().unwrap_or_else(|| new_decoder(stable));
+                decoder.update(&segment, &mut bytes).map_err(context)?;
+                decoder.finish(&mut bytes).map_err(context)?;
+                out.write(&bytes)?;
+                out.end()?;
+                segment.clear();
+                bytes.clear();
+            }
+            segment.push(c);
+            last = c;
+        }
+
+        if !segment.is_empty() {
+            let decoder = decoder.get_or_insert_with(|| new_decoder(stable));
+            decoder.update(&segment, &mut bytes).map_err(context)?;
+            out.write(&bytes)?;
+            segment.clear();
+            bytes.clear();
+        }
+
+        if len == 0 {
+            break;
+        }
+    }
+
+    if let Some(decoder) = decoder {
+        decoder.finish(&mut bytes).map_err(context)?;
+        out.write(&bytes)?;
+        out.end()?;
+    }
+
+    Ok(())
+}
+
+/// Print all data from `reader` as a babbledump.
+pub fn dump(mut reader: impl Read, dump: &Dump) -> Result<()> {
+    let mut chunk = vec![0; dump.line_len()];
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut line = String::new();
+    let mut offset = 0;
+
+    loop {
+        let len = read_chunk(&mut reader, &mut chunk)?;
+        if len == 0 {
+            break;
+        }
+
+        line.clear();
+        dump.write_line(offset, &chunk[..len], &mut line);
+        out.write_all(line.as_bytes())?;
+        offset += len;
+    }
+
+    out.flush()?;
+    Ok(())
+}
+
+/// Detect hex or base64 text in the first chunk of a large input.
+fn detect(data: &[u8]) -> Input {
+    let text = |symbols: &[u8]| {
+        data.iter()
+            .all(|b| b.is_ascii_whitespace() || b == &b'=' || symbols.contains(b))
+    };
+
+    if text(b"0123456789abcdefABCDEF") && !data.contains(&b'=') {
+        Input::Hex
+    } else if text(BASE64.specification().symbols.as_bytes()) {
+        Input::Base64
+    } else {
+        Input::Raw
+    }
+}
+
+fn new_decoder(stable: bool) -> Decoder {
+    if stable {
+        Decoder::stable()
+    } else {
+        Decoder::new()
+    }
+}
+
+/// Fill `chunk` from `reader` and return the length, 0 at the end.
+fn read_chunk(reader: &mut impl Read, chunk: &mut [u8]) -> io::Result<usize> {
+    let mut len = 0;
+
+    while len < chunk.len() {
+        match reader.read(&mut chunk[len..]) {
+            Ok(0) => break,
+            Ok(n) => len += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(len)
+}
+
+/// Convert chunks of hex or base64 input to bytes.
+struct InputDecoder {
+    input: Input,
+    carry: Vec<u8>,
+}
+
+impl InputDecoder {
+    fn new(input: Input) -> Self {
+        InputDecoder {
+            input,
+            carry: Vec::new(),
+        }
+    }
+
+    fn decode(&mut self, chunk: &[u8]) -> Result<Vec<u8>> {
+        let (block, name) = match self.input {
+            Input::Hex => (2, "hex"),
+            Input::Base64 => (4, "base64"),
+            _ => return Ok(chunk.to_vec()),
+        };
+
+        self.carry
+            .extend(chunk.iter().filter(|b| !b.is_ascii_whitespace()));
+        let len = self.carry.len() - (self.carry.len() % block);
+        let bytes = match self.input {
+            Input::Hex => HEXLOWER_PERMISSIVE.decode(&self.carry[..len]),
+            _ => BASE64.decode(&self.carry[..len]),
+        }
+        .map_err(|err| format!("invalid {} input: {}", name, err))?;
+        self.carry.drain(..len);
+
+        Ok(bytes)
+    }
+
+    fn finish(self) -> Result<()> {
+        if self.carry.is_empty() {
+            Ok(())
+        } else {
+            Err("invalid input: truncated".into())
+        }
+    }
+}
+
+/// Print decoded bytes in the selected output format.
+struct OutputEncoder {
+    output: Output,
+    out: BufWriter<io::StdoutLock<'static>>,
+    carry: Vec<u8>,
+    end: char,
+}
+
+impl OutputEncoder {
+    fn new(output: Output, end: char) -> Self {
+        OutputEncoder {
+            output,
+            out: BufWriter::new(io::stdout().lock()),
+            carry: Vec::new(),
+            end,
+        }
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        match self.output {
+            Output::Raw => self.out.write_all(bytes),
+            Output::Hex => self.out.write_all(HEXLOWER.encode(bytes).as_bytes()),
+            Output::Base64 => {
+                self.carry.extend_from_slice(bytes);
+                let len = self.carry.len() - (self.carry.len() % 3);
+                self.out
+                    .write_all(BASE64.encode(&self.carry[..len]).as_bytes())?;
+                self.carry.drain(..len);
+                Ok(())
+            }
+        }
+    }
+
+    /// Terminate the output of a decoded string.
+    fn end(&mut self) -> io::Result<()> {
+        match self.output {
+            Output::Raw => {}
+            Output::Hex => write!(self.out, "{}", self.end)?,
+            Output::Base64 => {
+                self.out.write_all(BASE64.encode(&self.carry).as_bytes())?;
+                write!(self.out, "{}", self.end)?;
+                self.carry.clear();
+            }
+        }
+        self.out.flush()
+    }
+}
+
+/// Wrap text output after a number of columns, 0 disables wrapping.
+pub struct Wrap<W: Write> {
+    out: BufWriter<W>,
+    width: usize,
+    column: usize,
+}
+
+impl<W: Write> Wrap<W> {
+    pub fn new(out: W, width: usize) -> Self {
+        Wrap {
+            out: BufWriter::new(out),
+            width,
+            column: 0,
+        }
+    }
+
+    pub fn write_str(&mut self, s: &str) -> io::Result<()> {
+        if self.width == 0 {
+            return self.out.write_all(s.as_bytes());
+        }
+
+        let mut s = s.as_bytes();
+        while !s.is_empty() {
+            if self.column == self.width {
+                self.out.write_all(b"\n")?;
+                self.column = 0;
+            }
+            let len = s.len().min(self.width - self.column);
+            self.out.write_all(&s[..len])?;
+            self.column += len;
+            s = &s[len..];
+        }
+
+        Ok(())
+    }
+
+    /// Terminate the last line with `end` and flush the output.
+    pub fn finish(mut self, end: char) -> Result<()> {
+        write!(self.out, "{}", end)?;
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream() {
+        let mut buf = Vec::new();
+        let mut out = Wrap::new(&mut buf, 5);
+        out.write_str("xigak-nyr").unwrap();
+        out.write_str("yk-hoxex").unwrap();
+        out.finish('\n').unwrap();
+        assert_eq!(buf, b"xigak\n-nyry\nk-hox\nex\n");
+
+        let mut input = InputDecoder::new(Input::Hex);
+        assert_eq!(input.decode(b"50 6").unwrap(), b"P");
+        assert_eq!(input.decode(b"9\n6e").unwrap(), b"in");
+        input.finish().unwrap();
+        assert!(InputDecoder::new(Input::Hex).decode(b"5x").is_err());
+
+        let mut input = InputDecoder::new(Input::Base64);
+        assert_eq!(input.decode(b"UGlu").unwrap(), b"Pin");
+        assert!(input.decode(b"ZQ").unwrap().is_empty());
+        assert!(input.finish().is_err());
+
+        assert!(detect(b"50696e65\n6170706c65") == Input::Hex);
+        assert!(detect(b"50696e65\nUGluZQ==") == Input::Base64);
+        assert!(detect(b"Pine apple!") == Input::Raw);
+    }
+}
@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Babble columns of CSV and TSV tables.
+
+use crate::{parse_input, Input, Result};
+use bubblebabble::Options;
+use std::io::{Read, Write};
+
+/// Options of a table with a column of data to encode.
+pub struct Table<'a> {
+    /// Header name or 1-based number of the column with the data.
+    pub column: &'a str,
+    /// Header of the added Babble column.
+    pub name: &'a str,
+    /// Field delimiter, `,` for CSV or a tab for TSV.
+    pub delimiter: u8,
+    /// The first row is a header.
+    pub header: bool,
+    /// Row terminator, a newline or NUL with `-z`.
+    pub end: u8,
+}
+
+impl Table<'_> {
+    /// Copy the table from `reader` to `writer` and append a column with
+    /// the Babble of the data column to each row.
+    ///
+    /// Rows with an empty data field get an empty Babble field.
+    pub fn annotate(
+        &self,
+        reader: impl Read,
+        writer: impl Write,
+        input: Input,
+        options: &Options,
+    ) -> Result<()> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(self.delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(reader);
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(self.delimiter)
+            .terminator(csv::Terminator::Any(self.end))
+            .flexible(true)
+            .from_writer(writer);
+        let mut records = reader.records();
+
+        let index = match self.column.parse::<usize>() {
+            Ok(number) if number > 0 => Some(number - 1),
+            Ok(_) => return Err("column numbers start with 1".into()),
+            Err(_) if !self.header => return Err("named columns need a header".into()),
+            Err(_) => None,
+        };
+        let index = match (index, self.header) {
+            (index, true) => {
+                let mut header = match records.next() {
+                    Some(header) => header?,
+                    None => return Ok(()),
+                };
+                let index = index
+                    .or_else(|| header.iter().position(|name| name == self.column))
+                    .ok_or_else(|| format!("unknown column: {}", self.column))?;
+                header.push_field(self.name);
+                writer.write_record(&header)?;
+                index
+            }
+            (index, false) => index.unwrap_or_default(),
+        };
+
+        for record in records {
+            let mut record = record?;
+            let line = record.position().map(|pos| pos.line()).unwrap_or_default();
+            let field = record.get(index).unwrap_or_default().trim();
+            let babble = if field.is_empty() {
+                String::new()
+            } else {
+                let bytes = parse_input(field.as_bytes(), input)
+                    .map_err(|err| format!("line {}: {}", line, err))?;
+                options.encode(&bytes)
+            };
+            record.push_field(&babble);
+            writer.write_record(&record)?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::sink;
+
+    #[test]
+    fn test_table() {
+        let csv = "host,key\nalpha,50696e656170706c65\nbeta,\n\"g,amma\",UGluZWFwcGxl\n";
+        let mut table = Table {
+            column: "key",
+            name: "babble",
+            delimiter: b',',
+            header: true,
+            end: b'\n',
+        };
+        let mut out = Vec::new();
+        table
+            .annotate(csv.as_bytes(), &mut out, Input::Auto, &Options::new())
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "host,key,babble\n\
+             alpha,50696e656170706c65,xigak-nyryk-humil-bosek-sonax\n\
+             beta,,\n\
+             \"g,amma\",UGluZWFwcGxl,xigak-nyryk-humil-bosek-sonax\n"
+        );
+
+        table.column = "2";
+        table.delimiter = b'\t';
+        table.header = false;
+        let options = Options::new().stable(true);
+        assert!(table
+            .annotate(&b"a\t0102\nb\tzz\n"[..], sink(), Input::Hex, &options)
+            .is_err());
+        let mut out = Vec::new();
+        table
+            .annotate(&b"a\t0102\n"[..], &mut out, Input::Hex, &options)
+            .unwrap();
+        assert_eq!(
+            out,
+            format!("a\t0102\t{}\n", options.encode(&[1, 2])).as_bytes()
+        );
+
+        table.column = "key";
+        assert!(table
+            .annotate(csv.as_bytes(), sink(), Input::Auto, &Options::new())
+            .is_err());
+    }
+}
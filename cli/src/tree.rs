@@ -0,0 +1,113 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Directory traversal for fingerprinting trees of files.
+
+use crate::Result;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Return the paths of all files below `dir`, relative to it and sorted.
+///
+/// Symbolic links to files are included but linked directories are not
+/// followed to avoid loops.
+pub fn files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    walk(dir, Path::new(""), &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+/// Return the relative path joined with `/`, as hashed into the combined
+/// digest of the tree, independent of the platform.
+pub fn name(path: &Path) -> Result<String> {
+    let names = path
+        .components()
+        .map(|component| {
+            component
+                .as_os_str()
+                .to_str()
+                .ok_or_else(|| format!("{}: invalid UTF-8 file name", path.display()))
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(names.join("/"))
+}
+
+fn walk(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let path = if dir.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(dir)
+    };
+    let entries = fs::read_dir(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    for entry in entries {
+        let entry = entry?;
+        let relative = dir.join(entry.file_name());
+        let file_type = entry.file_type()?;
+
+        if file_type.is_dir() {
+            walk(root, &relative, files)?;
+        } else if file_type.is_file() || entry.path().is_file() {
+            files.push(relative);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files() {
+        let dir = std::env::temp_dir().join(format!("bubblebabble-tree-{}", std::process::id()));
+        fs::create_dir_all(dir.join("a").join("b")).unwrap();
+        for name in &["c", "a/d", "a/b/e"] {
+            fs::write(dir.join(name), name).unwrap();
+        }
+
+        let files = files(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names = files
+            .unwrap()
+            .iter()
+            .map(|path| name(path).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["a/b/e", "a/d", "c"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_name() {
+        use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+        let path = Path::new("a").join(OsStr::from_bytes(b"\xff"));
+        assert!(name(&path).is_err());
+    }
+}
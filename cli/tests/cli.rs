@@ -0,0 +1,281 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use bubblebabble::bubblebabble;
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Run the tool with `args` and `stdin`, and return the output.
+fn run(args: &[&str], stdin: &[u8]) -> String {
+    let (success, stdout) = output(args, stdin);
+    assert!(success);
+    stdout
+}
+
+/// Run the tool and return whether it succeeded and its output.
+fn output(args: &[&str], stdin: &[u8]) -> (bool, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_bubblebabble"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut pipe = child.stdin.take().unwrap();
+    let input = stdin.to_vec();
+    let writer = std::thread::spawn(move || pipe.write_all(&input));
+    let output = child.wait_with_output().unwrap();
+    writer.join().unwrap().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn test_arguments() {
+    assert_eq!(run(&["test"], b""), bubblebabble(b"test") + "\n");
+    assert_eq!(
+        run(&["1234567890"], b""),
+        bubblebabble(b"1234567890") + "\n"
+    );
+    assert_eq!(
+        run(&["--input", "hex", "1234567890"], b""),
+        bubblebabble(&[0x12, 0x34, 0x56, 0x78, 0x90]) + "\n"
+    );
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn test_hash() {
+    let dir = std::env::temp_dir().join(format!("bubblebabble-hash-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (a, b) = (dir.join("a"), dir.join("b"));
+    std::fs::write(&a, "Pine").unwrap();
+    std::fs::write(&b, "apple").unwrap();
+    let (a, b) = (a.to_str().unwrap(), b.to_str().unwrap());
+    let missing = dir.join("missing");
+    let missing = missing.to_str().unwrap();
+
+    let single = run(&["hash", a], b"");
+    let (success, several) = output(&["hash", a, missing, b], b"");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // Several files are named like sha256sum, unreadable ones are skipped
+    assert!(!success);
+    let lines = several.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0], format!("{}  {}", single.trim(), a));
+    assert!(lines[1].ends_with(&format!("  {}", b)));
+}
+
+#[cfg(feature = "sha2")]
+#[test]
+fn test_check() {
+    let dir = std::env::temp_dir().join(format!("bubblebabble-check-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let file = dir.join("file");
+    std::fs::write(&file, "Pineapple").unwrap();
+    let file = file.to_str().unwrap();
+
+    // Sums are compared by their digests, not by the presentation
+    let mut results = Vec::new();
+    for flags in &[&[][..], &["--uppercase", "--separator", "."], &["--stable"]] {
+        let sums = run(&[&["hash", "-r"], *flags, &[file]].concat(), b"");
+        results.push(output(&["check"], sums.as_bytes()));
+    }
+    let sums = run(&["hash", "-r", "--stable"], b"Pine").replace("  -", &format!("  {}", file));
+    let mismatch = output(&["check"], sums.as_bytes());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    for (success, stdout) in results {
+        assert!(success);
+        assert_eq!(stdout, format!("{}: OK\n", file));
+    }
+    assert_eq!(mismatch, (false, format!("{}: FAILED\n", file)));
+}
+
+#[test]
+fn test_stream_encode() {
+    for &len in &[CHUNK_SIZE - 1, CHUNK_SIZE, CHUNK_SIZE + 1, 3 * CHUNK_SIZE] {
+        let raw = (0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>();
+        assert_eq!(run(&[], &raw), bubblebabble(&raw) + "\n");
+
+        // Hex and base64 are detected in the first chunk and streamed
+        let hex = raw.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        assert_eq!(run(&[], hex.as_bytes()), bubblebabble(&raw) + "\n");
+        assert_eq!(
+            run(&["--input", "hex"], hex.as_bytes()),
+            bubblebabble(&raw) + "\n"
+        );
+        let base64 = data_encoding::BASE64_MIME.encode(&raw);
+        assert_eq!(run(&[], base64.as_bytes()), bubblebabble(&raw) + "\n");
+
+        // Text that is not hex after the first chunk fails
+        let text = hex.clone() + "!";
+        assert!(!output(&[], text.as_bytes()).0);
+        assert_eq!(
+            run(&["--input", "raw"], text.as_bytes()),
+            bubblebabble(text.as_bytes()) + "\n"
+        );
+    }
+}
+
+#[test]
+fn test_stream_decode() {
+    let raw = (0..3 * CHUNK_SIZE).map(|i| i as u8).collect::<Vec<_>>();
+    let babble = run(&["--wrap", "76", "--input", "raw"], &raw);
+    assert!(babble.lines().all(|line| line.len() <= 76));
+    assert_eq!(babble.replace('\n', ""), bubblebabble(&raw));
+
+    let hex = run(&["decode"], babble.as_bytes());
+    assert_eq!(hex.trim(), data_encoding::HEXLOWER.encode(&raw));
+
+    let two = format!("{}\n{}\n", bubblebabble(b"Pine"), bubblebabble(b"apple"));
+    assert_eq!(
+        run(&["decode", "--output", "raw"], two.as_bytes()),
+        "Pineapple"
+    );
+}
+
+#[test]
+fn test_zero() {
+    let a = bubblebabble(b"Pineapple");
+    let b = bubblebabble(b"Pineapplf");
+    let (success, diff) = output(&["-z", "diff", &a, &b], b"");
+    assert!(!success);
+    assert_eq!(diff.matches('\0').count(), 5);
+    assert!(!diff.contains('\n'));
+
+    assert_eq!(
+        run(&["-z", "decode", &bubblebabble(b"Pine"), &a], b""),
+        "50696e65\x0050696e656170706c65\x00"
+    );
+    assert_eq!(
+        run(
+            &["-z", "--input", "raw", "table", "-c", "1", "--no-header"],
+            b"Pine\n"
+        ),
+        format!("Pine,{}\0", bubblebabble(b"Pine"))
+    );
+}
+
+#[cfg(all(feature = "ssh", feature = "sha1"))]
+#[test]
+fn test_known_hosts() {
+    let known_hosts = b"# comment\n\
+        *.example.com,!bad.example.com ssh-ed25519 \
+        AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX\n";
+    let babble = "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox";
+
+    assert_eq!(
+        run(&["known-hosts", "-"], known_hosts),
+        format!("*.example.com,!bad.example.com ssh-ed25519 {}\n", babble)
+    );
+    assert_eq!(
+        run(
+            &["known-hosts", "--host", "WWW.Example.COM", "-"],
+            known_hosts
+        ),
+        format!("WWW.Example.COM ssh-ed25519 {}\n", babble)
+    );
+    assert_eq!(
+        run(
+            &["known-hosts", "--host", "bad.example.com", "-"],
+            known_hosts
+        ),
+        ""
+    );
+    assert_eq!(
+        run(&["known-hosts", "--host", "example.org", "-"], known_hosts),
+        ""
+    );
+}
+
+#[cfg(all(unix, feature = "ssh", feature = "sha1"))]
+#[test]
+fn test_monitor() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // A fake ssh-keyscan in the PATH prints a key or fails for the host
+    let dir = std::env::temp_dir().join(format!("bubblebabble-monitor-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let keyscan = dir.join("ssh-keyscan");
+    std::fs::write(
+        &keyscan,
+        "#!/bin/sh\n\
+         case $3 in fail*) echo unreachable >&2; exit 1;; esac\n\
+         echo \"$3 ssh-ed25519 \
+         AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&keyscan, std::fs::Permissions::from_mode(0o755)).unwrap();
+    let path = format!("{}:{}", dir.display(), std::env::var("PATH").unwrap());
+    let pin = dir.join("pins");
+    let monitor = |flags: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_bubblebabble"))
+            .args(["monitor", "--count", "1", "--pin", pin.to_str().unwrap()])
+            .args(flags)
+            .env("PATH", &path)
+            .output()
+            .unwrap();
+        (
+            output.status.success(),
+            String::from_utf8(output.stdout).unwrap(),
+        )
+    };
+
+    let babble = "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox";
+    let first = monitor(&["example.com"]);
+    // Pins match in any format of the output
+    let again = monitor(&["--uppercase", "--separator", ":", "example.com"]);
+    let stable = monitor(&["--stable", "example.com"]);
+    let failed = monitor(&["fail.example.com", "example.com"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let name = "ssh://example.com:22 ssh-ed25519";
+    assert_eq!(first, (true, format!("{} {}: NEW\n", name, babble)));
+    assert_eq!(
+        again,
+        (
+            true,
+            format!("{} {}: OK\n", name, babble.to_uppercase().replace('-', ":"))
+        )
+    );
+    assert!(stable.0);
+    assert!(stable.1.ends_with(": OK\n"));
+    assert_eq!(
+        failed,
+        (
+            false,
+            format!(
+                "ssh://fail.example.com:22: FAILED\n{} {}: OK\n",
+                name, babble
+            )
+        )
+    );
+}
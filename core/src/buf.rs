@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Encoding of non-contiguous `bytes::Buf` buffers.
+
+use crate::Encoder;
+use alloc::string::String;
+use bytes::Buf;
+
+impl Encoder {
+    /// Encode the remaining bytes of `buf` and append the generated
+    /// text to `babble`.
+    ///
+    /// The chunks of chained or segmented buffers are encoded in place
+    /// without copying them into a contiguous buffer first.
+    pub fn update_buf(&mut self, mut buf: impl Buf, babble: &mut String) {
+        while buf.has_remaining() {
+            let chunk = buf.chunk();
+            let len = chunk.len();
+            self.update(chunk, babble);
+            buf.advance(len);
+        }
+    }
+}
+
+/// Convert the remaining bytes of `buf` to Babble.
+///
+/// # Examples
+///
+/// ```rust
+/// use bytes::Buf;
+///
+/// let buf = (&b"Pine"[..]).chain(&b"apple"[..]);
+/// assert_eq!(bubblebabble_core::bubblebabble_buf(buf), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+pub fn bubblebabble_buf(buf: impl Buf) -> String {
+    let mut babble = String::with_capacity((buf.remaining() / 2 + 1) * 6);
+    let mut encoder = Encoder::new();
+    encoder.update_buf(buf, &mut babble);
+    encoder.finish(&mut babble);
+    babble
+}
+
+/// Convert the remaining bytes of `buf` to stable Babble.
+pub fn stablebabble_buf(buf: impl Buf) -> String {
+    let mut babble = String::new();
+    let mut encoder = Encoder::stable();
+    encoder.update_buf(buf, &mut babble);
+    encoder.finish(&mut babble);
+    babble
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubblebabble, stablebabble};
+    use bytes::{Bytes, BytesMut};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_buf() {
+        let data = b"The quick brown fox jumps over the lazy dog";
+        let chained = Bytes::from_static(&data[..7])
+            .chain(BytesMut::from(&data[7..20]))
+            .chain(&data[20..]);
+        assert_eq!(bubblebabble_buf(chained), bubblebabble(data));
+
+        // A ring buffer that wraps around is split into two chunks
+        let mut ring = VecDeque::with_capacity(16);
+        ring.extend(&[0; 12]);
+        ring.drain(..12);
+        ring.extend(&[0, 0, 0, 0, 0, 0, 0, 0, 1, 2]);
+        assert_eq!(
+            stablebabble_buf(ring),
+            stablebabble(&[0, 0, 0, 0, 0, 0, 0, 0, 1, 2])
+        );
+    }
+}
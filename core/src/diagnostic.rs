@@ -0,0 +1,199 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Decode diagnostics with `miette`.
+
+use crate::BabbleError;
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+use std::{
+    boxed::Box,
+    error, fmt,
+    string::{String, ToString},
+};
+
+impl BabbleError {
+    /// Attach the decoded string to the error for labeled diagnostics.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use miette::Diagnostic;
+    ///
+    /// let babble = "xigak-nyryk-humil-bosek-sanax";
+    /// let err = bubblebabble_core::debabble(babble).unwrap_err().diagnostic(babble);
+    /// let label = err.labels().unwrap().next().unwrap();
+    /// assert_eq!(label.label(), Some("checksum mismatch in this word"));
+    /// assert_eq!(&babble[label.offset()..][..label.len()], "sanax");
+    /// ```
+    pub fn diagnostic(self, babble: impl Into<String>) -> BabbleDiagnostic {
+        BabbleDiagnostic {
+            babble: babble.into(),
+            error: self,
+        }
+    }
+
+    fn label(&self, babble: Option<&str>) -> Option<LabeledSpan> {
+        match *self {
+            BabbleError::InvalidCharacter(c, offset) => Some(LabeledSpan::new(
+                Some("invalid character".to_string()),
+                offset,
+                c.len_utf8(),
+            )),
+            BabbleError::ChecksumMismatch(offset) => {
+                // The tuple may start within the word, label the whole word
+                let (start, len) = babble
+                    .filter(|babble| offset < babble.len())
+                    .map(|babble| {
+                        let is_word = |c: char| c.is_ascii_alphanumeric();
+                        let start = babble[..offset].rfind(|c| !is_word(c)).map_or(0, |i| i + 1);
+                        let end = babble[offset..]
+                            .find(|c| !is_word(c))
+                            .map_or(babble.len(), |i| offset + i);
+                        (start, end - start)
+                    })
+                    .unwrap_or((offset, 1));
+                Some(LabeledSpan::new(
+                    Some("checksum mismatch in this word".to_string()),
+                    start,
+                    len,
+                ))
+            }
+            BabbleError::InvalidLength(len) => Some(LabeledSpan::new(
+                Some("incomplete or trailing words".to_string()),
+                0,
+                babble.map_or(len, str::len),
+            )),
+            BabbleError::InvalidAlphabet | BabbleError::BufferTooSmall(_) => None,
+        }
+    }
+}
+
+impl Diagnostic for BabbleError {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let code = match self {
+            BabbleError::InvalidLength(_) => "bubblebabble::invalid_length",
+            BabbleError::InvalidCharacter(..) => "bubblebabble::invalid_character",
+            BabbleError::ChecksumMismatch(_) => "bubblebabble::checksum_mismatch",
+            BabbleError::InvalidAlphabet => "bubblebabble::invalid_alphabet",
+            BabbleError::BufferTooSmall(_) => "bubblebabble::buffer_too_small",
+        };
+        Some(Box::new(code))
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        let help = match self {
+            BabbleError::InvalidLength(_) => "check for missing or extra words",
+            BabbleError::InvalidCharacter(..) => {
+                "words consist of the letters aeiouy and bcdfghklmnprstvzx"
+            }
+            BabbleError::ChecksumMismatch(_) => "this or an earlier word was mistyped",
+            BabbleError::InvalidAlphabet => {
+                "use 6 vowels and 17 consonants of distinct lowercase letters other than w"
+            }
+            BabbleError::BufferTooSmall(_) => "use a buffer of a third of the string length",
+        };
+        Some(Box::new(help))
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = self.label(None)?;
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+/// A `BabbleError` with the decoded string as the source code of the
+/// `miette` diagnostic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BabbleDiagnostic {
+    babble: String,
+    error: BabbleError,
+}
+
+impl BabbleDiagnostic {
+    /// Return the error.
+    pub fn error(&self) -> &BabbleError {
+        &self.error
+    }
+}
+
+impl fmt::Display for BabbleDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl error::Error for BabbleDiagnostic {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl Diagnostic for BabbleDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.code()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.error.help()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&self.babble)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let label = self.error.label(Some(&self.babble))?;
+        Some(Box::new(std::iter::once(label)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::debabble;
+    use miette::Diagnostic;
+
+    #[test]
+    fn test_diagnostic() {
+        let tests = [
+            ("xigak-nyryk-humil-bosek-sonax", None),
+            ("xigak-nyryk-humil-bosek-sanax", Some((24, 5))),
+            ("xigak-nyryk-hum!l-bosek-sonax", Some((15, 1))),
+            ("xigak-nyryk-humil", Some((0, 17))),
+        ];
+
+        for (babble, span) in tests.iter() {
+            let err = match debabble(babble) {
+                Ok(_) => continue,
+                Err(err) => err.diagnostic(*babble),
+            };
+            let label = err.labels().unwrap().next().unwrap();
+            assert_eq!(Some((label.offset(), label.len())), *span);
+            assert!(err.source_code().is_some());
+            assert!(err.code().is_some());
+        }
+    }
+}
@@ -0,0 +1,1045 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! The Bubble Babble algorithm without the standard library.
+//!
+//! This crate contains the encoder and decoder of the `bubblebabble`
+//! crate for embedded and FFI consumers that do not need its
+//! integrations.  It only requires `alloc` for the returned strings and
+//! byte vectors.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble_core::{bubblebabble, debabble};
+//!
+//! let babble = bubblebabble(b"Pineapple");
+//! assert_eq!(babble, "xigak-nyryk-humil-bosek-sonax");
+//! assert_eq!(debabble(&babble).unwrap(), b"Pineapple");
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+#[cfg(feature = "bytes")]
+mod buf;
+#[cfg(feature = "diagnostics")]
+mod diagnostic;
+mod prefix;
+#[cfg(feature = "zeroize")]
+mod secret;
+
+#[cfg(feature = "bytes")]
+pub use crate::buf::{bubblebabble_buf, stablebabble_buf};
+#[cfg(feature = "diagnostics")]
+pub use crate::diagnostic::BabbleDiagnostic;
+pub use crate::prefix::{babble_prefix, BabblePrefix};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{error, fmt, mem};
+
+/// The vowels of the encoding.
+pub const VOWELS: [char; 6] = ['a', 'e', 'i', 'o', 'u', 'y'];
+/// The consonants of the encoding, `x` only appears at the ends.
+pub const CONSONANTS: [char; 17] = [
+    'b', 'c', 'd', 'f', 'g', 'h', 'k', 'l', 'm', 'n', 'p', 'r', 's', 't', 'v', 'z', 'x',
+];
+
+/// Maximum counter of repeated words in the stable format, longer runs
+/// are split so that decoding untrusted input stays bounded.
+const MAX_REPEAT: usize = u16::MAX as usize;
+
+/// Error returned when decoding an invalid Babble string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BabbleError {
+    /// The string does not have a valid Babble length.
+    InvalidLength(usize),
+    /// An unexpected character was found at the given byte offset.
+    InvalidCharacter(char, usize),
+    /// The checksum of the tuple at the given byte offset does not match.
+    ChecksumMismatch(usize),
+    /// The custom alphabet does not consist of 6 vowels and 17 consonants.
+    InvalidAlphabet,
+    /// The output buffer of the given length is too small for the bytes.
+    BufferTooSmall(usize),
+}
+
+impl fmt::Display for BabbleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BabbleError::InvalidLength(len) => write!(f, "invalid length {}", len),
+            BabbleError::InvalidCharacter(c, offset) => {
+                write!(f, "invalid character '{}' at offset {}", c, offset)
+            }
+            BabbleError::ChecksumMismatch(offset) => {
+                write!(f, "checksum mismatch at offset {}", offset)
+            }
+            BabbleError::InvalidAlphabet => write!(f, "invalid alphabet"),
+            BabbleError::BufferTooSmall(len) => write!(f, "buffer of {} bytes too small", len),
+        }
+    }
+}
+
+impl error::Error for BabbleError {}
+
+/// Convert bytes to Bubble Babble `String`.
+///
+/// This is the standard and human-readable format.  The Bubble Babble
+/// includes a checksum that is carried through each generated word.
+pub fn bubblebabble(bytes: &[u8]) -> String {
+    encode(bytes, Encoder::new())
+}
+
+/// Convert the bytes of an iterator to Bubble Babble `String`.
+///
+/// The bytes are encoded as they are produced, for sources that cannot
+/// provide a contiguous slice, like decompressors or parsers.
+///
+/// ```rust
+/// use bubblebabble_core::bubblebabble_from_iter;
+///
+/// let bytes = b"Pineapple".iter().copied();
+/// assert_eq!(bubblebabble_from_iter(bytes), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+pub fn bubblebabble_from_iter(bytes: impl IntoIterator<Item = u8>) -> String {
+    let mut babble = String::new();
+    let mut encoder = Encoder::new();
+    for byte in bytes {
+        encoder.update(&[byte], &mut babble);
+    }
+    encoder.finish(&mut babble);
+    babble
+}
+
+/// Convert the bytes of a fallible iterator to Bubble Babble `String`.
+///
+/// Encoding stops at the first error, which is returned, so the bytes of
+/// a reader can be encoded without reading them into a buffer first.
+///
+/// ```rust
+/// use bubblebabble_core::bubblebabble_try_from_iter;
+/// use std::io::Read;
+///
+/// let reader = &b"Pineapple"[..];
+/// let babble = bubblebabble_try_from_iter(reader.bytes()).unwrap();
+/// assert_eq!(babble, "xigak-nyryk-humil-bosek-sonax");
+/// ```
+pub fn bubblebabble_try_from_iter<E>(
+    bytes: impl IntoIterator<Item = Result<u8, E>>,
+) -> Result<String, E> {
+    let mut babble = String::new();
+    let mut encoder = Encoder::new();
+    for byte in bytes {
+        encoder.update(&[byte?], &mut babble);
+    }
+    encoder.finish(&mut babble);
+    Ok(babble)
+}
+
+/// Convert bytes to stable Babble `String`.
+///
+/// This modified format lacks the checksum but keeps every word
+/// stable as they don't include the state.  It also compresses repeated
+/// words by printing them with a prepended counter of at most 65535.
+pub fn stablebabble(bytes: &[u8]) -> String {
+    encode(bytes, Encoder::stable())
+}
+
+/// Convert bytes to stable Babble `String` without compression.
+///
+/// The words are the same as in `stablebabble` but repeated words are
+/// neither counted nor replaced by `wa`, so the output has a fixed width
+/// and the Nth word always encodes the Nth pair of bytes, which keeps
+/// columns of fingerprints aligned for diffs.  It is decoded by
+/// `debabble_stable`.
+///
+/// ```rust
+/// use bubblebabble_core::{debabble_stable, stablebabble, stablebabble_uncompressed};
+///
+/// let bytes = [0, 0, 0, 0, 0, 1];
+/// assert_eq!(stablebabble(&bytes), "xebab-2wa-caxax");
+/// assert_eq!(stablebabble_uncompressed(&bytes), "xebab-babab-babab-caxax");
+/// assert_eq!(debabble_stable("xebab-babab-babab-caxax").unwrap(), bytes);
+/// ```
+pub fn stablebabble_uncompressed(bytes: &[u8]) -> String {
+    encode(bytes, Encoder::stable_uncompressed())
+}
+
+/// Convert a Bubble Babble string back to bytes.
+///
+/// The checksum that is carried through each word is verified and a
+/// mismatch is returned as `BabbleError::ChecksumMismatch`.
+pub fn debabble(babble: &str) -> Result<Vec<u8>, BabbleError> {
+    decode(babble, Decoder::new())
+}
+
+/// Convert a stable Babble string back to bytes.
+///
+/// Repeated words that have been compressed with a prepended counter or
+/// the `wa` shorthand are expanded before decoding.
+pub fn debabble_stable(babble: &str) -> Result<Vec<u8>, BabbleError> {
+    decode(babble, Decoder::stable())
+}
+
+/// Convert a Bubble Babble string to stable Babble.
+///
+/// The checksum of the input is verified before the bytes are encoded
+/// again, so tools that only handle the strings never see the data.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble_core::*;
+///
+/// let classic = "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-cixux";
+/// assert_eq!(restabilize(classic).unwrap(), "xebab-7wa-caxax");
+/// assert_eq!(destabilize("xebab-7wa-caxax").unwrap(), classic);
+/// ```
+pub fn restabilize(classic: &str) -> Result<String, BabbleError> {
+    debabble(classic).map(|bytes| stablebabble(&bytes))
+}
+
+/// Convert a stable Babble string to Bubble Babble with a checksum.
+pub fn destabilize(stable: &str) -> Result<String, BabbleError> {
+    debabble_stable(stable).map(|bytes| bubblebabble(&bytes))
+}
+
+/// Convert bytes to Bubble Babble with an initial checksum seed and
+/// return the final seed.
+///
+/// The checksum seed of the format starts with 1 and is updated by each
+/// word.  Protocols that split data into several Babble segments can
+/// pass the final seed of a segment to the next one, so the checksum is
+/// carried across the segments and verified end to end by
+/// `debabble_seeded`.  The seed is taken modulo 36.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble_core::*;
+///
+/// let (first, seed) = bubblebabble_seeded(b"Pine", 1);
+/// let (second, _) = bubblebabble_seeded(b"apple", seed);
+/// assert_eq!(first, bubblebabble(b"Pine"));
+/// assert_eq!(second, "xumil-bosek-sonax");
+/// assert_eq!(debabble_seeded(&second, seed).unwrap().0, b"apple");
+/// assert!(debabble_seeded(&second, 1).is_err());
+/// ```
+pub fn bubblebabble_seeded(bytes: &[u8], seed: u8) -> (String, u8) {
+    let mut babble = String::with_capacity(((bytes.len() / 2) + 1) * 6);
+    let mut encoder = Encoder::seeded(seed);
+    encoder.update(bytes, &mut babble);
+    let seed = encoder.seed();
+    encoder.finish(&mut babble);
+    (babble, seed)
+}
+
+/// Convert a Bubble Babble string with an initial checksum seed back
+/// to bytes and return the final seed.
+pub fn debabble_seeded(babble: &str, seed: u8) -> Result<(Vec<u8>, u8), BabbleError> {
+    let mut bytes = Vec::with_capacity(babble.len() / 3);
+    let mut decoder = Decoder::seeded(seed);
+    decoder.update(babble, &mut bytes)?;
+    let seed = decoder.seed();
+    decoder.finish(&mut bytes)?;
+    Ok((bytes, seed))
+}
+
+/// Check if `babble` is the Bubble Babble of `bytes`.
+///
+/// The expected text is generated word by word and compared with the
+/// string on the fly, without allocating the Babble or decoding the
+/// bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble_core::babble_eq;
+///
+/// assert!(babble_eq("xigak-nyryk-humil-bosek-sonax", b"Pineapple"));
+/// assert!(!babble_eq("xigak-nyryk-humil-bosek-sonax", b"Pine"));
+/// ```
+pub fn babble_eq(babble: &str, bytes: &[u8]) -> bool {
+    let mut chars = babble.chars();
+    let mut expect = |expected: &[char]| expected.iter().all(|&c| chars.next() == Some(c));
+    let mut seed = 1;
+
+    if !expect(&['x']) {
+        return false;
+    }
+
+    let mut pairs = bytes.chunks_exact(2);
+    for pair in &mut pairs {
+        let (byte1, byte2) = (pair[0] as usize, pair[1] as usize);
+        if !expect(&[
+            VOWELS[(((byte1 >> 6) & 3) + seed) % 6],
+            CONSONANTS[(byte1 >> 2) & 15],
+            VOWELS[((byte1 & 3) + (seed / 6)) % 6],
+            CONSONANTS[(byte2 >> 4) & 15],
+            '-',
+            CONSONANTS[byte2 & 15],
+        ]) {
+            return false;
+        }
+        seed = ((seed * 5) + ((byte1 * 7) + byte2)) % 36;
+    }
+
+    let last = match pairs.remainder() {
+        &[byte] => {
+            let byte = byte as usize;
+            [
+                VOWELS[(((byte >> 6) & 3) + seed) % 6],
+                CONSONANTS[(byte >> 2) & 15],
+                VOWELS[((byte & 3) + (seed / 6)) % 6],
+            ]
+        }
+        _ => [VOWELS[seed % 6], CONSONANTS[16], VOWELS[seed / 6]],
+    };
+
+    expect(&last) && expect(&['x']) && chars.next().is_none()
+}
+
+fn encode(bytes: &[u8], mut encoder: Encoder) -> String {
+    let mut babble = String::with_capacity(((bytes.len() / 2) + 1) * 6);
+    encoder.update(bytes, &mut babble);
+    encoder.finish(&mut babble);
+    babble
+}
+
+/// Convert a Bubble Babble string to bytes in a caller-provided buffer.
+///
+/// Returns the number of decoded bytes without allocating any memory.
+/// A buffer of `babble.len() / 3` bytes is always large enough, a
+/// smaller buffer may fail with `BabbleError::BufferTooSmall`.  Stable
+/// Babble is not supported as its compressed words are expanded first.
+///
+/// ```rust
+/// use bubblebabble_core::{debabble_to_slice, BabbleError};
+///
+/// let babble = "xigak-nyryk-humil-bosek-sonax";
+/// let mut buf = [0; 16];
+/// let len = debabble_to_slice(babble, &mut buf).unwrap();
+/// assert_eq!(&buf[..len], b"Pineapple");
+/// assert_eq!(
+///     debabble_to_slice(babble, &mut buf[..8]),
+///     Err(BabbleError::BufferTooSmall(8))
+/// );
+/// ```
+pub fn debabble_to_slice(babble: &str, out: &mut [u8]) -> Result<usize, BabbleError> {
+    let mut decoder = Decoder::new();
+    let mut sink = SliceSink(out, 0);
+    for (offset, c) in babble.char_indices() {
+        decoder.feed(offset, c, &mut sink)?;
+    }
+    decoder.verify_end()?;
+    Ok(sink.1)
+}
+
+fn decode(babble: &str, mut decoder: Decoder) -> Result<Vec<u8>, BabbleError> {
+    let mut bytes = Vec::with_capacity(babble.len() / 3);
+    decoder.update(babble, &mut bytes)?;
+    decoder.finish(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Incremental encoder that converts a stream of bytes to Babble.
+///
+/// The state is carried between the calls to `update`, so the data can
+/// be encoded in chunks of any size without keeping it in memory.
+///
+/// ```rust
+/// use bubblebabble_core::*;
+///
+/// let mut babble = String::new();
+/// let mut encoder = Encoder::new();
+/// encoder.update(b"Pine", &mut babble);
+/// encoder.update(b"apple", &mut babble);
+/// encoder.finish(&mut babble);
+/// assert_eq!(babble, bubblebabble(b"Pineapple"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Encoder {
+    use_seed: bool,
+    compress: bool,
+    seed: usize,
+    started: bool,
+    pending: Option<u8>,
+    // Repeated words of the stable format
+    word: String,
+    last: String,
+    count: usize,
+    words: usize,
+}
+
+impl Encoder {
+    /// Return a new encoder for the Bubble Babble format.
+    pub fn new() -> Self {
+        Self::with_seed(true)
+    }
+
+    /// Return a new encoder for the stable Babble format.
+    pub fn stable() -> Self {
+        Self::with_seed(false)
+    }
+
+    /// Return a new encoder for the stable Babble format that does not
+    /// compress repeated words.
+    pub fn stable_uncompressed() -> Self {
+        let mut encoder = Self::stable();
+        encoder.compress = false;
+        encoder
+    }
+
+    /// Return a new encoder for the Bubble Babble format that starts
+    /// with the checksum seed of a previous segment.
+    pub fn seeded(seed: u8) -> Self {
+        let mut encoder = Self::new();
+        encoder.seed = seed as usize % 36;
+        encoder
+    }
+
+    /// Return the checksum seed after the words of the complete pairs of
+    /// bytes, to continue with the next segment.
+    pub fn seed(&self) -> u8 {
+        self.seed as u8
+    }
+
+    fn with_seed(use_seed: bool) -> Self {
+        Encoder {
+            use_seed,
+            compress: !use_seed,
+            seed: 1,
+            started: false,
+            pending: None,
+            word: String::new(),
+            last: String::new(),
+            count: 0,
+            words: 0,
+        }
+    }
+
+    /// Encode `bytes` and append the generated text to `babble`.
+    pub fn update(&mut self, bytes: &[u8], babble: &mut String) {
+        self.start(babble);
+
+        for &byte in bytes {
+            match self.pending.take() {
+                Some(first) => self.round(first, Some(byte), babble),
+                None => self.pending = Some(byte),
+            }
+        }
+    }
+
+    /// Append the final word to `babble`.
+    pub fn finish(mut self, babble: &mut String) {
+        self.start(babble);
+
+        match self.pending.take() {
+            Some(byte) => self.round(byte, None, babble),
+            None => {
+                self.push(VOWELS[self.seed % 6], babble);
+                self.push(CONSONANTS[16], babble);
+                self.push(VOWELS[self.seed / 6], babble);
+            }
+        }
+
+        self.push('x', babble);
+
+        if self.compress {
+            self.end_word(babble);
+            self.flush(babble);
+        }
+    }
+
+    fn start(&mut self, babble: &mut String) {
+        if !self.started {
+            self.started = true;
+            self.push('x', babble);
+        }
+    }
+
+    // taken from OpenSSH ssh/sshkey.c
+    fn round(&mut self, byte1: u8, byte2: Option<u8>, babble: &mut String) {
+        let (byte1, seed) = (byte1 as usize, self.seed);
+
+        self.push(VOWELS[(((byte1 >> 6) & 3) + seed) % 6], babble);
+        self.push(CONSONANTS[(byte1 >> 2) & 15], babble);
+        self.push(VOWELS[((byte1 & 3) + (seed / 6)) % 6], babble);
+
+        if let Some(byte2) = byte2 {
+            let byte2 = byte2 as usize;
+
+            self.push(CONSONANTS[(byte2 >> 4) & 15], babble);
+            self.push('-', babble);
+            self.push(CONSONANTS[byte2 & 15], babble);
+
+            self.seed = if self.use_seed {
+                // The seed changes each word and serves as kind of a checksum
+                ((seed * 5) + ((byte1 * 7) + byte2)) % 36
+            } else {
+                0
+            };
+        }
+    }
+
+    fn push(&mut self, c: char, babble: &mut String) {
+        if !self.compress {
+            babble.push(c);
+        } else if c == '-' {
+            self.end_word(babble);
+        } else {
+            self.word.push(c);
+        }
+    }
+
+    // Find and replace repetitions
+    fn end_word(&mut self, babble: &mut String) {
+        if self.word == self.last && self.count < MAX_REPEAT {
+            self.count += 1;
+        } else {
+            self.flush(babble);
+            mem::swap(&mut self.word, &mut self.last);
+            self.count = 1;
+        }
+        self.word.clear();
+    }
+
+    fn flush(&mut self, babble: &mut String) {
+        if self.count == 0 {
+            return;
+        }
+        if self.words > 0 {
+            babble.push('-');
+        }
+        if self.count > 1 {
+            babble.push_str(&self.count.to_string());
+        }
+
+        // Use "wa" to represent a 0
+        if self.last == "babab" {
+            babble.push_str("wa");
+        } else {
+            babble.push_str(&self.last);
+        }
+
+        self.words += 1;
+    }
+}
+
+impl Default for Encoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Incremental decoder that converts Babble text back to bytes.
+///
+/// The text can be passed in chunks of any size; the checksum is
+/// verified as soon as each tuple is complete.
+#[derive(Debug, Clone)]
+pub struct Decoder {
+    use_seed: bool,
+    seed: usize,
+    offset: usize,
+    len: usize,
+    end: Option<usize>,
+    tuple: [(usize, char); 6],
+    tuple_len: usize,
+    // Compressed words of the stable format
+    word: String,
+    word_offset: usize,
+    words: usize,
+}
+
+impl Decoder {
+    /// Return a new decoder for the Bubble Babble format.
+    pub fn new() -> Self {
+        Self::with_seed(true)
+    }
+
+    /// Return a new decoder for the stable Babble format.
+    pub fn stable() -> Self {
+        Self::with_seed(false)
+    }
+
+    /// Return a new decoder for the Bubble Babble format that starts
+    /// with the checksum seed of a previous segment.
+    pub fn seeded(seed: u8) -> Self {
+        let mut decoder = Self::new();
+        decoder.seed = seed as usize % 36;
+        decoder
+    }
+
+    /// Return the checksum seed after the decoded words.
+    pub fn seed(&self) -> u8 {
+        self.seed as u8
+    }
+
+    fn with_seed(use_seed: bool) -> Self {
+        Decoder {
+            use_seed,
+            seed: 1,
+            offset: 0,
+            len: 0,
+            end: None,
+            tuple: [(0, 'x'); 6],
+            tuple_len: 0,
+            word: String::new(),
+            word_offset: 0,
+            words: 0,
+        }
+    }
+
+    /// Decode `babble` and append the decoded data to `bytes`.
+    pub fn update(&mut self, babble: &str, bytes: &mut Vec<u8>) -> Result<(), BabbleError> {
+        for (i, c) in babble.char_indices() {
+            let offset = self.offset + i;
+
+            if self.use_seed {
+                self.feed(offset, c, bytes)?;
+            } else if c == '-' {
+                self.expand_word(bytes)?;
+                self.word_offset = offset + 1;
+            } else {
+                self.word.push(c);
+            }
+        }
+        self.offset += babble.len();
+
+        Ok(())
+    }
+
+    /// Verify that the Babble text is complete.
+    pub fn finish(mut self, bytes: &mut Vec<u8>) -> Result<(), BabbleError> {
+        if !self.use_seed {
+            self.expand_word(bytes)?;
+        }
+        self.verify_end()
+    }
+
+    fn verify_end(&self) -> Result<(), BabbleError> {
+        match self.end {
+            Some(end) if end == self.len => Ok(()),
+            _ => Err(BabbleError::InvalidLength(self.len)),
+        }
+    }
+
+    fn expand_word(&mut self, bytes: &mut impl Sink) -> Result<(), BabbleError> {
+        let word = mem::take(&mut self.word);
+        let offset = self.word_offset;
+        let digits = word.bytes().take_while(u8::is_ascii_digit).count();
+        let count = match word[..digits].parse::<usize>() {
+            _ if digits == 0 => 1,
+            Ok(count) if count > 0 && count <= MAX_REPEAT => count,
+            _ => {
+                let c = word.as_bytes()[0] as char;
+                return Err(BabbleError::InvalidCharacter(c, offset));
+            }
+        };
+        let start = offset + digits;
+        let (word, shorthand) = match &word[digits..] {
+            "wa" => ("babab", true),
+            word => (word, false),
+        };
+
+        for _ in 0..count {
+            if self.words > 0 {
+                self.feed(offset.saturating_sub(1), '-', bytes)?;
+            }
+            for (i, c) in word.char_indices() {
+                self.feed(if shorthand { start } else { start + i }, c, bytes)?;
+            }
+            self.words += 1;
+        }
+
+        Ok(())
+    }
+
+    fn feed(&mut self, offset: usize, c: char, bytes: &mut impl Sink) -> Result<(), BabbleError> {
+        self.len += 1;
+
+        if self.end.is_some() {
+            // Trailing characters are reported as an invalid length
+            return Ok(());
+        } else if self.len == 1 {
+            return match c {
+                'x' => Ok(()),
+                c => Err(BabbleError::InvalidCharacter(c, offset)),
+            };
+        }
+
+        self.tuple[self.tuple_len] = (offset, c);
+        self.tuple_len += 1;
+
+        if self.tuple_len == 4 && c == 'x' {
+            self.end = Some(self.len);
+            self.round(bytes)
+        } else if self.tuple_len == 6 {
+            self.round(bytes)
+        } else {
+            Ok(())
+        }
+    }
+
+    // The reverse of Encoder::round()
+    fn round(&mut self, bytes: &mut impl Sink) -> Result<(), BabbleError> {
+        let lookup = |table: &[char], (offset, c): (usize, char)| {
+            table
+                .iter()
+                .position(|&t| t == c)
+                .ok_or(BabbleError::InvalidCharacter(c, offset))
+        };
+        let (tuple, seed) = (self.tuple, self.seed);
+        let offset = tuple[0].0;
+        let idx0 = lookup(&VOWELS, tuple[0])?;
+        let idx1 = lookup(&CONSONANTS, tuple[1])?;
+        let idx2 = lookup(&VOWELS, tuple[2])?;
+        self.tuple_len = 0;
+
+        if self.end.is_some() && idx1 == 16 {
+            if idx0 != seed % 6 || idx2 != seed / 6 {
+                return Err(BabbleError::ChecksumMismatch(offset));
+            }
+            return Ok(());
+        }
+
+        let high = (idx0 + 6 - (seed % 6)) % 6;
+        let low = (idx2 + 6 - (seed / 6)) % 6;
+        if high > 3 || low > 3 {
+            return Err(BabbleError::ChecksumMismatch(offset));
+        }
+        if idx1 > 15 {
+            return Err(BabbleError::InvalidCharacter(tuple[1].1, tuple[1].0));
+        }
+        let byte1 = (high << 6) | (idx1 << 2) | low;
+        bytes.put(byte1 as u8)?;
+
+        if self.end.is_none() {
+            if tuple[4].1 != '-' {
+                return Err(BabbleError::InvalidCharacter(tuple[4].1, tuple[4].0));
+            }
+            let idx3 = lookup(&CONSONANTS[..16], tuple[3])?;
+            let idx4 = lookup(&CONSONANTS[..16], tuple[5])?;
+            let byte2 = (idx3 << 4) | idx4;
+            bytes.put(byte2 as u8)?;
+
+            self.seed = if self.use_seed {
+                ((seed * 5) + (byte1 * 7) + byte2) % 36
+            } else {
+                0
+            };
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for Decoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Destination of the decoded bytes.
+trait Sink {
+    fn put(&mut self, byte: u8) -> Result<(), BabbleError>;
+}
+
+impl Sink for Vec<u8> {
+    fn put(&mut self, byte: u8) -> Result<(), BabbleError> {
+        self.push(byte);
+        Ok(())
+    }
+}
+
+/// A caller-provided buffer and the number of bytes written to it.
+struct SliceSink<'a>(&'a mut [u8], usize);
+
+impl Sink for SliceSink<'_> {
+    fn put(&mut self, byte: u8) -> Result<(), BabbleError> {
+        let len = self.0.len();
+        let slot = self
+            .0
+            .get_mut(self.1)
+            .ok_or(BabbleError::BufferTooSmall(len))?;
+        *slot = byte;
+        self.1 += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn test_from_iter() {
+        let bytes = (0..=255).collect::<Vec<u8>>();
+        assert_eq!(
+            bubblebabble_from_iter(bytes.iter().copied()),
+            bubblebabble(&bytes)
+        );
+        assert_eq!(bubblebabble_from_iter(None), "xexax");
+
+        let ok = bytes.iter().map(|&b| Ok::<u8, ()>(b));
+        assert_eq!(bubblebabble_try_from_iter(ok), Ok(bubblebabble(&bytes)));
+        let err = bytes.iter().map(|&b| if b < 8 { Ok(b) } else { Err(b) });
+        assert_eq!(bubblebabble_try_from_iter(err), Err(8));
+    }
+
+    #[test]
+    fn test_debabble_to_slice() {
+        let mut buf = [0; 512];
+        for len in [0, 1, 2, 15, 16, 255, 256] {
+            let bytes = (0..len).map(|i| i as u8).collect::<Vec<_>>();
+            let babble = bubblebabble(&bytes);
+            let buf = &mut buf[..babble.len() / 3];
+            assert_eq!(debabble_to_slice(&babble, buf), Ok(bytes.len()));
+            assert_eq!(&buf[..bytes.len()], &bytes[..]);
+        }
+
+        let invalid = "xigak-nyryk-hamil-bosek-sonax";
+        assert_eq!(
+            debabble_to_slice(invalid, &mut buf).unwrap_err(),
+            debabble(invalid).unwrap_err()
+        );
+        assert_eq!(
+            debabble_to_slice("xigak-nyryk", &mut buf),
+            Err(BabbleError::InvalidLength(11))
+        );
+    }
+
+    #[test]
+    fn test_bubblebabble() {
+        let tests = [
+            (
+                Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0),
+                "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-bexux",
+            ),
+            (
+                Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1),
+                "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-cixux",
+            ),
+            (
+                Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 2),
+                "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-doxux",
+            ),
+            (
+                "2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0".parse().unwrap(),
+                "xepib-panus-bubub-dubyb-hilyz-nefas-myzug-mihos-bexux",
+            ),
+            (
+                "fe80::4685:ff:fe76:1722".parse().unwrap(),
+                "xuzim-bobab-bobib-bobab-bucum-hibiz-zuzil-kyhed-duxix",
+            ),
+        ];
+
+        for addr in tests.iter() {
+            assert_eq!(bubblebabble(&(addr.0).octets()), addr.1);
+        }
+    }
+
+    #[test]
+    fn test_stablebabble() {
+        let tests = [
+            (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), "xebab-7wa-baxax"),
+            (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1), "xebab-7wa-caxax"),
+            (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 2), "xebab-7wa-daxax"),
+            (
+                "2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0".parse().unwrap(),
+                "xepib-pones-wa-dabab-helaz-nofas-mezag-mihos-baxax",
+            ),
+            (
+                "fe80::4685:ff:fe76:1722".parse().unwrap(),
+                "xuzim-3wa-becim-habaz-zozil-kahod-daxax",
+            ),
+        ];
+
+        for addr in tests.iter() {
+            assert_eq!(stablebabble(&(addr.0).octets()), addr.1);
+        }
+
+        // Counters are capped to bound the expansion of untrusted input
+        let zeros = [0; 2 * MAX_REPEAT + 4];
+        let babble = stablebabble(&zeros);
+        assert_eq!(babble, "xebab-65535wa-wa-baxax");
+        assert_eq!(debabble_stable(&babble).unwrap(), &zeros[..]);
+        assert_eq!(
+            debabble_stable("xebab-99999999999999wa-baxax"),
+            Err(BabbleError::InvalidCharacter('9', 6))
+        );
+        assert_eq!(
+            debabble_stable("xebab-65536wa-baxax"),
+            Err(BabbleError::InvalidCharacter('6', 6))
+        );
+
+        for addr in tests.iter() {
+            let bytes = (addr.0).octets();
+            let babble = stablebabble_uncompressed(&bytes);
+            assert_eq!(babble.len(), 9 * 6 - 1);
+            assert!(!babble.contains("wa"));
+            assert_eq!(debabble_stable(&babble).unwrap(), bytes);
+        }
+        assert_eq!(
+            stablebabble_uncompressed(&[0; 16]),
+            "xebab-babab-babab-babab-babab-babab-babab-babab-baxax"
+        );
+    }
+
+    #[test]
+    fn test_encoder() {
+        let data = b"Pineapple 2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0\0\0\0\0\0\0\0";
+
+        for chunk_size in 1..8 {
+            let mut bubble = String::new();
+            let mut stable = String::new();
+            let mut encoder = Encoder::new();
+            let mut stable_encoder = Encoder::stable();
+            for chunk in data.chunks(chunk_size) {
+                encoder.update(chunk, &mut bubble);
+                stable_encoder.update(chunk, &mut stable);
+            }
+            encoder.finish(&mut bubble);
+            stable_encoder.finish(&mut stable);
+            assert_eq!(bubble, bubblebabble(data));
+            assert_eq!(stable, stablebabble(data));
+
+            let mut bytes = Vec::new();
+            let mut stable_bytes = Vec::new();
+            let mut decoder = Decoder::new();
+            let mut stable_decoder = Decoder::stable();
+            for chunk in bubble.as_bytes().chunks(chunk_size) {
+                decoder
+                    .update(std::str::from_utf8(chunk).unwrap(), &mut bytes)
+                    .unwrap();
+            }
+            for chunk in stable.as_bytes().chunks(chunk_size) {
+                stable_decoder
+                    .update(std::str::from_utf8(chunk).unwrap(), &mut stable_bytes)
+                    .unwrap();
+            }
+            decoder.finish(&mut bytes).unwrap();
+            stable_decoder.finish(&mut stable_bytes).unwrap();
+            assert_eq!(bytes, &data[..]);
+            assert_eq!(stable_bytes, &data[..]);
+        }
+    }
+
+    #[test]
+    fn test_debabble() {
+        let tests: [&[u8]; 5] = [
+            b"",
+            b"1234567890",
+            b"Pineapple",
+            &[0; 16],
+            &[0x2a, 0x0a, 0xe5, 0xc0, 0, 0x2, 0, 0x5, 0x5c, 0xf9, 0xcc],
+        ];
+
+        for bytes in tests.iter() {
+            assert_eq!(debabble(&bubblebabble(bytes)).unwrap(), *bytes);
+            assert_eq!(debabble_stable(&stablebabble(bytes)).unwrap(), *bytes);
+        }
+
+        assert_eq!(bubblebabble(b"Pineapple"), "xigak-nyryk-humil-bosek-sonax");
+        assert_eq!(
+            debabble("xigak-nyryk-humil-bosek-sanax"),
+            Err(BabbleError::ChecksumMismatch(25))
+        );
+        assert_eq!(
+            debabble("xigak-nyryk-humil-bosek-sonaxx"),
+            Err(BabbleError::InvalidLength(30))
+        );
+        assert_eq!(
+            debabble("xigak-nyryk-humil-bosek-so1ax"),
+            Err(BabbleError::InvalidCharacter('1', 26))
+        );
+        assert_eq!(
+            debabble_stable("xebab-0wa-baxax"),
+            Err(BabbleError::InvalidCharacter('0', 6))
+        );
+    }
+
+    #[test]
+    fn test_babble_eq() {
+        let data = (0..=255).collect::<Vec<u8>>();
+        for len in 1..20 {
+            let babble = bubblebabble(&data[..len]);
+            assert!(babble_eq(&babble, &data[..len]));
+            assert!(!babble_eq(&babble, &data[1..=len]));
+            assert!(!babble_eq(&babble[..babble.len() - 1], &data[..len]));
+            assert!(!babble_eq(&format!("{}-", babble), &data[..len]));
+        }
+        assert!(babble_eq("xexax", &[]));
+        assert!(!babble_eq("", &[]));
+        assert!(!babble_eq("xigak-nyryk-humil-bosek-sonax", b"Pineapplf"));
+    }
+
+    #[test]
+    fn test_restabilize() {
+        let classic = "xigak-nyryk-humil-bosek-sonax";
+        let stable = restabilize(classic).unwrap();
+        assert_eq!(stable, stablebabble(b"Pineapple"));
+        assert_eq!(destabilize(&stable).unwrap(), classic);
+        assert_eq!(
+            destabilize(&restabilize("xexax").unwrap()).unwrap(),
+            "xexax"
+        );
+        assert!(restabilize("xigak-nyryk-humil-bosek-sonox").is_err());
+        assert!(destabilize("xigak-nyryk").is_err());
+    }
+
+    #[test]
+    fn test_seeded() {
+        let data = b"Pineapple 2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0";
+        assert_eq!(bubblebabble_seeded(data, 1).0, bubblebabble(data));
+        assert_eq!(bubblebabble_seeded(data, 37), bubblebabble_seeded(data, 1));
+
+        // Carry the seed across segments of even length
+        let mut seed = 1;
+        let mut segments = Vec::new();
+        for chunk in data.chunks(8) {
+            let (babble, next) = bubblebabble_seeded(chunk, seed);
+            assert_eq!(debabble_seeded(&babble, seed), Ok((chunk.to_vec(), next)));
+            segments.push(babble);
+            seed = next;
+        }
+        let words = segments
+            .iter()
+            .map(|babble| &babble[1..babble.len() - 4])
+            .collect::<String>();
+        assert!(bubblebabble(data).starts_with(&format!("x{}", words)));
+
+        assert!(debabble_seeded(&segments[1], 1).is_err());
+    }
+}
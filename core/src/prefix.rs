@@ -0,0 +1,248 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Decoding of partially typed Babble.
+
+use crate::{BabbleError, Decoder, CONSONANTS, VOWELS};
+use alloc::vec::Vec;
+
+/// The bytes that are consistent with the beginning of a Babble string.
+///
+/// Each complete tuple of the string decodes to two bytes, the letters
+/// of an incomplete tuple determine some bits of the next byte.  Search
+/// tools can use the prefix to find the keys or records that match a
+/// fingerprint while the user is still typing it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble_core::babble_prefix;
+///
+/// let keys: [&[u8]; 3] = [b"Pineapple", b"Pinecone", b"Pizza"];
+/// let prefix = babble_prefix("xigak-nyr").unwrap();
+/// assert_eq!(prefix.bytes(), b"Pi");
+/// assert_eq!(prefix.next_bits(), (0x6c, 0xfc));
+///
+/// let found = keys.iter().filter(|key| prefix.matches(key)).count();
+/// assert_eq!(found, 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BabblePrefix {
+    bytes: Vec<u8>,
+    next: u8,
+    mask: u8,
+    may_end: bool,
+    exact: bool,
+    complete: bool,
+}
+
+impl BabblePrefix {
+    /// Return the bytes of the complete tuples.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Return the known bits of the next byte and their mask.
+    pub fn next_bits(&self) -> (u8, u8) {
+        (self.next, self.mask)
+    }
+
+    /// Return true if the string is a complete Babble string.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Check if the Babble of the data starts with the string.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        if self.exact {
+            return data == self.bytes.as_slice();
+        }
+        if !data.starts_with(&self.bytes) {
+            return false;
+        }
+        match data.get(self.bytes.len()) {
+            Some(byte) => byte & self.mask == self.next,
+            None => self.may_end,
+        }
+    }
+
+    /// Return the shortest byte strings that match: the bytes of the
+    /// complete tuples if the data can end there, and one byte string
+    /// for each value of the bits of the next byte that are not known.
+    pub fn candidates(&self) -> impl Iterator<Item = Vec<u8>> + '_ {
+        let end = Some(self.bytes.clone()).filter(|_| self.may_end);
+        let next = (0..=255)
+            .filter(move |byte| self.mask != 0 && byte & self.mask == self.next)
+            .map(move |byte| {
+                let mut bytes = self.bytes.clone();
+                bytes.push(byte);
+                bytes
+            });
+        end.into_iter().chain(next)
+    }
+}
+
+/// Decode the beginning of a Babble string.
+///
+/// The checksums of the complete tuples and the letters of the
+/// incomplete tuple are verified against the seed chain of the Bubble
+/// Babble format, so a mistyped letter is reported as soon as possible.
+/// The stable format is not supported.
+pub fn babble_prefix(partial: &str) -> Result<BabblePrefix, BabbleError> {
+    let mut decoder = Decoder::new();
+    let mut bytes = Vec::new();
+    decoder.update(partial, &mut bytes)?;
+
+    if let Some(end) = decoder.end {
+        if end != decoder.len {
+            return Err(BabbleError::InvalidLength(decoder.len));
+        }
+        return Ok(BabblePrefix {
+            bytes,
+            next: 0,
+            mask: 0,
+            may_end: true,
+            exact: true,
+            complete: true,
+        });
+    }
+
+    let lookup = |table: &[char], (offset, c): (usize, char)| {
+        table
+            .iter()
+            .position(|&t| t == c)
+            .ok_or(BabbleError::InvalidCharacter(c, offset))
+    };
+    let seed = decoder.seed;
+    let tuple = &decoder.tuple[..decoder.tuple_len];
+    let (mut next, mut mask, mut may_end, mut last) = (0, 0, true, false);
+
+    for (i, &(offset, c)) in tuple.iter().enumerate() {
+        match i {
+            0 => {
+                let high = (lookup(&VOWELS, (offset, c))? + 6 - seed % 6) % 6;
+                if high > 3 {
+                    return Err(BabbleError::ChecksumMismatch(offset));
+                }
+                next = high << 6;
+                mask = 0xc0;
+                // Or the start of the final tuple of data with an even length
+                may_end = high == 0;
+            }
+            1 if c == 'x' => {
+                if next != 0 {
+                    return Err(BabbleError::ChecksumMismatch(tuple[0].0));
+                }
+                mask = 0;
+                last = true;
+            }
+            1 => {
+                next |= lookup(&CONSONANTS[..16], (offset, c))? << 2;
+                mask |= 0x3c;
+                may_end = false;
+            }
+            2 if last && lookup(&VOWELS, (offset, c))? != seed / 6 => {
+                return Err(BabbleError::ChecksumMismatch(tuple[0].0));
+            }
+            2 if last => {}
+            2 => {
+                let low = (lookup(&VOWELS, (offset, c))? + 6 - seed / 6) % 6;
+                if low > 3 {
+                    return Err(BabbleError::ChecksumMismatch(tuple[0].0));
+                }
+                bytes.push((next | low) as u8);
+                next = 0;
+                mask = 0;
+                // Or the final tuple of data with an odd length
+                may_end = true;
+            }
+            3 => {
+                next = lookup(&CONSONANTS[..16], (offset, c))? << 4;
+                mask = 0xf0;
+                may_end = false;
+            }
+            _ if c != '-' => return Err(BabbleError::InvalidCharacter(c, offset)),
+            _ => {}
+        }
+    }
+
+    Ok(BabblePrefix {
+        bytes,
+        next: next as u8,
+        mask,
+        may_end,
+        exact: last,
+        complete: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bubblebabble;
+
+    #[test]
+    fn test_babble_prefix() {
+        for data in &[&b""[..], b"P", b"Pi", b"Pineapple", b"1234567890"] {
+            let babble = bubblebabble(data);
+            for i in 0..=babble.len() {
+                let prefix = babble_prefix(&babble[..i]).unwrap();
+                assert!(prefix.matches(data), "{}", &babble[..i]);
+                assert_eq!(prefix.is_complete(), i == babble.len());
+                assert!(prefix.candidates().all(|c| prefix.matches(&c)));
+                assert!(prefix.candidates().any(|c| data.starts_with(&c)));
+            }
+        }
+
+        let prefix = babble_prefix("xigak").unwrap();
+        assert_eq!(prefix.bytes(), b"P");
+        assert_eq!(prefix.next_bits(), (0x60, 0xf0));
+        assert_eq!(prefix.candidates().count(), 16);
+        assert_eq!(babble_prefix("xe").unwrap().candidates().count(), 65);
+        let prefix = babble_prefix("xexa").unwrap();
+        assert_eq!(prefix.candidates().collect::<Vec<_>>(), [Vec::<u8>::new()]);
+        assert!(prefix.matches(b""));
+        assert!(!prefix.matches(b"P"));
+        assert!(!prefix.matches(b"Pa"));
+        assert!(!prefix.matches(b"P"));
+        assert_eq!(babble_prefix("").unwrap().candidates().count(), 1);
+
+        // Mistyped vowels that break the seed chain
+        assert_eq!(
+            babble_prefix("xigak-nyryk-he"),
+            Err(BabbleError::ChecksumMismatch(13))
+        );
+        assert_eq!(
+            babble_prefix("xigak-nyryk-humil-bosek-sonax-"),
+            Err(BabbleError::InvalidLength(30))
+        );
+        assert_eq!(
+            babble_prefix("xig4"),
+            Err(BabbleError::InvalidCharacter('4', 3))
+        );
+    }
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Integration with `zeroize` for sensitive material.
+
+use crate::{Decoder, Encoder};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Clear the pending input and the checksum state.
+impl Zeroize for Encoder {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.pending.zeroize();
+        self.word.zeroize();
+        self.last.zeroize();
+        self.count.zeroize();
+    }
+}
+
+impl Drop for Encoder {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Encoder {}
+
+/// Clear the buffered text and the checksum state.
+impl Zeroize for Decoder {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.tuple.zeroize();
+        self.tuple_len.zeroize();
+        self.word.zeroize();
+    }
+}
+
+impl Drop for Decoder {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for Decoder {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bubblebabble;
+    use alloc::{string::String, vec::Vec};
+
+    #[test]
+    fn test_zeroize() {
+        let mut babble = String::new();
+        let mut encoder = Encoder::new();
+        encoder.update(b"Pineapple", &mut babble);
+        encoder.zeroize();
+        assert_eq!(encoder.pending, None);
+        assert_eq!(encoder.seed, 0);
+
+        let mut decoder = Decoder::new();
+        decoder
+            .update(&bubblebabble(b"Pine"), &mut Vec::new())
+            .unwrap();
+        decoder.zeroize();
+        assert_eq!(decoder.tuple, [(0, '\0'); 6]);
+    }
+}
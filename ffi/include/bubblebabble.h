@@ -0,0 +1,49 @@
+/* C bindings of the bubblebabble crate, see ffi/src/lib.rs. */
+
+#ifndef BUBBLEBABBLE_H
+#define BUBBLEBABBLE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/**
+ * Convert `len` bytes at `data` to a NUL-terminated Babble string.
+ */
+char *bb_encode(const uint8_t *data, size_t len);
+
+/**
+ * Convert `len` bytes at `data` to a NUL-terminated stable Babble
+ * string.
+ */
+char *bb_encode_stable(const uint8_t *data, size_t len);
+
+/**
+ * Convert a NUL-terminated Babble string back to bytes.
+ *
+ * Returns the bytes and stores their number in `len`, or returns NULL
+ * if the string is not valid Babble.
+ */
+uint8_t *bb_decode(const char *babble, size_t *len);
+
+/**
+ * Convert a NUL-terminated stable Babble string back to bytes.
+ *
+ * Returns the bytes and stores their number in `len`, or returns NULL
+ * if the string is not valid stable Babble.
+ */
+uint8_t *bb_decode_stable(const char *babble, size_t *len);
+
+/**
+ * Release a string or buffer that was returned by the library.
+ */
+void bb_free(void *ptr);
+
+#ifdef __cplusplus
+}  /* extern "C" */
+#endif
+
+#endif  /* BUBBLEBABBLE_H */
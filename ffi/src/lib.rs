@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! C bindings of the Bubble Babble encoding.
+//!
+//! The functions are declared in `include/bubblebabble.h`, which is
+//! generated with `cbindgen --config cbindgen.toml`.  All strings and
+//! buffers that are returned by the library must be released with
+//! `bb_free`.
+
+use bubblebabble_core::{bubblebabble, debabble, debabble_stable, stablebabble, BabbleError};
+use std::{
+    ffi::CStr,
+    mem,
+    os::raw::{c_char, c_void},
+    ptr, slice,
+};
+
+/// Size of the hidden length prefix of returned buffers.
+const HEADER: usize = mem::size_of::<usize>();
+
+/// Copy `bytes` into a buffer that can be released with `bb_free`.
+fn into_raw(bytes: &[u8], nul: bool) -> *mut u8 {
+    let len = HEADER + bytes.len() + nul as usize;
+    let mut buf = Vec::with_capacity(len);
+    buf.extend_from_slice(&len.to_ne_bytes());
+    buf.extend_from_slice(bytes);
+    if nul {
+        buf.push(0);
+    }
+    let buf = Box::into_raw(buf.into_boxed_slice()) as *mut u8;
+    // SAFETY: the buffer is at least HEADER bytes long
+    unsafe { buf.add(HEADER) }
+}
+
+/// Read `len` bytes from `data`, which may be NULL if `len` is 0.
+unsafe fn input<'a>(data: *const u8, len: usize) -> &'a [u8] {
+    if data.is_null() || len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(data, len)
+    }
+}
+
+/// Convert `len` bytes at `data` to a NUL-terminated Babble string.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes or be NULL if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn bb_encode(data: *const u8, len: usize) -> *mut c_char {
+    into_raw(bubblebabble(input(data, len)).as_bytes(), true) as *mut c_char
+}
+
+/// Convert `len` bytes at `data` to a NUL-terminated stable Babble
+/// string.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes or be NULL if `len` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn bb_encode_stable(data: *const u8, len: usize) -> *mut c_char {
+    into_raw(stablebabble(input(data, len)).as_bytes(), true) as *mut c_char
+}
+
+/// Decode `babble` with `debabble` and store the number of bytes in
+/// `len`, or return NULL if either pointer is NULL or decoding fails.
+///
+/// # Safety
+///
+/// `babble` must be NULL or a NUL-terminated string and `len` must be
+/// NULL or writable.
+unsafe fn decode(
+    babble: *const c_char,
+    len: *mut usize,
+    debabble: fn(&str) -> Result<Vec<u8>, BabbleError>,
+) -> *mut u8 {
+    if babble.is_null() || len.is_null() {
+        return ptr::null_mut();
+    }
+    match CStr::from_ptr(babble).to_str().ok().map(debabble) {
+        Some(Ok(bytes)) => {
+            *len = bytes.len();
+            into_raw(&bytes, false)
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Convert a NUL-terminated Babble string back to bytes.
+///
+/// Returns the bytes and stores their number in `len`, or returns NULL
+/// if the string is not valid Babble.
+///
+/// # Safety
+///
+/// `babble` must be a NUL-terminated string and `len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn bb_decode(babble: *const c_char, len: *mut usize) -> *mut u8 {
+    // SAFETY: the caller's contract is the one of decode
+    unsafe { decode(babble, len, debabble) }
+}
+
+/// Convert a NUL-terminated stable Babble string back to bytes.
+///
+/// Returns the bytes and stores their number in `len`, or returns NULL
+/// if the string is not valid stable Babble.
+///
+/// # Safety
+///
+/// `babble` must be a NUL-terminated string and `len` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn bb_decode_stable(babble: *const c_char, len: *mut usize) -> *mut u8 {
+    // SAFETY: the caller's contract is the one of decode
+    unsafe { decode(babble, len, debabble_stable) }
+}
+
+/// Release a string or buffer that was returned by the library.
+///
+/// # Safety
+///
+/// `ptr` must be NULL or returned by one of the `bb_` functions, and it
+/// must not be used after it is released.
+#[no_mangle]
+pub unsafe extern "C" fn bb_free(ptr: *mut c_void) {
+    if ptr.is_null() {
+        return;
+    }
+    let start = (ptr as *mut u8).sub(HEADER);
+    let len = usize::from_ne_bytes(ptr::read_unaligned(start as *const [u8; HEADER]));
+    drop(Box::from_raw(ptr::slice_from_raw_parts_mut(start, len)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ffi() {
+        unsafe {
+            let babble = bb_encode(b"Pineapple".as_ptr(), 9);
+            assert_eq!(
+                CStr::from_ptr(babble).to_str(),
+                Ok("xigak-nyryk-humil-bosek-sonax")
+            );
+
+            let mut len = 0;
+            let data = bb_decode(babble, &mut len);
+            assert_eq!(slice::from_raw_parts(data, len), b"Pineapple");
+            bb_free(data as *mut c_void);
+            bb_free(babble as *mut c_void);
+
+            let babble = bb_encode_stable(ptr::null(), 0);
+            assert_eq!(CStr::from_ptr(babble).to_str(), Ok("xexax"));
+            let data = bb_decode_stable(babble, &mut len);
+            assert!(!data.is_null());
+            assert_eq!(len, 0);
+            bb_free(data as *mut c_void);
+            bb_free(babble as *mut c_void);
+
+            let invalid = b"xigak-nyryk\0";
+            assert!(bb_decode(invalid.as_ptr() as *const c_char, &mut len).is_null());
+            bb_free(ptr::null_mut());
+        }
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! The `BabbleDebug` derive macro.
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    ext::IdentExt, parse_quote, Data, DeriveInput, Error, Field, Fields, GenericArgument,
+    PathArguments, Result, Type,
+};
+
+pub fn expand(mut input: DeriveInput) -> Result<TokenStream> {
+    let name = &input.ident;
+    let arms = match &input.data {
+        Data::Struct(data) => vec![arm(quote!(#name), name, &data.fields)?],
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                arm(quote!(#name::#ident), ident, &variant.fields)
+            })
+            .collect::<Result<_>>()?,
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "BabbleDebug cannot be derived for unions",
+            ))
+        }
+    };
+
+    // Like the standard derive, require `Debug` for all type parameters
+    let params = input
+        .generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = input.generics.make_where_clause();
+    for param in params {
+        where_clause
+            .predicates
+            .push(parse_quote!(#param: ::core::fmt::Debug));
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let scrutinee = if arms.is_empty() {
+        quote!(*self)
+    } else {
+        quote!(self)
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::core::fmt::Debug for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                match #scrutinee {
+                    #(#arms)*
+                }
+            }
+        }
+    })
+}
+
+/// Return the match arm that prints a struct or an enum variant.
+fn arm(path: TokenStream, name: &Ident, fields: &Fields) -> Result<TokenStream> {
+    let label = name.unraw().to_string();
+    let bindings = (0..fields.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect::<Vec<_>>();
+    let values = fields
+        .iter()
+        .zip(&bindings)
+        .map(|(field, binding)| value(field, binding))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|field| &field.ident);
+            let labels = named
+                .named
+                .iter()
+                .filter_map(|field| field.ident.as_ref())
+                .map(|ident| ident.unraw().to_string());
+            quote! {
+                #path { #(#idents: #bindings),* } => f
+                    .debug_struct(#label)
+                    #(.field(#labels, #values))*
+                    .finish(),
+            }
+        }
+        Fields::Unnamed(_) => quote! {
+            #path(#(#bindings),*) => f
+                .debug_tuple(#label)
+                #(.field(#values))*
+                .finish(),
+        },
+        Fields::Unit => quote! {
+            #path => f.write_str(#label),
+        },
+    })
+}
+
+/// Return the expression that prints the field bound to `binding`.
+fn value(field: &Field, binding: &Ident) -> Result<TokenStream> {
+    let mut babble = is_bytes(&field.ty);
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("babble"))
+    {
+        attr.meta.require_path_only()?;
+        babble = true;
+    }
+
+    Ok(if babble {
+        quote!(&::bubblebabble::__DebugBabble(::core::convert::AsRef::<[u8]>::as_ref(#binding)))
+    } else {
+        quote!(#binding)
+    })
+}
+
+/// Check if the type is one of the known containers of bytes.
+fn is_bytes(ty: &Type) -> bool {
+    match ty {
+        Type::Array(array) => is_u8(&array.elem),
+        Type::Reference(reference) => {
+            matches!(&*reference.elem, Type::Slice(slice) if is_u8(&slice.elem))
+        }
+        Type::Group(group) => is_bytes(&group.elem),
+        Type::Paren(paren) => is_bytes(&paren.elem),
+        Type::Path(path) if path.qself.is_none() => {
+            let segment = match path.path.segments.last() {
+                Some(segment) => segment,
+                None => return false,
+            };
+            let arg = match &segment.arguments {
+                PathArguments::AngleBracketed(args) if args.args.len() == 1 => &args.args[0],
+                _ => return false,
+            };
+            match (segment.ident.to_string().as_str(), arg) {
+                ("Vec", GenericArgument::Type(elem)) => is_u8(elem),
+                ("Box", GenericArgument::Type(Type::Slice(slice))) => is_u8(&slice.elem),
+                _ => false,
+            }
+        }
+        _ => false,
+    }
+}
+
+fn is_u8(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("u8"))
+}
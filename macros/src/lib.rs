@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Procedural macros of the Bubble Babble encoding.
+//!
+//! The macros are re-exported by the `bubblebabble` crate with the
+//! `macros` feature and should be used from there.
+
+extern crate proc_macro;
+
+mod debug;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, Error, LitStr};
+
+/// Derive `Debug` and print byte fields as Babble.
+///
+/// Fields of the types `Vec<u8>`, `[u8; N]`, `&[u8]`, and `Box<[u8]>`
+/// are printed as Babble instead of lists of decimal numbers, all other
+/// fields use their own `Debug` implementation.  Other byte containers
+/// that implement `AsRef<[u8]>` can be marked with `#[babble]`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleDebug;
+///
+/// #[derive(BabbleDebug)]
+/// struct Key {
+///     name: &'static str,
+///     key: [u8; 9],
+/// }
+///
+/// let key = Key {
+///     name: "fruit",
+///     key: *b"Pineapple",
+/// };
+/// assert_eq!(
+///     format!("{:?}", key),
+///     r#"Key { name: "fruit", key: xigak-nyryk-humil-bosek-sonax }"#
+/// );
+/// ```
+#[proc_macro_derive(BabbleDebug, attributes(babble))]
+pub fn derive_babble_debug(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    debug::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Decode a Babble string literal at compile time.
+///
+/// The macro expands to a `[u8; N]` array of the decoded bytes.  The
+/// checksum of the string is verified and an invalid string is a
+/// compile error, so fingerprints can be pinned in their readable form.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::babble_bytes;
+///
+/// const PINNED: [u8; 9] = babble_bytes!("xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(&PINNED, b"Pineapple");
+/// ```
+///
+/// A mistyped word fails to compile:
+///
+/// ```compile_fail
+/// let key = bubblebabble::babble_bytes!("xigak-nyryk-hamil-bosek-sonax");
+/// ```
+#[proc_macro]
+pub fn babble_bytes(input: TokenStream) -> TokenStream {
+    let lit = parse_macro_input!(input as LitStr);
+    match bubblebabble_core::debabble(lit.value().trim()) {
+        Ok(bytes) => {
+            let len = bytes.len();
+            quote!({
+                const BYTES: [u8; #len] = [#(#bytes),*];
+                BYTES
+            })
+            .into()
+        }
+        Err(err) => Error::new(lit.span(), err).into_compile_error().into(),
+    }
+}
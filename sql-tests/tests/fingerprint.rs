@@ -0,0 +1,60 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+use bubblebabble::BabbleFingerprint;
+
+#[tokio::test]
+async fn test_sqlx_babble_fingerprint() {
+    use sqlx::{Connection, SqliteConnection};
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    let fpr = BabbleFingerprint::new("SHA256", b"Pineapple".to_vec());
+    let loaded: BabbleFingerprint = sqlx::query_scalar("SELECT ?")
+        .bind(&fpr)
+        .fetch_one(&mut conn)
+        .await
+        .unwrap();
+    assert_eq!(loaded, fpr);
+    assert!(
+        sqlx::query_scalar::<_, BabbleFingerprint>("SELECT 'SHA256/xigak'")
+            .fetch_one(&mut conn)
+            .await
+            .is_err()
+    );
+}
+
+#[test]
+fn test_diesel_babble_fingerprint() {
+    use diesel::{dsl::sql, prelude::*, sql_types::Text, IntoSql, SqliteConnection};
+
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    let fpr = BabbleFingerprint::new("SHA256", b"Pineapple".to_vec());
+    let loaded: BabbleFingerprint = diesel::select(fpr.clone().into_sql::<Text>())
+        .get_result(&mut conn)
+        .unwrap();
+    assert_eq!(loaded, fpr);
+    assert!(diesel::select(sql::<Text>("'SHA256/xigak'"))
+        .get_result::<BabbleFingerprint>(&mut conn)
+        .is_err());
+}
@@ -0,0 +1,116 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! age recipients.
+
+use crate::{bech32, BabbleError, FromBabble, ToBabble};
+use std::{borrow::Cow, convert::TryFrom, error, fmt, str::FromStr};
+
+/// Bech32 prefix of X25519 recipients.
+const HRP: &str = "age";
+
+/// An age X25519 recipient, the public key of an age identity.
+///
+/// Recipients are parsed from and displayed in the `age1…` Bech32 form
+/// that is printed by `age-keygen`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{AgeRecipient, ToBabble};
+///
+/// let recipient: AgeRecipient = "age1qyl4gsqvstdqsqmhtxk6jpaghpjwjl0grsyg5xpqvtzt2c30624sfc2yyh"
+///     .parse()
+///     .unwrap();
+/// assert!(recipient.to_babble().starts_with("xebef-zuhag-befom-dakyb"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AgeRecipient(pub [u8; 32]);
+
+impl fmt::Display for AgeRecipient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bech32::encode(HRP, &self.0))
+    }
+}
+
+/// Error returned when parsing an invalid age recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgeRecipientParseError;
+
+impl fmt::Display for AgeRecipientParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid age recipient syntax")
+    }
+}
+
+impl error::Error for AgeRecipientParseError {}
+
+impl FromStr for AgeRecipient {
+    type Err = AgeRecipientParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match bech32::decode(s.trim()) {
+            Some((hrp, key)) if hrp == HRP => <[u8; 32]>::try_from(key)
+                .map(AgeRecipient)
+                .map_err(|_| AgeRecipientParseError),
+            _ => Err(AgeRecipientParseError),
+        }
+    }
+}
+
+impl ToBabble for AgeRecipient {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+}
+
+impl FromBabble for AgeRecipient {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 32]>::from_babble(babble).map(AgeRecipient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_age_recipient() {
+        let bech32 = "age1qyl4gsqvstdqsqmhtxk6jpaghpjwjl0grsyg5xpqvtzt2c30624sfc2yyh";
+        let recipient: AgeRecipient = bech32.parse().unwrap();
+
+        assert_eq!(recipient.to_string(), bech32);
+        assert_eq!(
+            AgeRecipient::from_babble(&recipient.to_babble()),
+            Ok(recipient)
+        );
+
+        for s in &["", "age1qyl4gsqvstdq", "a12uel5l", "AGE-SECRET-KEY-1"] {
+            assert_eq!(s.parse::<AgeRecipient>(), Err(AgeRecipientParseError));
+        }
+    }
+}
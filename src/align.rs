@@ -0,0 +1,177 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Alignment of transcribed Babble with the expected bytes.
+
+use crate::bubblebabble;
+
+/// One step of the alignment of the expected and the transcribed words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordEdit {
+    /// The word was transcribed correctly.
+    Match(String),
+    /// The expected word was transcribed as another word.
+    Substitution {
+        expected: String,
+        transcribed: String,
+    },
+    /// The expected word is missing from the transcription.
+    Deletion(String),
+    /// The transcription has a word that was not expected.
+    Insertion(String),
+}
+
+impl WordEdit {
+    /// Return true if the word was transcribed correctly.
+    pub fn is_match(&self) -> bool {
+        matches!(self, WordEdit::Match(_))
+    }
+}
+
+/// The result of aligning a transcription with `align`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alignment {
+    edits: Vec<WordEdit>,
+}
+
+impl Alignment {
+    /// Return the edits in the order of the words.
+    pub fn edits(&self) -> &[WordEdit] {
+        &self.edits
+    }
+
+    /// Return true if all words were transcribed correctly.
+    pub fn is_match(&self) -> bool {
+        self.edits.iter().all(WordEdit::is_match)
+    }
+
+    /// Return the number of inserted, deleted, and substituted words.
+    pub fn distance(&self) -> usize {
+        self.edits.iter().filter(|edit| !edit.is_match()).count()
+    }
+}
+
+/// Align the words that were heard or typed with the Babble of
+/// `expected`.
+///
+/// Unlike `diff`, which compares the words by their position, the
+/// alignment has the minimal number of word edits, so a dropped or an
+/// extra word does not shift all following words.  Words are separated
+/// by `-` or whitespace and compared without case.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{align, WordEdit};
+///
+/// // xigak-nyryk-humil-bosek-sonax
+/// let alignment = align(b"Pineapple", "xigak humil bozek sonax");
+/// assert_eq!(alignment.distance(), 2);
+/// assert_eq!(alignment.edits()[1], WordEdit::Deletion("nyryk".into()));
+/// assert_eq!(
+///     alignment.edits()[3],
+///     WordEdit::Substitution {
+///         expected: "bosek".into(),
+///         transcribed: "bozek".into(),
+///     }
+/// );
+/// ```
+pub fn align(expected: &[u8], transcribed: &str) -> Alignment {
+    let babble = bubblebabble(expected);
+    let a = babble.split('-').collect::<Vec<_>>();
+    let b = transcribed
+        .split(|c: char| c == '-' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    let same = |i: usize, j: usize| a[i].eq_ignore_ascii_case(b[j]);
+
+    // Edit distances of the prefixes, d[i][j] for a[..i] and b[..j]
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if same(i - 1, j - 1) { 0 } else { 1 };
+            d[i][j] = (d[i - 1][j - 1] + cost)
+                .min(d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1);
+        }
+    }
+
+    // Walk back from the end and prefer matches and substitutions
+    let (mut i, mut j) = (a.len(), b.len());
+    let mut edits = Vec::new();
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + !same(i - 1, j - 1) as usize {
+            edits.push(if same(i - 1, j - 1) {
+                WordEdit::Match(a[i - 1].to_string())
+            } else {
+                WordEdit::Substitution {
+                    expected: a[i - 1].to_string(),
+                    transcribed: b[j - 1].to_string(),
+                }
+            });
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            edits.push(WordEdit::Deletion(a[i - 1].to_string()));
+            i -= 1;
+        } else {
+            edits.push(WordEdit::Insertion(b[j - 1].to_string()));
+            j -= 1;
+        }
+    }
+    edits.reverse();
+
+    Alignment { edits }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align() {
+        let exact = align(b"Pineapple", "XIGAK NYRYK HUMIL BOSEK SONAX");
+        assert!(exact.is_match());
+        assert_eq!(exact.edits().len(), 5);
+
+        let inserted = align(b"Pineapple", "xigak-nyryk-nyryk-humil-bosek-sonax");
+        assert_eq!(inserted.distance(), 1);
+        assert!(inserted
+            .edits()
+            .contains(&WordEdit::Insertion("nyryk".into())));
+
+        let swapped = align(b"Pineapple", "xigak-humil-nyryk-bosek-sonax");
+        assert_eq!(swapped.distance(), 2);
+
+        let empty = align(b"", "");
+        assert_eq!(empty.edits(), [WordEdit::Deletion("xexax".into())]);
+    }
+}
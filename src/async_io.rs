@@ -0,0 +1,323 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Asynchronous adapters for tokio.
+
+use crate::{writer::WrapBuf, Decoder, Encoder};
+use std::{
+    io,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+/// An `AsyncWrite` adapter that encodes all written bytes as Babble and
+/// writes the text to the inner writer.
+///
+/// The last word is written by `shutdown`, which must be called once
+/// all bytes have been written.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::AsyncBabbleWriter;
+/// use tokio::io::AsyncWriteExt;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut writer = AsyncBabbleWriter::new(Vec::new());
+/// writer.write_all(b"Pineapple").await?;
+/// writer.shutdown().await?;
+/// assert_eq!(writer.into_inner(), b"xigak-nyryk-humil-bosek-sonax");
+/// # Ok::<(), std::io::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct AsyncBabbleWriter<W> {
+    inner: W,
+    encoder: Option<Encoder>,
+    babble: String,
+    text: WrapBuf,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncBabbleWriter<W> {
+    /// Return a writer that encodes to the Bubble Babble format.
+    pub fn new(inner: W) -> Self {
+        Self::with_encoder(inner, Encoder::new())
+    }
+
+    /// Return a writer that encodes to the stable Babble format.
+    pub fn stable(inner: W) -> Self {
+        Self::with_encoder(inner, Encoder::stable())
+    }
+
+    fn with_encoder(inner: W, encoder: Encoder) -> Self {
+        AsyncBabbleWriter {
+            inner,
+            encoder: Some(encoder),
+            babble: String::new(),
+            text: WrapBuf::new(),
+        }
+    }
+
+    /// Wrap the text with a newline after `width` characters, 0
+    /// disables wrapping.
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.text.width = width;
+        self
+    }
+
+    /// Return a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Return the inner writer, any text that was not written is lost.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn poll_drain(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while !self.text.is_empty() {
+            match ready!(Pin::new(&mut self.inner).poll_write(cx, self.text.pending()))? {
+                0 => return Poll::Ready(Err(io::ErrorKind::WriteZero.into())),
+                len => self.text.consume(len),
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncBabbleWriter<W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bytes: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        // New bytes are only accepted after the previous text is written
+        ready!(this.poll_drain(cx))?;
+        let encoder = match this.encoder.as_mut() {
+            Some(encoder) => encoder,
+            None => return Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+        };
+        encoder.update(bytes, &mut this.babble);
+        this.text.push_str(&this.babble);
+        this.babble.clear();
+
+        Poll::Ready(Ok(bytes.len()))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        ready!(this.poll_drain(cx))?;
+        Pin::new(&mut this.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if let Some(encoder) = this.encoder.take() {
+            encoder.finish(&mut this.babble);
+            this.text.push_str(&this.babble);
+            this.babble.clear();
+        }
+        ready!(this.poll_drain(cx))?;
+        Pin::new(&mut this.inner).poll_shutdown(cx)
+    }
+}
+
+/// An `AsyncRead` adapter that decodes the Babble text of the inner
+/// reader.
+///
+/// Whitespace is ignored, so wrapped lines are joined.  Invalid Babble
+/// is returned as an error of the kind `InvalidData` and the end of the
+/// text is only reported after the final word has been verified.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::AsyncDebabbleReader;
+/// use tokio::io::AsyncReadExt;
+///
+/// # tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+/// let mut reader = AsyncDebabbleReader::new(&b"xigak-nyryk-\nhumil-bosek-sonax\n"[..]);
+/// let mut data = Vec::new();
+/// reader.read_to_end(&mut data).await?;
+/// assert_eq!(data, b"Pineapple");
+/// # Ok::<(), std::io::Error>(())
+/// # }).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct AsyncDebabbleReader<R> {
+    inner: R,
+    decoder: Option<Decoder>,
+    babble: String,
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: AsyncRead + Unpin> AsyncDebabbleReader<R> {
+    /// Return a reader that decodes the Bubble Babble format.
+    pub fn new(inner: R) -> Self {
+        Self::with_decoder(inner, Decoder::new())
+    }
+
+    /// Return a reader that decodes the stable Babble format.
+    pub fn stable(inner: R) -> Self {
+        Self::with_decoder(inner, Decoder::stable())
+    }
+
+    fn with_decoder(inner: R, decoder: Decoder) -> Self {
+        AsyncDebabbleReader {
+            inner,
+            decoder: Some(decoder),
+            babble: String::new(),
+            bytes: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Return a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncDebabbleReader<R> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        while buf.remaining() > 0 {
+            if this.pos < this.bytes.len() {
+                let len = buf.remaining().min(this.bytes.len() - this.pos);
+                buf.put_slice(&this.bytes[this.pos..this.pos + len]);
+                this.pos += len;
+                if this.pos == this.bytes.len() {
+                    this.bytes.clear();
+                    this.pos = 0;
+                }
+                break;
+            }
+
+            let decoder = match this.decoder.as_mut() {
+                Some(decoder) => decoder,
+                None => break,
+            };
+            let mut chunk = [0; 4096];
+            let mut text = ReadBuf::new(&mut chunk);
+            ready!(Pin::new(&mut this.inner).poll_read(cx, &mut text))?;
+
+            if text.filled().is_empty() {
+                if let Some(decoder) = this.decoder.take() {
+                    decoder.finish(&mut this.bytes).map_err(invalid_data)?;
+                }
+                continue;
+            }
+
+            this.babble.clear();
+            for &b in text.filled() {
+                match b {
+                    b if b.is_ascii_whitespace() => {}
+                    b if b.is_ascii() => this.babble.push(b as char),
+                    _ => return Poll::Ready(Err(io::ErrorKind::InvalidData.into())),
+                }
+            }
+            decoder
+                .update(&this.babble, &mut this.bytes)
+                .map_err(invalid_data)?;
+        }
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+fn invalid_data(err: crate::BabbleError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubblebabble, stablebabble};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    #[tokio::test]
+    async fn test_async_io() {
+        let data = (0..=255).cycle().take(10000).collect::<Vec<u8>>();
+
+        let mut writer = AsyncBabbleWriter::new(Vec::new()).wrap(76);
+        for chunk in data.chunks(7) {
+            writer.write_all(chunk).await.unwrap();
+        }
+        writer.shutdown().await.unwrap();
+        let babble = writer.into_inner();
+        assert_eq!(
+            String::from_utf8(babble.clone()).unwrap().replace('\n', ""),
+            bubblebabble(&data)
+        );
+
+        let mut reader = AsyncDebabbleReader::new(&babble[..]);
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await.unwrap();
+        assert_eq!(bytes, data);
+
+        let expected = stablebabble(&data);
+        let (client, mut server) = tokio::io::duplex(64);
+        let task = tokio::spawn(async move {
+            let mut writer = AsyncBabbleWriter::stable(client);
+            writer.write_all(&data).await.unwrap();
+            writer.shutdown().await.unwrap();
+        });
+        let mut babble = String::new();
+        server.read_to_string(&mut babble).await.unwrap();
+        task.await.unwrap();
+        assert_eq!(babble, expected);
+
+        let mut reader = AsyncDebabbleReader::new(&b"xigak-nyryk-humil-bosek-sonox"[..]);
+        let err = reader.read_to_end(&mut bytes).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
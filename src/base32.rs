@@ -0,0 +1,100 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Base32 helpers shared by the text formats of the integrations.
+
+const SYMBOLS: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encode bytes as uppercase base32 with padding (RFC 4648).
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut base32 = String::with_capacity(bytes.len().div_ceil(5) * 8);
+
+    for chunk in bytes.chunks(5) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u64, |n, (i, &b)| n | (b as u64) << (32 - i * 8));
+        let symbols = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < symbols {
+                base32.push(SYMBOLS[(n >> (35 - i * 5)) as usize & 0x1f] as char);
+            } else {
+                base32.push('=');
+            }
+        }
+    }
+
+    base32
+}
+
+/// Decode base32 in any case with optional padding, returning `None`
+/// for invalid input.
+pub(crate) fn decode(base32: &str) -> Option<Vec<u8>> {
+    let base32 = base32.trim_end_matches('=').as_bytes();
+    if matches!(base32.len() % 8, 1 | 3 | 6) {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(base32.len() * 5 / 8);
+
+    for chunk in base32.chunks(8) {
+        let mut n = 0u64;
+        for (i, c) in chunk.iter().enumerate() {
+            let c = c.to_ascii_uppercase();
+            let value = SYMBOLS.iter().position(|&s| s == c)? as u64;
+            n |= value << (35 - i * 5);
+        }
+        for i in 0..chunk.len() * 5 / 8 {
+            bytes.push((n >> (32 - i * 8)) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base32() {
+        let tests: [(&[u8], &str); 5] = [
+            (b"", ""),
+            (b"f", "MY======"),
+            (b"fo", "MZXQ===="),
+            (b"foob", "MZXW6YQ="),
+            (b"foobar", "MZXW6YTBOI======"),
+        ];
+
+        for (bytes, base32) in tests.iter() {
+            assert_eq!(encode(bytes), *base32);
+            assert_eq!(decode(base32).as_deref(), Some(*bytes));
+        }
+        assert_eq!(decode("mzxw6ytboi").as_deref(), Some(&b"foobar"[..]));
+        assert_eq!(decode("MZXW6YTBO"), None);
+        assert_eq!(decode("MZ1Q"), None);
+    }
+}
@@ -0,0 +1,97 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Base64 helpers shared by the text formats of the integrations.
+
+const SYMBOLS: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode bytes as standard base64 with padding.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut base64 = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - i * 8));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                base64.push(SYMBOLS[(n >> (18 - i * 6)) as usize & 0x3f] as char);
+            } else {
+                base64.push('=');
+            }
+        }
+    }
+
+    base64
+}
+
+/// Decode standard base64 with optional padding, returning `None` for
+/// invalid input.
+pub(crate) fn decode(base64: &str) -> Option<Vec<u8>> {
+    let base64 = base64.trim_end_matches('=').as_bytes();
+    if base64.len() % 4 == 1 {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(base64.len() * 3 / 4);
+
+    for chunk in base64.chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let value = SYMBOLS.iter().position(|s| s == c)? as u32;
+            n |= value << (18 - i * 6);
+        }
+        for i in 0..chunk.len() - 1 {
+            bytes.push((n >> (16 - i * 8)) as u8);
+        }
+    }
+
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_base64() {
+        let tests: [(&[u8], &str); 4] = [
+            (b"", ""),
+            (b"P", "UA=="),
+            (b"Pi", "UGk="),
+            (b"Pineapple", "UGluZWFwcGxl"),
+        ];
+
+        for (bytes, base64) in tests.iter() {
+            assert_eq!(encode(bytes), *base64);
+            assert_eq!(decode(base64).as_deref(), Some(*bytes));
+        }
+        assert_eq!(decode("UGk").as_deref(), Some(&b"Pi"[..]));
+        assert_eq!(decode("UGluZ"), None);
+        assert_eq!(decode("UG-="), None);
+    }
+}
@@ -0,0 +1,149 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Bech32 helpers for the text formats of the integrations.
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+fn polymod(values: impl IntoIterator<Item = u8>) -> u32 {
+    values.into_iter().fold(1, |chk, value| {
+        let top = chk >> 25;
+        let chk = (chk & 0x1ff_ffff) << 5 ^ value as u32;
+        GENERATOR
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| (top >> i) & 1 == 1)
+            .fold(chk, |chk, (_, g)| chk ^ g)
+    })
+}
+
+fn hrp_expand(hrp: &str) -> impl Iterator<Item = u8> + '_ {
+    let hrp = hrp.bytes();
+    hrp.clone()
+        .map(|c| c >> 5)
+        .chain(Some(0))
+        .chain(hrp.map(|c| c & 0x1f))
+}
+
+/// Regroup bits, padding the last group with zeros if `pad` is set.
+fn convert(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+    let mask = (1 << to) - 1;
+
+    for &value in data {
+        acc = (acc << from) | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push((acc >> bits & mask) as u8);
+        }
+    }
+    if pad && bits > 0 {
+        result.push((acc << (to - bits) & mask) as u8);
+    } else if !pad && (bits >= from || acc << (to - bits) & mask != 0) {
+        return None;
+    }
+
+    Some(result)
+}
+
+/// Encode bytes as a lowercase Bech32 string with the given prefix.
+pub(crate) fn encode(hrp: &str, bytes: &[u8]) -> String {
+    let data = convert(bytes, 8, 5, true).unwrap_or_default();
+    let chk = polymod(
+        hrp_expand(hrp)
+            .chain(data.iter().copied())
+            .chain([0; 6].iter().copied()),
+    ) ^ 1;
+
+    let mut bech32 = String::with_capacity(hrp.len() + data.len() + 7);
+    bech32.push_str(hrp);
+    bech32.push('1');
+    for value in data
+        .iter()
+        .copied()
+        .chain((0..6).map(|i| (chk >> (5 * (5 - i))) as u8 & 0x1f))
+    {
+        bech32.push(CHARSET[value as usize] as char);
+    }
+
+    bech32
+}
+
+/// Decode a Bech32 string into the lowercase prefix and the bytes,
+/// returning `None` for invalid input or checksums.
+pub(crate) fn decode(bech32: &str) -> Option<(String, Vec<u8>)> {
+    if bech32.bytes().any(|c| c.is_ascii_lowercase())
+        && bech32.bytes().any(|c| c.is_ascii_uppercase())
+    {
+        return None;
+    }
+    let bech32 = bech32.to_ascii_lowercase();
+    let (hrp, data) = bech32.split_at(bech32.rfind('1')?);
+    let data = data[1..]
+        .bytes()
+        .map(|c| CHARSET.iter().position(|&s| s == c).map(|v| v as u8))
+        .collect::<Option<Vec<_>>>()?;
+    if hrp.is_empty() || data.len() < 6 {
+        return None;
+    }
+    if polymod(hrp_expand(hrp).chain(data.iter().copied())) != 1 {
+        return None;
+    }
+
+    let bytes = convert(&data[..data.len() - 6], 5, 8, false)?;
+    Some((hrp.to_string(), bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bech32() {
+        let bech32 = "age1qyl4gsqvstdqsqmhtxk6jpaghpjwjl0grsyg5xpqvtzt2c30624sfc2yyh";
+        let (hrp, bytes) = decode(bech32).unwrap();
+
+        assert_eq!(hrp, "age");
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(encode(&hrp, &bytes), bech32);
+        assert_eq!(decode(&bech32.to_ascii_uppercase()), Some((hrp, bytes)));
+        assert_eq!(decode("a12uel5l"), Some(("a".to_string(), vec![])));
+        assert_eq!(decode(&bech32.replace("yyh", "yyj")), None);
+        assert_eq!(decode("Age1qyl4gsqvstdq"), None);
+        assert_eq!(decode("1qyl4gsqvstdq"), None);
+    }
+}
@@ -0,0 +1,140 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Blocklists of offensive words in Babble.
+
+use crate::{bubblebabble, debabble, BabbleError};
+
+/// A list of words that must not appear in Babble shown to users.
+///
+/// Babble is pronounceable and can accidentally spell words that
+/// should not be shown on customer-facing screens.  `encode` prefixes
+/// the data with a 16-bit salt word and increments the salt until the
+/// Babble does not contain any blocked word.  The salt changes the
+/// first word and the checksums of all following words, and `decode`
+/// strips it to return the original data.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::Blocklist;
+///
+/// let blocklist = Blocklist::new(["bad", "mad"]);
+/// let babble = blocklist.encode(b"Pineapple").unwrap();
+/// assert!(!blocklist.contains(&babble));
+/// assert_eq!(Blocklist::decode(&babble).unwrap(), b"Pineapple");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Blocklist {
+    words: Vec<String>,
+}
+
+impl Blocklist {
+    /// Create a blocklist, ignoring case and empty words.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let words = words
+            .into_iter()
+            .map(|word| word.as_ref().to_ascii_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        Blocklist { words }
+    }
+
+    /// Check if the Babble contains a blocked word.
+    ///
+    /// The dashes are removed before the check, so that words spanning
+    /// two Babble words are found as well.
+    pub fn contains(&self, babble: &str) -> bool {
+        let babble = babble
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        self.words.iter().any(|word| babble.contains(word.as_str()))
+    }
+
+    /// Encode the data with the first salt that gives a clean Babble.
+    ///
+    /// Returns `None` if the Babble contains a blocked word for every
+    /// salt, like a list that blocks single letters.
+    pub fn encode(&self, data: &[u8]) -> Option<String> {
+        let mut bytes = vec![0; 2];
+        bytes.extend_from_slice(data);
+
+        (0..=u16::MAX).find_map(|salt| {
+            bytes[..2].copy_from_slice(&salt.to_be_bytes());
+            Some(bubblebabble(&bytes)).filter(|babble| !self.contains(babble))
+        })
+    }
+
+    /// Decode the Babble of `encode` and strip the salt.
+    pub fn decode(babble: &str) -> Result<Vec<u8>, BabbleError> {
+        let mut bytes = debabble(babble)?;
+        if bytes.len() < 2 {
+            return Err(BabbleError::InvalidLength(babble.len()));
+        }
+        Ok(bytes.split_off(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocklist() {
+        let data = b"Pineapple";
+        let unsalted = bubblebabble(&[&[0, 0][..], data].concat());
+        let word = unsalted.split('-').nth(2).unwrap();
+
+        let empty = Blocklist::new([""]);
+        assert_eq!(empty, Blocklist::default());
+        assert_eq!(empty.encode(data), Some(unsalted.clone()));
+
+        // Block a word of the first encoding and one spanning a dash
+        let blocklist = Blocklist::new([
+            word.to_uppercase(),
+            unsalted.replace('-', "")[3..7].to_string(),
+        ]);
+        assert!(blocklist.contains(&unsalted));
+        let babble = blocklist.encode(data).unwrap();
+        assert_ne!(babble, unsalted);
+        assert!(!blocklist.contains(&babble));
+        assert_eq!(Blocklist::decode(&babble).unwrap(), data);
+        assert_eq!(blocklist.encode(data), Some(babble));
+
+        assert_eq!(Blocklist::new(["x"]).encode(data), None);
+        assert_eq!(
+            Blocklist::decode("xebax"),
+            Err(BabbleError::InvalidLength(5))
+        );
+    }
+}
@@ -0,0 +1,208 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! IP network prefixes in CIDR notation.
+
+use crate::{debabble, BabbleError, FromBabble, ToBabble};
+use std::{
+    borrow::Cow,
+    error, fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    str::FromStr,
+};
+
+/// Flag of IPv4 prefixes in the length byte.
+const IPV4: u8 = 0xc0;
+
+/// An IPv4 or IPv6 network prefix like `2a0a:e5c0::/29`.
+///
+/// The Babble contains the bytes of the network that are covered by the
+/// prefix, followed by a byte with the prefix length, so the last word
+/// carries the length and short prefixes have short strings.  IPv4
+/// lengths are marked with the bits `0xc0` to distinguish the families.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{FromBabble, IpPrefix, ToBabble};
+///
+/// let prefix: IpPrefix = "2a0a:e5c0::/29".parse().unwrap();
+/// assert_eq!(prefix.to_babble(), "xepib-panus-bulyx");
+/// assert_eq!(IpPrefix::from_babble("xepib-panus-bulyx"), Ok(prefix));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct IpPrefix {
+    addr: IpAddr,
+    len: u8,
+}
+
+impl IpPrefix {
+    /// Create a prefix of `len` bits, the host bits of `addr` are
+    /// cleared.  Returns `None` if the length exceeds the address.
+    pub fn new(addr: IpAddr, len: u8) -> Option<Self> {
+        let addr = match addr {
+            IpAddr::V4(addr) if len <= 32 => {
+                let mask = u32::MAX.checked_shl(32 - len as u32).unwrap_or(0);
+                IpAddr::V4(Ipv4Addr::from(u32::from(addr) & mask))
+            }
+            IpAddr::V6(addr) if len <= 128 => {
+                let mask = u128::MAX.checked_shl(128 - len as u32).unwrap_or(0);
+                IpAddr::V6(Ipv6Addr::from(u128::from(addr) & mask))
+            }
+            _ => return None,
+        };
+        Some(IpPrefix { addr, len })
+    }
+
+    /// Return the network address.
+    pub fn addr(&self) -> IpAddr {
+        self.addr
+    }
+
+    /// Return the prefix length in bits.
+    pub fn len(&self) -> u8 {
+        self.len
+    }
+
+    /// Return `true` for the prefix `/0` that covers all addresses.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return `true` if the prefix contains `addr`.
+    pub fn contains(&self, addr: IpAddr) -> bool {
+        IpPrefix::new(addr, self.len).is_some_and(|prefix| prefix == *self)
+    }
+}
+
+impl fmt::Display for IpPrefix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.addr, self.len)
+    }
+}
+
+/// Error returned when parsing an invalid network prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IpPrefixParseError;
+
+impl fmt::Display for IpPrefixParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid network prefix syntax")
+    }
+}
+
+impl error::Error for IpPrefixParseError {}
+
+impl FromStr for IpPrefix {
+    type Err = IpPrefixParseError;
+
+    /// Parse an address and a prefix length separated by `/`.  Host bits
+    /// of the address are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (addr, len) = s.trim().split_once('/').ok_or(IpPrefixParseError)?;
+        let addr = addr.parse().map_err(|_| IpPrefixParseError)?;
+        let len = len.parse().map_err(|_| IpPrefixParseError)?;
+
+        IpPrefix::new(addr, len)
+            .filter(|prefix| prefix.addr == addr)
+            .ok_or(IpPrefixParseError)
+    }
+}
+
+impl ToBabble for IpPrefix {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        let (mut bytes, len) = match self.addr {
+            IpAddr::V4(addr) => (addr.octets().to_vec(), IPV4 | self.len),
+            IpAddr::V6(addr) => (addr.octets().to_vec(), self.len),
+        };
+        bytes.truncate((self.len as usize).div_ceil(8));
+        bytes.push(len);
+        Cow::Owned(bytes)
+    }
+}
+
+impl FromBabble for IpPrefix {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        let mut bytes = debabble(babble)?;
+        let invalid = BabbleError::InvalidLength(babble.len());
+        let len = bytes.pop().ok_or_else(|| invalid.clone())?;
+
+        let (addr, len) = if len & IPV4 == IPV4 {
+            let mut octets = [0; 4];
+            octets
+                .get_mut(..bytes.len())
+                .ok_or_else(|| invalid.clone())?
+                .copy_from_slice(&bytes);
+            (IpAddr::from(octets), len & !IPV4)
+        } else {
+            let mut octets = [0; 16];
+            octets
+                .get_mut(..bytes.len())
+                .ok_or_else(|| invalid.clone())?
+                .copy_from_slice(&bytes);
+            (IpAddr::from(octets), len)
+        };
+
+        IpPrefix::new(addr, len)
+            .filter(|prefix| prefix.addr == addr && bytes.len() == (len as usize).div_ceil(8))
+            .ok_or(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bubblebabble;
+
+    #[test]
+    fn test_ip_prefix() {
+        let prefix: IpPrefix = "192.0.2.0/24".parse().unwrap();
+        assert_eq!(prefix.to_string(), "192.0.2.0/24");
+        assert_eq!(prefix.to_babble(), bubblebabble(&[192, 0, 2, 0xc0 | 24]));
+        assert_eq!(IpPrefix::from_babble(&prefix.to_babble()), Ok(prefix));
+        assert!(prefix.contains("192.0.2.42".parse().unwrap()));
+        assert!(!prefix.contains("192.0.3.1".parse().unwrap()));
+        assert!(!prefix.contains("::1".parse().unwrap()));
+
+        for s in &["::/0", "2a0a:e5c0::/29", "2001:db8::1/128", "0.0.0.0/0"] {
+            let prefix: IpPrefix = s.parse().unwrap();
+            assert_eq!(prefix.to_string(), *s);
+            assert_eq!(IpPrefix::from_babble(&prefix.to_babble()), Ok(prefix));
+        }
+        assert_ne!(
+            "::/24".parse::<IpPrefix>().unwrap().to_babble(),
+            "0.0.0.0/24".parse::<IpPrefix>().unwrap().to_babble()
+        );
+
+        for s in &["", "192.0.2.0", "192.0.2.1/24", "192.0.2.0/33", "::/129"] {
+            assert_eq!(s.parse::<IpPrefix>(), Err(IpPrefixParseError));
+        }
+        for bytes in &[&[][..], &[192, 0, 2, 0xc0 | 16], &[192, 0, 0xc0 | 24]] {
+            assert!(IpPrefix::from_babble(&bubblebabble(bytes)).is_err());
+        }
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Color hashes for visual verification.
+
+use crate::siphash::SipHasher;
+use std::{fmt, hash::Hasher};
+
+/// Colors of maximum contrast by Kenneth Kelly, without white and black
+/// that are hard to see on terminal backgrounds.
+const PALETTE: [Rgb; 16] = [
+    Rgb::new(0xf3, 0xc3, 0x00),
+    Rgb::new(0x87, 0x56, 0x92),
+    Rgb::new(0xf3, 0x84, 0x00),
+    Rgb::new(0xa1, 0xca, 0xf1),
+    Rgb::new(0xbe, 0x00, 0x32),
+    Rgb::new(0xc2, 0xb2, 0x80),
+    Rgb::new(0x84, 0x84, 0x82),
+    Rgb::new(0x00, 0x88, 0x56),
+    Rgb::new(0xe6, 0x8f, 0xac),
+    Rgb::new(0x00, 0x67, 0xa5),
+    Rgb::new(0xf9, 0x93, 0x79),
+    Rgb::new(0x60, 0x4e, 0x97),
+    Rgb::new(0xb3, 0x44, 0x6c),
+    Rgb::new(0x88, 0x2d, 0x17),
+    Rgb::new(0x8d, 0xb6, 0x00),
+    Rgb::new(0x2b, 0x3d, 0x26),
+];
+
+/// An RGB color.
+///
+/// The `Display` implementation prints the hex form `#rrggbb`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    /// Red.
+    pub r: u8,
+    /// Green.
+    pub g: u8,
+    /// Blue.
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Create a color from its components.
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Rgb { r, g, b }
+    }
+
+    /// Return a swatch of two spaces with the color as 24-bit ANSI
+    /// background, followed by the reset sequence.
+    pub fn ansi(&self) -> String {
+        format!("\x1b[48;2;{};{};{}m  \x1b[0m", self.r, self.g, self.b)
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// Return a sequence of four distinct colors for the data.
+///
+/// The colors are picked from a palette of 16 high-contrast colors by
+/// the SipHash of the data, so that any change of the data changes the
+/// colors.  They are shown next to the Babble to compare fingerprints
+/// at a glance, but the 15 bits of a color hash are too short to verify
+/// a fingerprint on their own.
+///
+/// # Examples
+///
+/// ```rust
+/// let colors = bubblebabble::color_hash(b"Pineapple");
+/// let swatches = colors.iter().map(|c| c.ansi()).collect::<String>();
+/// println!("{} xigak-nyryk-humil-bosek-sonax", swatches);
+/// assert_ne!(colors[0], colors[1]);
+/// ```
+pub fn color_hash(data: &[u8]) -> [Rgb; 4] {
+    let mut hasher = SipHasher::new(0, 0);
+    hasher.write(data);
+    let mut hash = hasher.finish();
+
+    // Pick without replacement to get distinct colors
+    let mut palette = PALETTE.to_vec();
+    let mut colors = [Rgb::new(0, 0, 0); 4];
+    for color in colors.iter_mut() {
+        let i = (hash % palette.len() as u64) as usize;
+        hash /= palette.len() as u64;
+        *color = palette.remove(i);
+    }
+
+    colors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_hash() {
+        let colors = color_hash(b"Pineapple");
+        assert_eq!(colors, color_hash(b"Pineapple"));
+        assert_ne!(colors, color_hash(b"Pineapplf"));
+
+        for data in [&b""[..], b"\0", b"Pineapple", &[0xff; 32]].iter() {
+            let colors = color_hash(data);
+            for (i, a) in colors.iter().enumerate() {
+                assert!(PALETTE.contains(a));
+                assert!(colors[i + 1..].iter().all(|b| a != b));
+            }
+        }
+
+        let rgb = Rgb::new(0xbe, 0x00, 0x32);
+        assert_eq!(rgb.to_string(), "#be0032");
+        assert_eq!(rgb.ansi(), "\x1b[48;2;190;0;50m  \x1b[0m");
+    }
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Constant-time comparison of fingerprints.
+
+use std::hint::black_box;
+
+/// Compare two Babble strings in constant time.
+///
+/// Case, separators, and whitespace are ignored, so that differently
+/// formatted strings of the same Babble are equal.  The time of the
+/// comparison depends on the lengths of the strings but not on the
+/// position of the first difference.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::constant_time_eq;
+///
+/// assert!(constant_time_eq(
+///     "xigak-nyryk-humil-bosek-sonax",
+///     "XIGAK NYRYK HUMIL BOSEK SONAX"
+/// ));
+/// assert!(!constant_time_eq(
+///     "xigak-nyryk-humil-bosek-sonax",
+///     "xigak-nyryk-humil-bosek-sanax"
+/// ));
+/// ```
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    constant_time_eq_bytes(&normalize(a), &normalize(b))
+}
+
+/// Compare two byte slices in constant time.
+///
+/// Slices of different lengths are never equal, the time of the
+/// comparison does not depend on the contents.
+pub fn constant_time_eq_bytes(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let diff = a
+        .iter()
+        .zip(b.iter())
+        .fold(0u8, |diff, (x, y)| black_box(diff | (x ^ y)));
+
+    diff == 0
+}
+
+/// Return the lowercase letters and digits of the string.
+fn normalize(s: &str) -> Vec<u8> {
+    s.bytes()
+        .filter(u8::is_ascii_alphanumeric)
+        .map(|b| b.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constant_time_eq() {
+        let babble = "xigak-nyryk-humil-bosek-sonax";
+
+        assert!(constant_time_eq(babble, babble));
+        assert!(constant_time_eq(babble, "xigak:nyryk:humil:bosek:sonax"));
+        assert!(constant_time_eq(
+            babble,
+            " Xigak-Nyryk  humil-bosek-sonax\n"
+        ));
+        assert!(!constant_time_eq(babble, "xigak-nyryk-humil-bosek"));
+        assert!(!constant_time_eq(babble, "xigak-nyryk-humil-bosek-sonaz"));
+        assert!(!constant_time_eq("xebab-7wa-caxax", "xebab-6wa-caxax"));
+        assert!(constant_time_eq("", "--"));
+
+        assert!(constant_time_eq_bytes(b"Pineapple", b"Pineapple"));
+        assert!(!constant_time_eq_bytes(b"Pineapple", b"pineapple"));
+        assert!(!constant_time_eq_bytes(b"Pineapple", b"Pine"));
+        assert!(constant_time_eq_bytes(b"", b""));
+    }
+}
@@ -0,0 +1,185 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Conversion between Babble and other encodings of the same bytes.
+//!
+//! Verification often has to compare a fingerprint that is shown in
+//! one encoding with a Babble string, so these helpers convert both
+//! ways in one call.  Babble is the standard format with checksum.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::convert;
+//!
+//! let babble = convert::hex_to_babble("50696e656170706c65").unwrap();
+//! assert_eq!(babble, "xigak-nyryk-humil-bosek-sonax");
+//! assert_eq!(convert::babble_to_base64(&babble).unwrap(), "UGluZWFwcGxl");
+//! ```
+
+use crate::{base32, base64, bech32, bubblebabble, debabble, hex, BabbleError};
+use std::{error, fmt};
+
+/// Error returned when converting an invalid string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    /// The Babble string is invalid.
+    Babble(BabbleError),
+    /// The hex string is invalid.
+    Hex,
+    /// The base32 string is invalid.
+    Base32,
+    /// The base64 string is invalid.
+    Base64,
+    /// The Bech32 string or its checksum is invalid.
+    Bech32,
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Babble(err) => err.fmt(f),
+            ConvertError::Hex => f.write_str("invalid hex"),
+            ConvertError::Base32 => f.write_str("invalid base32"),
+            ConvertError::Base64 => f.write_str("invalid base64"),
+            ConvertError::Bech32 => f.write_str("invalid bech32"),
+        }
+    }
+}
+
+impl error::Error for ConvertError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ConvertError::Babble(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<BabbleError> for ConvertError {
+    fn from(err: BabbleError) -> Self {
+        ConvertError::Babble(err)
+    }
+}
+
+fn babble_bytes(babble: &str) -> Result<Vec<u8>, ConvertError> {
+    Ok(debabble(babble.trim())?)
+}
+
+/// Convert hex digits in any case, optionally spaced or separated by
+/// colons, to Babble.
+pub fn hex_to_babble(hex: &str) -> Result<String, ConvertError> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    let hex = hex
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ':')
+        .collect::<String>();
+    hex::decode(&hex)
+        .map(|bytes| bubblebabble(&bytes))
+        .ok_or(ConvertError::Hex)
+}
+
+/// Convert Babble to lowercase hex.
+pub fn babble_to_hex(babble: &str) -> Result<String, ConvertError> {
+    babble_bytes(babble).map(|bytes| hex::encode(&bytes))
+}
+
+/// Convert base32 (RFC 4648) in any case to Babble.
+pub fn base32_to_babble(base32: &str) -> Result<String, ConvertError> {
+    base32::decode(base32.trim())
+        .map(|bytes| bubblebabble(&bytes))
+        .ok_or(ConvertError::Base32)
+}
+
+/// Convert Babble to uppercase base32 with padding.
+pub fn babble_to_base32(babble: &str) -> Result<String, ConvertError> {
+    babble_bytes(babble).map(|bytes| base32::encode(&bytes))
+}
+
+/// Convert standard or URL-safe base64, with optional padding, to
+/// Babble.
+pub fn base64_to_babble(base64: &str) -> Result<String, ConvertError> {
+    let base64 = base64.trim().replace('-', "+").replace('_', "/");
+    base64::decode(&base64)
+        .map(|bytes| bubblebabble(&bytes))
+        .ok_or(ConvertError::Base64)
+}
+
+/// Convert Babble to standard base64 with padding.
+pub fn babble_to_base64(babble: &str) -> Result<String, ConvertError> {
+    babble_bytes(babble).map(|bytes| base64::encode(&bytes))
+}
+
+/// Convert a Bech32 string to Babble and return it with the prefix.
+pub fn bech32_to_babble(bech32: &str) -> Result<(String, String), ConvertError> {
+    bech32::decode(bech32.trim())
+        .map(|(hrp, bytes)| (hrp, bubblebabble(&bytes)))
+        .ok_or(ConvertError::Bech32)
+}
+
+/// Convert Babble to a Bech32 string with the prefix `hrp`.
+pub fn babble_to_bech32(hrp: &str, babble: &str) -> Result<String, ConvertError> {
+    babble_bytes(babble).map(|bytes| bech32::encode(&hrp.to_ascii_lowercase(), &bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let babble = "xigak-nyryk-humil-bosek-sonax";
+
+        assert_eq!(babble_to_hex(babble).unwrap(), "50696e656170706c65");
+        assert_eq!(hex_to_babble("0x50:69:6E:65 6170706c65").unwrap(), babble);
+        assert_eq!(babble_to_base32(babble).unwrap(), "KBUW4ZLBOBYGYZI=");
+        assert_eq!(base32_to_babble("kbuw4zlbobygyzi").unwrap(), babble);
+        assert_eq!(babble_to_base64(babble).unwrap(), "UGluZWFwcGxl");
+        assert_eq!(base64_to_babble("UGluZWFwcGxl").unwrap(), babble);
+        assert_eq!(
+            base64_to_babble("-_8").unwrap(),
+            bubblebabble(&[0xfb, 0xff])
+        );
+
+        let bech32 = babble_to_bech32("test", babble).unwrap();
+        assert!(bech32.starts_with("test1"));
+        assert_eq!(
+            bech32_to_babble(&bech32).unwrap(),
+            ("test".to_string(), babble.to_string())
+        );
+
+        assert_eq!(hex_to_babble("506"), Err(ConvertError::Hex));
+        assert_eq!(base32_to_babble("KBU!"), Err(ConvertError::Base32));
+        assert_eq!(base64_to_babble("U"), Err(ConvertError::Base64));
+        assert_eq!(bech32_to_babble("test1qqqq"), Err(ConvertError::Bech32));
+        assert!(matches!(
+            babble_to_hex("xigak-nyryk"),
+            Err(ConvertError::Babble(_))
+        ));
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Support of the `BabbleDebug` derive macro.
+
+use crate::bubblebabble;
+use std::fmt;
+
+/// Print bytes as Babble in `Debug` output.
+pub struct DebugBabble<'a>(pub &'a [u8]);
+
+impl fmt::Debug for DebugBabble<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bubblebabble(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::BabbleDebug;
+
+    #[derive(BabbleDebug)]
+    struct Peer<'a, T> {
+        name: T,
+        key: Vec<u8>,
+        r#ref: &'a [u8],
+        #[babble]
+        nonce: std::borrow::Cow<'a, [u8]>,
+        port: u16,
+    }
+
+    #[derive(BabbleDebug)]
+    enum Message {
+        Hello([u8; 2], Box<[u8]>),
+        Bytes { f: Vec<u8>, len: usize },
+        Bye,
+    }
+
+    #[test]
+    fn test_babble_debug() {
+        let peer = Peer {
+            name: "alpha",
+            key: b"Pineapple".to_vec(),
+            r#ref: &[],
+            nonce: b"1234567890"[..].into(),
+            port: 22,
+        };
+        assert_eq!(
+            format!("{:?}", peer),
+            "Peer { name: \"alpha\", key: xigak-nyryk-humil-bosek-sonax, ref: xexax, \
+             nonce: xesef-disof-gytuf-katof-movif-baxux, port: 22 }"
+        );
+
+        let hello = Message::Hello([0, 1], vec![].into());
+        assert_eq!(format!("{:?}", hello), "Hello(xebab-caxex, xexax)");
+        let bytes = Message::Bytes {
+            f: b"Pineapple".to_vec(),
+            len: 9,
+        };
+        assert_eq!(
+            format!("{:#?}", bytes),
+            "Bytes {\n    f: xigak-nyryk-humil-bosek-sonax,\n    len: 9,\n}"
+        );
+        assert_eq!(format!("{:?}", Message::Bye), "Bye");
+    }
+}
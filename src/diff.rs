@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Word-by-word comparison of Babble strings.
+
+/// The comparison of one pair of aligned words.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordDiff {
+    /// The word of the first string, if it has a word at this position.
+    pub a: Option<String>,
+    /// The word of the second string, if it has a word at this position.
+    pub b: Option<String>,
+    /// The character positions in the word that differ.
+    pub positions: Vec<usize>,
+}
+
+impl WordDiff {
+    /// Return true if both words are equal.
+    pub fn is_match(&self) -> bool {
+        self.positions.is_empty() && self.a.is_some() && self.b.is_some()
+    }
+}
+
+/// The result of comparing two Babble strings with `diff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BabbleDiff {
+    words: Vec<WordDiff>,
+}
+
+impl BabbleDiff {
+    /// Return the aligned words.
+    pub fn words(&self) -> &[WordDiff] {
+        &self.words
+    }
+
+    /// Return true if all words are equal.
+    pub fn is_match(&self) -> bool {
+        self.words.iter().all(WordDiff::is_match)
+    }
+
+    /// Return the number of words that differ.
+    pub fn mismatches(&self) -> usize {
+        self.words.iter().filter(|word| !word.is_match()).count()
+    }
+}
+
+/// Compare two Babble strings word by word.
+///
+/// The words are separated by `-` or whitespace and compared without
+/// case, so that GUIs can highlight the characters where two
+/// fingerprints diverge.
+///
+/// # Examples
+///
+/// ```rust
+/// let diff = bubblebabble::diff(
+///     "xigak-nyryk-humil-bosek-sonax",
+///     "xigak-nyryk-humil-bosek-sanix",
+/// );
+/// assert!(!diff.is_match());
+/// assert_eq!(diff.mismatches(), 1);
+/// assert_eq!(diff.words()[4].positions, [1, 3]);
+/// ```
+pub fn diff(a: &str, b: &str) -> BabbleDiff {
+    let split = |s| {
+        str::split(s, |c: char| c == '-' || c.is_whitespace())
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (split(a), split(b));
+
+    let words = (0..a.len().max(b.len()))
+        .map(|i| {
+            let (wa, wb) = (a.get(i).copied(), b.get(i).copied());
+            let (ca, cb) = (chars(wa), chars(wb));
+            let positions = (0..ca.len().max(cb.len()))
+                .filter(|&j| ca.get(j) != cb.get(j))
+                .collect();
+
+            WordDiff {
+                a: wa.map(str::to_string),
+                b: wb.map(str::to_string),
+                positions,
+            }
+        })
+        .collect();
+
+    BabbleDiff { words }
+}
+
+fn chars(word: Option<&str>) -> Vec<char> {
+    word.unwrap_or_default()
+        .chars()
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let babble = "xigak-nyryk-humil-bosek-sonax";
+
+        let same = diff(babble, "XIGAK NYRYK HUMIL BOSEK SONAX");
+        assert!(same.is_match());
+        assert_eq!(same.words().len(), 5);
+        assert_eq!(same.mismatches(), 0);
+
+        let changed = diff(babble, "xigak-nyrok-humil-bosek");
+        assert!(!changed.is_match());
+        assert_eq!(changed.mismatches(), 2);
+        assert_eq!(changed.words()[1].positions, [3]);
+        assert_eq!(changed.words()[4].a.as_deref(), Some("sonax"));
+        assert_eq!(changed.words()[4].b, None);
+        assert_eq!(changed.words()[4].positions, [0, 1, 2, 3, 4]);
+
+        assert!(diff("", "").is_match());
+    }
+}
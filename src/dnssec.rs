@@ -0,0 +1,378 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! DNSSEC DS and DNSKEY records (RFC 4034).
+
+use crate::{base64, hex, ToBabble};
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+/// Return the RDATA tokens of a record in zone file syntax.
+///
+/// Comments and parentheses of multi-line records are removed, and the
+/// owner, TTL, and class are skipped if the record type is present.
+fn rdata(s: &str, rrtype: &str) -> Vec<String> {
+    let tokens = s
+        .lines()
+        .map(|line| line.split(';').next().unwrap_or_default())
+        .flat_map(|line| line.split_whitespace())
+        .map(|t| t.trim_matches(['(', ')']))
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+
+    match tokens.iter().position(|t| t.eq_ignore_ascii_case(rrtype)) {
+        Some(i) => tokens[i + 1..].to_vec(),
+        None => tokens,
+    }
+}
+
+fn algorithm_name(algorithm: u8) -> Option<&'static str> {
+    match algorithm {
+        5 => Some("RSASHA1"),
+        7 => Some("RSASHA1-NSEC3-SHA1"),
+        8 => Some("RSASHA256"),
+        10 => Some("RSASHA512"),
+        13 => Some("ECDSAP256SHA256"),
+        14 => Some("ECDSAP384SHA384"),
+        15 => Some("ED25519"),
+        16 => Some("ED448"),
+        _ => None,
+    }
+}
+
+/// The RDATA of a DS record, the digest of a DNSKEY in the parent zone.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{Ds, ToBabble};
+///
+/// let ds: Ds = ". IN DS 20326 8 2 E06D44B80B8F1D39A95C0B0D7C65D08458E880409BBC683457104237C7F8EC8D"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(ds.key_tag, 20326);
+/// assert_eq!(ds.digest_type_name(), Some("SHA-256"));
+/// assert!(ds.to_babble().starts_with("xumak-tocur-"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ds {
+    /// The key tag of the DNSKEY.
+    pub key_tag: u16,
+    /// The algorithm number of the DNSKEY.
+    pub algorithm: u8,
+    /// The digest type, 2 for SHA-256 and 4 for SHA-384.
+    pub digest_type: u8,
+    /// The digest of the owner name and the DNSKEY RDATA.
+    pub digest: Vec<u8>,
+}
+
+impl Ds {
+    /// Return the name of the DNSKEY algorithm.
+    pub fn algorithm_name(&self) -> Option<&'static str> {
+        algorithm_name(self.algorithm)
+    }
+
+    /// Return the name of the digest algorithm.
+    pub fn digest_type_name(&self) -> Option<&'static str> {
+        match self.digest_type {
+            1 => Some("SHA-1"),
+            2 => Some("SHA-256"),
+            4 => Some("SHA-384"),
+            _ => None,
+        }
+    }
+
+    /// Return the record in zone file syntax with the Babble as comment.
+    pub fn to_record(&self, owner: &str) -> String {
+        format!("{} IN DS {} ; {}", owner, self, self.to_babble())
+    }
+}
+
+impl fmt::Display for Ds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.key_tag,
+            self.algorithm,
+            self.digest_type,
+            hex::encode(&self.digest).to_ascii_uppercase()
+        )
+    }
+}
+
+/// Error returned when parsing an invalid DS or DNSKEY record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DnssecParseError;
+
+impl fmt::Display for DnssecParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid DNSSEC record syntax")
+    }
+}
+
+impl error::Error for DnssecParseError {}
+
+impl FromStr for Ds {
+    type Err = DnssecParseError;
+
+    /// Parse the RDATA `20326 8 2 E06D...` or a zone file record with it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = rdata(s, "DS");
+        let token = |i: usize| tokens.get(i).map(String::as_str).unwrap_or_default();
+        // The digest can be split by whitespace
+        let digest = tokens.get(3..).and_then(|t| hex::decode(&t.concat()));
+
+        match (token(0).parse(), token(1).parse(), token(2).parse(), digest) {
+            (Ok(key_tag), Ok(algorithm), Ok(digest_type), Some(digest)) if !digest.is_empty() => {
+                Ok(Ds {
+                    key_tag,
+                    algorithm,
+                    digest_type,
+                    digest,
+                })
+            }
+            _ => Err(DnssecParseError),
+        }
+    }
+}
+
+impl ToBabble for Ds {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.digest)
+    }
+}
+
+/// The RDATA of a DNSKEY record.
+///
+/// The Babble of a DNSKEY is the Babble of its public key, the digests
+/// for trust anchors are calculated with `to_ds`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::Dnskey;
+///
+/// let dnskey: Dnskey = "example.com. 3600 IN DNSKEY 257 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4="
+///     .parse()
+///     .unwrap();
+/// assert!(dnskey.is_sep());
+/// assert_eq!(dnskey.algorithm_name(), Some("ED25519"));
+/// assert_eq!(dnskey.key_tag(), 3613);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dnskey {
+    /// The flags, 256 for a ZSK and 257 for a KSK.
+    pub flags: u16,
+    /// The protocol, always 3.
+    pub protocol: u8,
+    /// The algorithm number.
+    pub algorithm: u8,
+    /// The public key.
+    pub public_key: Vec<u8>,
+}
+
+impl Dnskey {
+    /// Return the name of the algorithm.
+    pub fn algorithm_name(&self) -> Option<&'static str> {
+        algorithm_name(self.algorithm)
+    }
+
+    /// Return `true` if the secure entry point flag of a KSK is set.
+    pub fn is_sep(&self) -> bool {
+        self.flags & 1 == 1
+    }
+
+    /// Return the wire format of the RDATA.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.public_key.len());
+        bytes.extend_from_slice(&self.flags.to_be_bytes());
+        bytes.push(self.protocol);
+        bytes.push(self.algorithm);
+        bytes.extend_from_slice(&self.public_key);
+        bytes
+    }
+
+    /// Calculate the key tag of RFC 4034, Appendix B.
+    pub fn key_tag(&self) -> u16 {
+        let mut ac = self
+            .to_bytes()
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if i % 2 == 0 {
+                    (b as u32) << 8
+                } else {
+                    b as u32
+                }
+            })
+            .sum::<u32>();
+        ac += ac >> 16;
+        ac as u16
+    }
+
+    /// Calculate the DS record of the key for `owner` with the digest
+    /// type 2 (SHA-256) or 4 (SHA-384).
+    #[cfg(feature = "sha2")]
+    pub fn to_ds(&self, owner: &str, digest_type: u8) -> Option<Ds> {
+        use sha2::{Digest, Sha256, Sha384};
+
+        // The owner name in canonical wire format
+        let mut data = Vec::new();
+        for label in owner.trim_end_matches('.').split('.') {
+            if label.is_empty() {
+                continue;
+            }
+            if label.len() > 63 {
+                return None;
+            }
+            data.push(label.len() as u8);
+            data.extend(label.bytes().map(|b| b.to_ascii_lowercase()));
+        }
+        data.push(0);
+        data.extend_from_slice(&self.to_bytes());
+
+        let digest = match digest_type {
+            2 => Sha256::digest(&data).to_vec(),
+            4 => Sha384::digest(&data).to_vec(),
+            _ => return None,
+        };
+
+        Some(Ds {
+            key_tag: self.key_tag(),
+            algorithm: self.algorithm,
+            digest_type,
+            digest,
+        })
+    }
+}
+
+impl fmt::Display for Dnskey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.flags,
+            self.protocol,
+            self.algorithm,
+            base64::encode(&self.public_key)
+        )
+    }
+}
+
+impl FromStr for Dnskey {
+    type Err = DnssecParseError;
+
+    /// Parse the RDATA `257 3 8 AwEA...` or a zone file record with it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = rdata(s, "DNSKEY");
+        let token = |i: usize| tokens.get(i).map(String::as_str).unwrap_or_default();
+        // The public key can be split by whitespace
+        let public_key = tokens.get(3..).and_then(|t| base64::decode(&t.concat()));
+
+        match (
+            token(0).parse(),
+            token(1).parse(),
+            token(2).parse(),
+            public_key,
+        ) {
+            (Ok(flags), Ok(protocol), Ok(algorithm), Some(public_key))
+                if !public_key.is_empty() =>
+            {
+                Ok(Dnskey {
+                    flags,
+                    protocol,
+                    algorithm,
+                    public_key,
+                })
+            }
+            _ => Err(DnssecParseError),
+        }
+    }
+}
+
+impl ToBabble for Dnskey {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.public_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROOT_KSK: &str = ". 172800 IN DNSKEY 257 3 8 (
+        AwEAAaz/tAm8yTn4Mfeh5eyI96WSVexTBAvkMgJzkKTOiW1vkIbzxeF3+/4RgWOq
+        7HrxRixHlFlExOLAJr5emLvN7SWXgnLh4+B5xQlNVz8Og8kvArMtNROxVQuCaSnI
+        DdD5LKyWbRd2n9WGe2R8PzgCmr3EgVLrjyBxWezF0jLHwVN8efS3rCj/EWgvIWgb
+        9tarpVUDK/b58Da+sqqls3eNbuv7pr+eoZG+SrDK6nWeL3c6H5Apxz7LjVc1uTId
+        sIXxuOLYA4/ilBmSVIzuDWfdRUfhHdY6+cn8HFRm+2hM8AnXGXws9555KrUB5qih
+        ylGa8subX2Nn6UwNR1AkUTV74bU= ) ; KSK; alg = RSASHA256 ; key id = 20326";
+    const ROOT_DS: &str =
+        "20326 8 2 E06D44B80B8F1D39A95C0B0D7C65D08458E880409BBC683457104237C7F8EC8D";
+
+    #[test]
+    fn test_ds() {
+        let ds: Ds = ROOT_DS.parse().unwrap();
+
+        assert_eq!(ds.to_string(), ROOT_DS);
+        assert_eq!(ds.algorithm_name(), Some("RSASHA256"));
+        assert_eq!(ds.to_record(".").parse(), Ok(ds.clone()));
+        assert!(ds.to_record(".").ends_with(&ds.to_babble()));
+
+        for s in &["", "20326 8 2", "20326 8 x E06D", ". IN DS 20326 8 2 E06"] {
+            assert_eq!(s.parse::<Ds>(), Err(DnssecParseError));
+        }
+    }
+
+    #[test]
+    fn test_dnskey() {
+        let dnskey: Dnskey = ROOT_KSK.parse().unwrap();
+
+        assert!(dnskey.is_sep());
+        assert_eq!(dnskey.key_tag(), 20326);
+        assert_eq!(dnskey.to_string().parse(), Ok(dnskey.clone()));
+        #[cfg(feature = "sha2")]
+        {
+            assert_eq!(dnskey.to_ds(".", 2), ROOT_DS.parse().ok());
+
+            // RFC 8080, section 6.1
+            let dnskey: Dnskey = "257 3 15 l02Woi0iS8Aa25FQkUd9RMzZHJpBoRQwAQEX1SxZJA4="
+                .parse()
+                .unwrap();
+            assert_eq!(
+                dnskey.to_ds("Example.COM", 2).unwrap().to_string(),
+                "3613 15 2 3AA5AB37EFCE57F737FC1627013FEE07BDF241BD10F3B1964AB55C78E79A304B"
+            );
+            assert_eq!(dnskey.to_ds("example.com.", 1), None);
+        }
+
+        for s in &["", "257 3 8", "257 3 8 !!!!", "257 3 300 AwEAAaz="] {
+            assert_eq!(s.parse::<Dnskey>(), Err(DnssecParseError));
+        }
+    }
+}
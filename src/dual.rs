@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Hex and Babble side by side for error messages and logs.
+
+use crate::{bubblebabble, hex, short, ELLIPSIS};
+use std::fmt;
+
+/// Print bytes as hex followed by their Babble in parentheses.
+///
+/// Some readers compare fingerprints by their hex digits and others by
+/// the spoken words, so messages and logs can show both.  The alternate
+/// form `{:#}` abbreviates long values to the first two bytes, the last
+/// byte, and the first two words.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::HexAndBabble;
+///
+/// let key = [0x5c, 0xf9, 0xcc, 0xc8, 0x7c, 0x48, 0x97, 0xc0];
+/// assert_eq!(
+///     HexAndBabble(&key).to_string(),
+///     "5cf9ccc87c4897c0 (xilaz-nefys-myzyg-mihis-bexyx)"
+/// );
+/// assert_eq!(
+///     format!("{:#}", HexAndBabble(&key)),
+///     "5cf9...c0 (xilaz-nefys...)"
+/// );
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HexAndBabble<T>(pub T);
+
+impl<T: AsRef<[u8]>> fmt::Display for HexAndBabble<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0.as_ref();
+        if f.alternate() && bytes.len() > 3 {
+            write!(
+                f,
+                "{}{}{} ({})",
+                hex::encode(&bytes[..2]),
+                ELLIPSIS,
+                hex::encode(&bytes[bytes.len() - 1..]),
+                short(bytes, 2)
+            )
+        } else {
+            write!(f, "{} ({})", hex::encode(bytes), bubblebabble(bytes))
+        }
+    }
+}
+
+impl<T: AsRef<[u8]>> fmt::Debug for HexAndBabble<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_and_babble() {
+        let babble = bubblebabble(b"Pineapple");
+        assert_eq!(
+            format!("{:?}", HexAndBabble(b"Pineapple")),
+            format!("50696e656170706c65 ({})", babble)
+        );
+        assert_eq!(
+            format!("{:#}", HexAndBabble([1, 2, 3])),
+            "010203 (xebeb-dibyx)"
+        );
+        assert_eq!(
+            format!("{:#}", HexAndBabble(vec![1, 2, 3, 4])),
+            "0102...04 (xebeb-dibyb...)"
+        );
+    }
+}
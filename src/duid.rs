@@ -0,0 +1,274 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! DHCPv6 DHCP Unique Identifiers.
+
+use crate::{debabble, hex, BabbleError, FromBabble, ToBabble};
+use std::{borrow::Cow, convert::TryInto, error, fmt, str::FromStr};
+
+/// Maximum length of a DUID including the type.
+const MAX_LEN: usize = 130;
+
+/// A DHCPv6 DUID (RFC 8415, section 11) that identifies a client or
+/// server in lease databases.
+///
+/// The `Display` implementation prints the colon-separated hex form of
+/// lease files and logs.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{Duid, ToBabble};
+///
+/// let duid: Duid = "00:03:00:01:00:1b:21:3a:9f:c4".parse().unwrap();
+/// assert_eq!(duid.link_layer_address(), Some(&[0x00, 0x1b, 0x21, 0x3a, 0x9f, 0xc4][..]));
+/// assert_eq!(duid.to_babble(), "xebab-fibeb-cybac-rumof-pylus-guxox");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Duid {
+    /// DUID-LLT, a link-layer address with the time in seconds since
+    /// 2000-01-01 UTC when it was generated.
+    Llt {
+        hardware_type: u16,
+        time: u32,
+        address: Vec<u8>,
+    },
+    /// DUID-EN, an identifier assigned by the vendor with the IANA
+    /// enterprise number.
+    En {
+        enterprise: u32,
+        identifier: Vec<u8>,
+    },
+    /// DUID-LL, a link-layer address.
+    Ll {
+        hardware_type: u16,
+        address: Vec<u8>,
+    },
+    /// DUID-UUID of RFC 6355.
+    Uuid([u8; 16]),
+}
+
+impl Duid {
+    /// Parse the bytes of a DUID, including its 2-byte type.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < 2 || bytes.len() > MAX_LEN {
+            return None;
+        }
+        let u16_at = |i: usize| u16::from_be_bytes([bytes[i], bytes[i + 1]]);
+        let u32_at = |i: usize| u32::from_be_bytes(bytes[i..i + 4].try_into().unwrap());
+
+        let duid = match (u16_at(0), bytes.len()) {
+            (1, 9..=MAX_LEN) => Duid::Llt {
+                hardware_type: u16_at(2),
+                time: u32_at(4),
+                address: bytes[8..].to_vec(),
+            },
+            (2, 7..=MAX_LEN) => Duid::En {
+                enterprise: u32_at(2),
+                identifier: bytes[6..].to_vec(),
+            },
+            (3, 5..=MAX_LEN) => Duid::Ll {
+                hardware_type: u16_at(2),
+                address: bytes[4..].to_vec(),
+            },
+            (4, 18) => Duid::Uuid(bytes[2..].try_into().unwrap()),
+            _ => return None,
+        };
+
+        Some(duid)
+    }
+
+    /// Return the bytes of the DUID, including its 2-byte type.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.duid_type().to_be_bytes().to_vec();
+        match self {
+            Duid::Llt {
+                hardware_type,
+                time,
+                address,
+            } => {
+                bytes.extend_from_slice(&hardware_type.to_be_bytes());
+                bytes.extend_from_slice(&time.to_be_bytes());
+                bytes.extend_from_slice(address);
+            }
+            Duid::En {
+                enterprise,
+                identifier,
+            } => {
+                bytes.extend_from_slice(&enterprise.to_be_bytes());
+                bytes.extend_from_slice(identifier);
+            }
+            Duid::Ll {
+                hardware_type,
+                address,
+            } => {
+                bytes.extend_from_slice(&hardware_type.to_be_bytes());
+                bytes.extend_from_slice(address);
+            }
+            Duid::Uuid(uuid) => bytes.extend_from_slice(uuid),
+        }
+        bytes
+    }
+
+    /// Return the DUID type, 1 to 4.
+    pub fn duid_type(&self) -> u16 {
+        match self {
+            Duid::Llt { .. } => 1,
+            Duid::En { .. } => 2,
+            Duid::Ll { .. } => 3,
+            Duid::Uuid(_) => 4,
+        }
+    }
+
+    /// Return the link-layer address of a DUID-LLT or DUID-LL.
+    pub fn link_layer_address(&self) -> Option<&[u8]> {
+        match self {
+            Duid::Llt { address, .. } | Duid::Ll { address, .. } => Some(address),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Duid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, b) in self.to_bytes().iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing an invalid DUID.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuidParseError;
+
+impl fmt::Display for DuidParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid DHCPv6 DUID syntax")
+    }
+}
+
+impl error::Error for DuidParseError {}
+
+impl FromStr for Duid {
+    type Err = DuidParseError;
+
+    /// Parse hex digits, optionally separated into bytes by `:` or `-`
+    /// like `00:03:00:01:00:1b:21:3a:9f:c4`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let hex = if s.contains(':') || s.contains('-') {
+            let mut hex = String::with_capacity(s.len());
+            for group in s.split([':', '-']) {
+                match group.len() {
+                    1 => hex.push('0'),
+                    2 => {}
+                    _ => return Err(DuidParseError),
+                }
+                hex.push_str(group);
+            }
+            hex
+        } else {
+            s.to_string()
+        };
+
+        hex::decode(&hex)
+            .and_then(|bytes| Self::from_bytes(&bytes))
+            .ok_or(DuidParseError)
+    }
+}
+
+impl ToBabble for Duid {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.to_bytes())
+    }
+}
+
+impl FromBabble for Duid {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        Duid::from_bytes(&debabble(babble)?).ok_or(BabbleError::InvalidLength(babble.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duid() {
+        let llt: Duid = "00:01:00:01:2a:3b:4c:5d:00:1b:21:3a:9f:c4".parse().unwrap();
+        assert_eq!(
+            llt,
+            Duid::Llt {
+                hardware_type: 1,
+                time: 0x2a3b_4c5d,
+                address: vec![0x00, 0x1b, 0x21, 0x3a, 0x9f, 0xc4],
+            }
+        );
+        assert_eq!(llt.to_string(), "00:01:00:01:2a:3b:4c:5d:00:1b:21:3a:9f:c4");
+        assert_eq!("0001-0001-2a3b".parse::<Duid>(), Err(DuidParseError));
+        assert_eq!(
+            "0-1-0-1-2a-3b-4c-5d-0-1b-21-3a-9f-c4".parse(),
+            Ok(llt.clone())
+        );
+
+        let en: Duid = "0002000001370102030405".parse().unwrap();
+        assert_eq!(
+            en,
+            Duid::En {
+                enterprise: 311,
+                identifier: vec![1, 2, 3, 4, 5],
+            }
+        );
+        assert_eq!(en.link_layer_address(), None);
+
+        let uuid = Duid::from_bytes(&[[0, 4].as_ref(), &[0xab; 16]].concat()).unwrap();
+        assert_eq!(uuid, Duid::Uuid([0xab; 16]));
+        assert_eq!(uuid.duid_type(), 4);
+
+        for duid in &[llt, en, uuid] {
+            assert_eq!(duid.to_string().parse().as_ref(), Ok(duid));
+            assert_eq!(Duid::from_babble(&duid.to_babble()).as_ref(), Ok(duid));
+        }
+
+        for s in &[
+            "",
+            "00:01",
+            "00:01:00:01:2a:3b:4c:5d",
+            "00:03:00:01",
+            "00:04:ab:ab",
+            "00:05:00:01:02",
+            "00:03:00:01:00:1b:21:3a:9f:g4",
+        ] {
+            assert_eq!(s.parse::<Duid>(), Err(DuidParseError));
+        }
+        assert!(Duid::from_bytes(&[0, 3, 0, 1].repeat(40)).is_none());
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Hexdump-style rendering of binary data as Babble.
+
+use crate::Options;
+use std::fmt::Write;
+
+/// Renders data like `xxd` with an offset column and Babble words.
+///
+/// Every line is a complete Babble string of its bytes, so a line can be
+/// decoded on its own, and the bytes can be found by their offset.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::Dump;
+///
+/// let dump = Dump::new().words(3).ascii(true).dump(b"Pineapple");
+/// assert_eq!(
+///     dump,
+///     "00000000: xigak-nyryk-hoxex  Pine\n\
+///      00000004: ximel-bysak-saxix  appl\n\
+///      00000008: xinex              e\n"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dump {
+    words: usize,
+    ascii: bool,
+    options: Options,
+}
+
+impl Dump {
+    /// Return the default dump of 9 words, or 16 bytes, per line.
+    pub fn new() -> Self {
+        Dump {
+            words: 9,
+            ascii: false,
+            options: Options::new(),
+        }
+    }
+
+    /// Print `words` words per line, at least 2.
+    pub fn words(mut self, words: usize) -> Self {
+        self.words = words.max(2);
+        self
+    }
+
+    /// Print a gutter with the printable ASCII characters.
+    pub fn ascii(mut self, ascii: bool) -> Self {
+        self.ascii = ascii;
+        self
+    }
+
+    /// Format the Babble strings with `options`.
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Return the number of bytes per line.
+    pub fn line_len(&self) -> usize {
+        (self.words - 1) * 2
+    }
+
+    /// Render all lines of `bytes`.
+    pub fn dump(&self, bytes: &[u8]) -> String {
+        let mut dump = String::new();
+        for (i, line) in bytes.chunks(self.line_len()).enumerate() {
+            self.write_line(i * self.line_len(), line, &mut dump);
+        }
+        dump
+    }
+
+    /// Render a single line of up to `line_len` bytes at `offset`.
+    pub fn write_line(&self, offset: usize, bytes: &[u8], dump: &mut String) {
+        let babble = self.options.encode(bytes);
+        write!(dump, "{:08x}: {}", offset, babble).unwrap();
+
+        if self.ascii {
+            // Each word has 5 letters and a separator
+            let width = self.words * 6 - 1;
+            let padding = width.saturating_sub(babble.chars().count()) + 2;
+            dump.push_str(&" ".repeat(padding));
+            dump.extend(bytes.iter().map(|&b| match b {
+                b' '..=b'~' => b as char,
+                _ => '.',
+            }));
+        }
+        dump.push('\n');
+    }
+}
+
+impl Default for Dump {
+    fn default() -> Self {
+        Dump::new()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Dump {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Dump::new()
+            .words(u.int_in_range(2..=32)?)
+            .ascii(u.arbitrary()?)
+            .options(u.arbitrary()?))
+    }
+}
@@ -0,0 +1,206 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Emoji fingerprints.
+//!
+//! Each emoji encodes 6 bits with the 64 visually distinct emoji of the
+//! Matrix short authentication string, so that two screens can be
+//! compared at a glance.  Data is encoded as a stream of bits, the last
+//! emoji is padded with zero bits.  The first seven emoji of six bytes
+//! are the same as in the Matrix verification.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::emoji;
+//!
+//! let data = [0x00, 0x10, 0x83];
+//! assert_eq!(emoji::encode(&data), "\u{1f436} \u{1f431} \u{1f981} \u{1f40e}");
+//! assert_eq!(emoji::names(&data), ["Dog", "Cat", "Lion", "Horse"]);
+//! assert_eq!(emoji::decode("\u{1f436} \u{1f431} \u{1f981} \u{1f40e}").unwrap(), data);
+//! ```
+
+use crate::BabbleError;
+
+/// Emoji and their names in order of their 6-bit values.
+const EMOJI: [(&str, &str); 64] = [
+    ("\u{1f436}", "Dog"),
+    ("\u{1f431}", "Cat"),
+    ("\u{1f981}", "Lion"),
+    ("\u{1f40e}", "Horse"),
+    ("\u{1f984}", "Unicorn"),
+    ("\u{1f437}", "Pig"),
+    ("\u{1f418}", "Elephant"),
+    ("\u{1f430}", "Rabbit"),
+    ("\u{1f43c}", "Panda"),
+    ("\u{1f413}", "Rooster"),
+    ("\u{1f427}", "Penguin"),
+    ("\u{1f422}", "Turtle"),
+    ("\u{1f41f}", "Fish"),
+    ("\u{1f419}", "Octopus"),
+    ("\u{1f98b}", "Butterfly"),
+    ("\u{1f337}", "Flower"),
+    ("\u{1f333}", "Tree"),
+    ("\u{1f335}", "Cactus"),
+    ("\u{1f344}", "Mushroom"),
+    ("\u{1f30f}", "Globe"),
+    ("\u{1f319}", "Moon"),
+    ("\u{2601}\u{fe0f}", "Cloud"),
+    ("\u{1f525}", "Fire"),
+    ("\u{1f34c}", "Banana"),
+    ("\u{1f34e}", "Apple"),
+    ("\u{1f353}", "Strawberry"),
+    ("\u{1f33d}", "Corn"),
+    ("\u{1f355}", "Pizza"),
+    ("\u{1f382}", "Cake"),
+    ("\u{2764}\u{fe0f}", "Heart"),
+    ("\u{1f600}", "Smiley"),
+    ("\u{1f916}", "Robot"),
+    ("\u{1f3a9}", "Hat"),
+    ("\u{1f453}", "Glasses"),
+    ("\u{1f527}", "Spanner"),
+    ("\u{1f385}", "Santa"),
+    ("\u{1f44d}", "Thumbs Up"),
+    ("\u{2602}\u{fe0f}", "Umbrella"),
+    ("\u{231b}", "Hourglass"),
+    ("\u{23f0}", "Clock"),
+    ("\u{1f381}", "Gift"),
+    ("\u{1f4a1}", "Light Bulb"),
+    ("\u{1f4d5}", "Book"),
+    ("\u{270f}\u{fe0f}", "Pencil"),
+    ("\u{1f4ce}", "Paperclip"),
+    ("\u{2702}\u{fe0f}", "Scissors"),
+    ("\u{1f512}", "Lock"),
+    ("\u{1f511}", "Key"),
+    ("\u{1f528}", "Hammer"),
+    ("\u{260e}\u{fe0f}", "Telephone"),
+    ("\u{1f3c1}", "Flag"),
+    ("\u{1f682}", "Train"),
+    ("\u{1f6b2}", "Bicycle"),
+    ("\u{2708}\u{fe0f}", "Aeroplane"),
+    ("\u{1f680}", "Rocket"),
+    ("\u{1f3c6}", "Trophy"),
+    ("\u{26bd}", "Ball"),
+    ("\u{1f3b8}", "Guitar"),
+    ("\u{1f3ba}", "Trumpet"),
+    ("\u{1f514}", "Bell"),
+    ("\u{2693}", "Anchor"),
+    ("\u{1f3a7}", "Headphones"),
+    ("\u{1f4c1}", "Folder"),
+    ("\u{1f4cc}", "Pin"),
+];
+
+/// Split the data into 6-bit values, padding the last value with zeros.
+fn values(data: &[u8]) -> impl Iterator<Item = usize> + '_ {
+    let count = (data.len() * 8).div_ceil(6);
+    (0..count).map(move |i| {
+        let bit = i * 6;
+        let byte = bit / 8;
+        let word = u16::from(data[byte]) << 8 | u16::from(*data.get(byte + 1).unwrap_or(&0));
+        usize::from((word >> (10 - bit % 8)) & 0x3f)
+    })
+}
+
+/// Encode data as emoji separated by spaces.
+pub fn encode(data: &[u8]) -> String {
+    values(data)
+        .map(|i| EMOJI[i].0)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Return the English names of the emoji of the data.
+///
+/// The names can be shown with the emoji or read aloud.
+pub fn names(data: &[u8]) -> Vec<&'static str> {
+    values(data).map(|i| EMOJI[i].1).collect()
+}
+
+/// Decode emoji, with or without the emoji presentation selector and
+/// separated by optional whitespace.
+pub fn decode(emoji: &str) -> Result<Vec<u8>, BabbleError> {
+    let mut result = Vec::with_capacity(emoji.len() / 4);
+    let mut bits = 0u16;
+    let mut count = 0;
+
+    for (offset, c) in emoji.char_indices() {
+        if c.is_whitespace() || c == '\u{fe0f}' {
+            continue;
+        }
+        let value = EMOJI
+            .iter()
+            .position(|(e, _)| e.starts_with(c))
+            .ok_or(BabbleError::InvalidCharacter(c, offset))?;
+
+        bits = (bits << 6) | value as u16;
+        count += 6;
+        if count >= 8 {
+            count -= 8;
+            result.push((bits >> count) as u8);
+        }
+    }
+
+    // Padding must be shorter than an emoji and zero
+    if count >= 6 || bits & ((1 << count) - 1) != 0 {
+        return Err(BabbleError::InvalidLength(emoji.len()));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emoji() {
+        for data in [&b""[..], b"\0", b"Pineapple", &[0xff; 3], &[0xab; 7]].iter() {
+            assert_eq!(decode(&encode(data)).as_deref(), Ok(*data));
+            assert_eq!(names(data).len(), (data.len() * 8).div_ceil(6));
+        }
+
+        assert_eq!(names(&[0xff, 0xff, 0xff]), ["Pin"; 4]);
+        assert_eq!(
+            encode(&[0x55, 0x55, 0x55]),
+            ["\u{2601}\u{fe0f}"; 4].join(" ")
+        );
+        assert_eq!(
+            decode("\u{2601}\u{2601}\u{2601}\u{2601}"),
+            Ok(vec![0x55, 0x55, 0x55])
+        );
+
+        assert_eq!(decode("\u{1f436}"), Err(BabbleError::InvalidLength(4)));
+        assert_eq!(
+            decode("\u{1f436}\u{1f431}"),
+            Err(BabbleError::InvalidLength(8))
+        );
+        assert_eq!(
+            decode("\u{1f436} x"),
+            Err(BabbleError::InvalidCharacter('x', 5))
+        );
+    }
+}
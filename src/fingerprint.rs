@@ -0,0 +1,261 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Fingerprints with their hash algorithm.
+
+use crate::{bubblebabble, debabble, ToBabble};
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+/// A digest with the name of its hash algorithm and its Babble.
+///
+/// The fingerprint is printed and parsed as `ALGORITHM/babble`, like
+/// `SHA256/xepib-...`, so it can be stored in databases and config
+/// files as a single string.  The Babble is created once and cached.
+/// With the `sqlx` or `diesel` features it can be used as a `TEXT`
+/// column that is validated when it is loaded.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleFingerprint;
+///
+/// let fpr = BabbleFingerprint::new("SHA256", [0x2a, 0x0a, 0xe5, 0xc0, 0, 0x2, 0, 0x5]);
+/// assert_eq!(fpr.to_string(), "SHA256/xepib-panus-bubub-dubyb-hexyx");
+/// assert_eq!(fpr.to_string().parse(), Ok(fpr));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "diesel",
+    derive(diesel::expression::AsExpression, diesel::deserialize::FromSqlRow),
+    diesel(sql_type = diesel::sql_types::Text)
+)]
+pub struct BabbleFingerprint {
+    algorithm: String,
+    digest: Vec<u8>,
+    // The `ALGORITHM/babble` string
+    text: String,
+}
+
+impl BabbleFingerprint {
+    /// Create a fingerprint from the name of the hash algorithm and the
+    /// digest.
+    pub fn new(algorithm: impl Into<String>, digest: impl Into<Vec<u8>>) -> Self {
+        let (algorithm, digest) = (algorithm.into(), digest.into());
+        BabbleFingerprint {
+            text: format!("{}/{}", algorithm, bubblebabble(&digest)),
+            algorithm,
+            digest,
+        }
+    }
+
+    /// Return the name of the hash algorithm.
+    pub fn algorithm(&self) -> &str {
+        &self.algorithm
+    }
+
+    /// Return the digest.
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Return the Babble of the digest.
+    pub fn babble(&self) -> &str {
+        &self.text[self.algorithm.len() + 1..]
+    }
+}
+
+impl fmt::Display for BabbleFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Error returned when parsing an invalid fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BabbleFingerprintParseError;
+
+impl fmt::Display for BabbleFingerprintParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Babble fingerprint syntax")
+    }
+}
+
+impl error::Error for BabbleFingerprintParseError {}
+
+impl FromStr for BabbleFingerprint {
+    type Err = BabbleFingerprintParseError;
+
+    /// Parse `ALGORITHM/babble` with a non-empty algorithm name.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let (algorithm, babble) = text
+            .split_once('/')
+            .filter(|(algorithm, _)| {
+                !algorithm.is_empty() && !algorithm.contains(char::is_whitespace)
+            })
+            .ok_or(BabbleFingerprintParseError)?;
+        let digest = debabble(babble).map_err(|_| BabbleFingerprintParseError)?;
+
+        Ok(BabbleFingerprint {
+            algorithm: algorithm.to_string(),
+            digest,
+            text: text.to_string(),
+        })
+    }
+}
+
+impl ToBabble for BabbleFingerprint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.digest)
+    }
+}
+
+/// Serialize the fingerprint as an `ALGORITHM/babble` string.
+#[cfg(feature = "serde")]
+impl ::serde::Serialize for BabbleFingerprint {
+    fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> ::serde::Deserialize<'de> for BabbleFingerprint {
+    fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = <Cow<'de, str>>::deserialize(deserializer)?;
+        s.parse().map_err(::serde::de::Error::custom)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<DB: sqlx_core::database::Database> sqlx_core::types::Type<DB> for BabbleFingerprint
+where
+    String: sqlx_core::types::Type<DB>,
+{
+    fn type_info() -> DB::TypeInfo {
+        <String as sqlx_core::types::Type<DB>>::type_info()
+    }
+
+    fn compatible(ty: &DB::TypeInfo) -> bool {
+        <String as sqlx_core::types::Type<DB>>::compatible(ty)
+    }
+}
+
+/// Store the fingerprint as an `ALGORITHM/babble` string.
+#[cfg(feature = "sqlx")]
+impl<'q, DB: sqlx_core::database::Database> sqlx_core::encode::Encode<'q, DB> for BabbleFingerprint
+where
+    String: sqlx_core::encode::Encode<'q, DB>,
+{
+    fn encode_by_ref(
+        &self,
+        buf: &mut DB::ArgumentBuffer<'q>,
+    ) -> Result<sqlx_core::encode::IsNull, sqlx_core::error::BoxDynError> {
+        self.text.encode_by_ref(buf)
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl<'r, DB: sqlx_core::database::Database> sqlx_core::decode::Decode<'r, DB> for BabbleFingerprint
+where
+    String: sqlx_core::decode::Decode<'r, DB>,
+{
+    fn decode(value: DB::ValueRef<'r>) -> Result<Self, sqlx_core::error::BoxDynError> {
+        Ok(String::decode(value)?.parse()?)
+    }
+}
+
+/// Store the fingerprint as an `ALGORITHM/babble` string.
+#[cfg(feature = "diesel")]
+impl<DB: diesel::backend::Backend> diesel::serialize::ToSql<diesel::sql_types::Text, DB>
+    for BabbleFingerprint
+where
+    str: diesel::serialize::ToSql<diesel::sql_types::Text, DB>,
+{
+    fn to_sql<'b>(
+        &'b self,
+        out: &mut diesel::serialize::Output<'b, '_, DB>,
+    ) -> diesel::serialize::Result {
+        self.text.as_str().to_sql(out)
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl<DB: diesel::backend::Backend> diesel::deserialize::FromSql<diesel::sql_types::Text, DB>
+    for BabbleFingerprint
+where
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, DB>,
+{
+    fn from_sql(bytes: DB::RawValue<'_>) -> diesel::deserialize::Result<Self> {
+        Ok(String::from_sql(bytes)?.parse()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_babble_fingerprint() {
+        let fpr = BabbleFingerprint::new("SHA256", b"Pineapple".to_vec());
+        assert_eq!(fpr.to_string(), "SHA256/xigak-nyryk-humil-bosek-sonax");
+        assert_eq!(fpr.to_babble(), fpr.babble());
+        assert_eq!(
+            " SHA256/xigak-nyryk-humil-bosek-sonax\n".parse(),
+            Ok(fpr.clone())
+        );
+
+        let other = BabbleFingerprint::new("SHA1", b"Pineapple".to_vec());
+        assert!(other < fpr);
+        assert!(BabbleFingerprint::new("SHA256", b"Pine".to_vec()) < fpr);
+
+        for s in &[
+            "",
+            "xigak-nyryk-humil-bosek-sonax",
+            "/xigak-nyryk-humil-bosek-sonax",
+            "SHA 256/xigak-nyryk-humil-bosek-sonax",
+            "SHA256/xigak-nyryk-humil-bosek-sanax",
+        ] {
+            assert_eq!(
+                s.parse::<BabbleFingerprint>(),
+                Err(BabbleFingerprintParseError)
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_babble_fingerprint() {
+        let fpr = BabbleFingerprint::new("SHA256", b"Pineapple".to_vec());
+        let json = serde_json::to_string(&fpr).unwrap();
+        assert_eq!(json, r#""SHA256/xigak-nyryk-humil-bosek-sonax""#);
+        assert_eq!(
+            serde_json::from_str::<BabbleFingerprint>(&json).unwrap(),
+            fpr
+        );
+        assert!(serde_json::from_str::<BabbleFingerprint>(r#""SHA256/xigak""#).is_err());
+    }
+}
@@ -0,0 +1,462 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Formatting options for the presentation of Babble strings.
+
+use crate::{bubblebabble, debabble, debabble_stable, stablebabble, BabbleError};
+use crate::{CONSONANTS, VOWELS};
+use std::{fmt, str::FromStr};
+
+/// Separator that is inserted between groups of words.
+const GROUP_SEPARATOR: char = ' ';
+
+/// The vowels and consonants that are used to build the words.
+///
+/// Custom alphabets replace the letters of the standard alphabet by
+/// their position, the structure and the checksum of the words are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Alphabet {
+    vowels: [char; 6],
+    consonants: [char; 17],
+}
+
+impl Alphabet {
+    /// The standard alphabet of the Bubble Babble encoding.
+    pub const STANDARD: Alphabet = Alphabet {
+        vowels: VOWELS,
+        consonants: CONSONANTS,
+    };
+
+    /// Create an alphabet from 6 vowels and 17 consonants.
+    ///
+    /// All letters must be distinct lowercase ASCII letters other than
+    /// `w`, which is reserved for the compressed words of stable Babble.
+    pub fn new(vowels: &str, consonants: &str) -> Result<Self, BabbleError> {
+        if vowels.chars().count() != 6 || consonants.chars().count() != 17 {
+            return Err(BabbleError::InvalidAlphabet);
+        }
+
+        let mut alphabet = Alphabet::STANDARD;
+        for (slot, c) in alphabet.vowels.iter_mut().zip(vowels.chars()) {
+            *slot = c;
+        }
+        for (slot, c) in alphabet.consonants.iter_mut().zip(consonants.chars()) {
+            *slot = c;
+        }
+
+        let letters = alphabet.letters().collect::<Vec<_>>();
+        for (i, c) in letters.iter().enumerate() {
+            if !c.is_ascii_lowercase() || *c == 'w' || letters[..i].contains(c) {
+                return Err(BabbleError::InvalidAlphabet);
+            }
+        }
+
+        Ok(alphabet)
+    }
+
+    /// The 6 vowels.
+    pub fn vowels(&self) -> &[char; 6] {
+        &self.vowels
+    }
+
+    /// The 17 consonants, the last one starts and ends every string.
+    pub fn consonants(&self) -> &[char; 17] {
+        &self.consonants
+    }
+
+    fn letters(&self) -> impl Iterator<Item = char> + '_ {
+        self.vowels.iter().chain(self.consonants.iter()).copied()
+    }
+
+    /// Map a letter of the standard alphabet to this alphabet.
+    fn encode(&self, c: char) -> char {
+        if let Some(i) = VOWELS.iter().position(|&v| v == c) {
+            self.vowels[i]
+        } else if let Some(i) = CONSONANTS.iter().position(|&v| v == c) {
+            self.consonants[i]
+        } else {
+            c
+        }
+    }
+
+    /// Map a letter of this alphabet to the standard alphabet.
+    ///
+    /// Unknown characters are mapped to `?` to be rejected by the decoder.
+    fn decode(&self, c: char) -> char {
+        if let Some(i) = self.vowels.iter().position(|&v| v == c) {
+            VOWELS[i]
+        } else if let Some(i) = self.consonants.iter().position(|&v| v == c) {
+            CONSONANTS[i]
+        } else if c == 'w' || c.is_ascii_digit() {
+            c
+        } else {
+            '?'
+        }
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::STANDARD
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let vowels = self.vowels.iter().collect::<String>();
+        let consonants = self.consonants.iter().collect::<String>();
+        write!(f, "{}:{}", vowels, consonants)
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = BabbleError;
+
+    /// Parse `standard` or an alphabet in the `VOWELS:CONSONANTS` form.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            _ if s == "standard" => Ok(Alphabet::STANDARD),
+            Some((vowels, consonants)) => Alphabet::new(vowels, consonants),
+            None => Err(BabbleError::InvalidAlphabet),
+        }
+    }
+}
+
+/// Options to change the presentation of Babble strings.
+///
+/// The words are the same as generated by `bubblebabble` or
+/// `stablebabble`, but they can be joined with a different separator,
+/// grouped, uppercased, or spelled with a custom `Alphabet`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::Options;
+///
+/// let options = Options::new().separator('.').unwrap().group(2).uppercase(true);
+/// let babble = options.encode(b"Pineapple");
+/// assert_eq!(babble, "XIGAK.NYRYK HUMIL.BOSEK SONAX");
+/// assert_eq!(options.decode(&babble).unwrap(), b"Pineapple");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    stable: bool,
+    separator: char,
+    group: usize,
+    uppercase: bool,
+    alphabet: Alphabet,
+}
+
+impl Options {
+    /// Return the default options of the standard format.
+    pub fn new() -> Self {
+        Options {
+            stable: false,
+            separator: '-',
+            group: 0,
+            uppercase: false,
+            alphabet: Alphabet::STANDARD,
+        }
+    }
+
+    /// Use the stable Babble format without checksum.
+    pub fn stable(mut self, stable: bool) -> Self {
+        self.stable = stable;
+        self
+    }
+
+    /// Join the words with `separator` instead of `-`.
+    ///
+    /// An alphanumeric separator could not be told apart from the words
+    /// and returns `BabbleError::InvalidCharacter`.
+    pub fn separator(mut self, separator: char) -> Result<Self, BabbleError> {
+        if separator.is_alphanumeric() {
+            return Err(BabbleError::InvalidCharacter(separator, 0));
+        }
+        self.separator = separator;
+        Ok(self)
+    }
+
+    /// Split the words into groups of `group` words separated by a space,
+    /// 0 disables grouping.
+    pub fn group(mut self, group: usize) -> Self {
+        self.group = group;
+        self
+    }
+
+    /// Print the words in uppercase letters.
+    pub fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+
+    /// Spell the words with a custom alphabet.
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Return `true` if the output only differs from the plain format
+    /// by the `stable` option.
+    pub fn is_plain(&self) -> bool {
+        *self == Options::new().stable(self.stable)
+    }
+
+    /// Convert bytes to a formatted Babble string.
+    pub fn encode(&self, bytes: &[u8]) -> String {
+        let babble = if self.stable {
+            stablebabble(bytes)
+        } else {
+            bubblebabble(bytes)
+        };
+        if self.is_plain() {
+            return babble;
+        }
+
+        let mut formatted = String::with_capacity(babble.len());
+        for (i, word) in babble.split('-').enumerate() {
+            if i > 0 && self.group > 0 && i % self.group == 0 {
+                formatted.push(GROUP_SEPARATOR);
+            } else if i > 0 {
+                formatted.push(self.separator);
+            }
+            for c in word.chars().map(|c| self.alphabet.encode(c)) {
+                formatted.push(if self.uppercase {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                });
+            }
+        }
+
+        formatted
+    }
+
+    /// Convert a formatted Babble string back to bytes.
+    ///
+    /// Letters are accepted in any case and whitespace is accepted
+    /// between words.  The offsets of errors refer to `babble`.
+    pub fn decode(&self, babble: &str) -> Result<Vec<u8>, BabbleError> {
+        // Offsets of the trimmed string are moved past the leading whitespace
+        let start = babble.len() - babble.trim_start().len();
+        let trimmed = babble.trim();
+        let mut normalized = String::with_capacity(trimmed.len());
+        let mut offsets = Vec::with_capacity(trimmed.len());
+
+        let mut space = None;
+
+        for (offset, c) in trimmed.char_indices() {
+            let offset = start + offset;
+            // Group separators and line breaks are accepted between words
+            if c.is_whitespace() && c != self.separator {
+                space = space.or(Some(offset));
+                continue;
+            }
+            let c = if c == self.separator {
+                '-'
+            } else {
+                self.alphabet.decode(c.to_ascii_lowercase())
+            };
+            if let Some(space) = space.take() {
+                if c != '-' && !normalized.ends_with('-') {
+                    normalized.push('-');
+                    offsets.push(space);
+                }
+            }
+            normalized.push(c);
+            offsets.push(offset);
+        }
+
+        let result = if self.stable {
+            debabble_stable(&normalized)
+        } else {
+            debabble(&normalized)
+        };
+
+        result.map_err(|err| match err {
+            BabbleError::InvalidCharacter(_, i) => match offsets.get(i) {
+                Some(&offset) => {
+                    let c = babble[offset..].chars().next().unwrap_or('?');
+                    BabbleError::InvalidCharacter(c, offset)
+                }
+                None => BabbleError::InvalidLength(trimmed.len()),
+            },
+            BabbleError::ChecksumMismatch(i) => BabbleError::ChecksumMismatch(
+                offsets.get(i).copied().unwrap_or(start + trimmed.len()),
+            ),
+            BabbleError::InvalidLength(_) => BabbleError::InvalidLength(trimmed.len()),
+            err => err,
+        })
+    }
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options::new()
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Alphabet {
+    /// Return a random permutation of the letters other than `w`.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let mut letters = ('a'..='z').filter(|&c| c != 'w').collect::<Vec<_>>();
+        let mut alphabet = Alphabet::STANDARD;
+        for slot in alphabet
+            .vowels
+            .iter_mut()
+            .chain(alphabet.consonants.iter_mut())
+        {
+            *slot = letters.swap_remove(u.choose_index(letters.len())?);
+        }
+        Ok(alphabet)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Options {
+    /// Return valid options, alphanumeric separators are replaced by `-`.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Options::new()
+            .separator(char::arbitrary(u)?)
+            .unwrap_or_default()
+            .stable(u.arbitrary()?)
+            .group(u.int_in_range(0..=16)?)
+            .uppercase(u.arbitrary()?)
+            .alphabet(u.arbitrary()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_options() {
+        let tests: [&[u8]; 4] = [b"", b"Pineapple", b"1234567890", &[0; 16]];
+        let alphabet = Alphabet::new("aeiouy", "bcdfghjklmnpqrstv").unwrap();
+        let options = [
+            Options::new(),
+            Options::new().stable(true).group(3),
+            Options::new().separator(' ').unwrap().uppercase(true),
+            Options::new()
+                .stable(true)
+                .separator(':')
+                .unwrap()
+                .alphabet(alphabet),
+        ];
+
+        for bytes in tests.iter() {
+            for options in options.iter() {
+                assert_eq!(options.decode(&options.encode(bytes)).unwrap(), *bytes);
+            }
+        }
+
+        assert_eq!(
+            Options::new().separator('a'),
+            Err(BabbleError::InvalidCharacter('a', 0))
+        );
+        let options = Options::new().separator('.').unwrap().group(2);
+        assert_eq!(
+            options.encode(b"Pineapple"),
+            "xigak.nyryk humil.bosek sonax"
+        );
+        assert_eq!(
+            options.decode("XIGAK.NYRYK\n  humil.bosek. sonax").unwrap(),
+            b"Pineapple"
+        );
+        assert_eq!(
+            options.decode("xigak.nyryk humil-bosek sonax"),
+            Err(BabbleError::InvalidCharacter('-', 17))
+        );
+        assert_eq!(
+            options.decode("xigak.nyryk humil.bosek sanax"),
+            Err(BabbleError::ChecksumMismatch(25))
+        );
+        assert_eq!(
+            options.decode("\n  xigak.nyryk humil-bosek sonax"),
+            Err(BabbleError::InvalidCharacter('-', 20))
+        );
+        assert_eq!(
+            options.decode("  xigak.nyryk humil.bosek sanax "),
+            Err(BabbleError::ChecksumMismatch(27))
+        );
+        assert_eq!(
+            Options::new().alphabet(alphabet).encode(b"Pineapple"),
+            "vigaj-mypyj-hulik-boqej-qomav"
+        );
+
+        assert_eq!("standard".parse(), Ok(Alphabet::STANDARD));
+        assert_eq!(alphabet.to_string().parse(), Ok(alphabet));
+        assert_eq!(
+            Alphabet::new("aeiouw", "bcdfghklmnprstvzx"),
+            Err(BabbleError::InvalidAlphabet)
+        );
+        assert_eq!(
+            Alphabet::new("aeiouy", "bcdfghklmnprstvza"),
+            Err(BabbleError::InvalidAlphabet)
+        );
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_options() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let data = (0..4096u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect::<Vec<_>>();
+        let mut u = Unstructured::new(&data);
+
+        for _ in 0..64 {
+            let options = Options::arbitrary(&mut u).unwrap();
+            let alphabet = options.alphabet;
+            assert_eq!(
+                Alphabet::new(
+                    &alphabet.vowels.iter().collect::<String>(),
+                    &alphabet.consonants.iter().collect::<String>()
+                ),
+                Ok(alphabet)
+            );
+            let bytes = Vec::<u8>::arbitrary(&mut u).unwrap();
+            assert_eq!(options.decode(&options.encode(&bytes)), Ok(bytes));
+        }
+    }
+
+    #[cfg(feature = "diagnostics")]
+    #[test]
+    fn test_diagnostic_offsets() {
+        use miette::Diagnostic;
+
+        // The offsets of formatted Babble refer to the input
+        let options = Options::new().separator(' ').unwrap();
+        let babble = "xigak nyryk humil bosek sanax";
+        let err = options.decode(babble).unwrap_err().diagnostic(babble);
+        let label = err.labels().unwrap().next().unwrap();
+        assert_eq!(&babble[label.offset()..][..label.len()], "sanax");
+    }
+}
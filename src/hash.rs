@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Ready-made Babble fingerprints of common hash functions.
+
+/// Hash `data` with SHA-256 and convert the digest to Babble.
+///
+/// # Examples
+///
+/// ```rust
+/// let babble = bubblebabble::sha256_babble(b"Pineapple");
+/// assert!(babble.starts_with("xobag-deleb-bymes-"));
+/// ```
+#[cfg(feature = "sha2")]
+pub fn sha256_babble(data: impl AsRef<[u8]>) -> String {
+    crate::babble_digest::<sha2::Sha256>(data)
+}
+
+/// Hash `data` with SHA-512 and convert the digest to Babble.
+#[cfg(feature = "sha2")]
+pub fn sha512_babble(data: impl AsRef<[u8]>) -> String {
+    crate::babble_digest::<sha2::Sha512>(data)
+}
+
+/// Hash `data` with BLAKE3 and convert the 256-bit digest to Babble.
+#[cfg(feature = "blake3")]
+pub fn blake3_babble(data: impl AsRef<[u8]>) -> String {
+    crate::bubblebabble(blake3::hash(data.as_ref()).as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bubblebabble;
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn test_sha2_babble() {
+        use sha2::{Digest, Sha256, Sha512};
+
+        let data = b"Pineapple";
+        assert_eq!(sha256_babble(data), bubblebabble(&Sha256::digest(data)));
+        assert_eq!(sha512_babble(data), bubblebabble(&Sha512::digest(data)));
+        assert_eq!(sha512_babble(data).split('-').count(), 33);
+    }
+
+    #[cfg(feature = "blake3")]
+    #[test]
+    fn test_blake3_babble() {
+        let data = b"Pineapple";
+        assert_eq!(
+            blake3_babble(data),
+            bubblebabble(blake3::hash(data).as_bytes())
+        );
+    }
+}
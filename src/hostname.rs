@@ -0,0 +1,91 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Host names from hardware identifiers.
+
+use crate::{bubblebabble, siphash::SipHasher};
+use std::hash::Hasher;
+
+/// Maximum length of a DNS label.
+pub const MAX_LABEL_LEN: usize = 63;
+
+/// Return a pronounceable host name for a device identifier like a MAC
+/// address or a serial number.
+///
+/// Babble only contains lowercase letters and hyphens between the words,
+/// so it is a valid DNS label and mDNS name.  Identifiers of up to 19
+/// bytes are encoded directly and can be decoded back to the identifier,
+/// longer ones are hashed with SipHash-2-4 to a name of five words that
+/// fits the label limit of 63 characters.  The name only depends on the
+/// identifier, so a device keeps its name across reboots and networks.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{hostname, MacAddr};
+///
+/// let mac: MacAddr = "00:1b:21:3a:9f:c4".parse().unwrap();
+/// assert_eq!(hostname(mac.as_bytes()), "xebac-rimaf-pelys-gixux");
+///
+/// let serial = hostname(b"SN-2019-0042-7781-AB93-C");
+/// assert_eq!(serial.split('-').count(), 5);
+/// ```
+pub fn hostname(id: &[u8]) -> String {
+    // Each 2 bytes add a word of 5 letters and a hyphen
+    if (id.len() / 2 + 1) * 6 - 1 <= MAX_LABEL_LEN {
+        bubblebabble(id)
+    } else {
+        let mut hasher = SipHasher::new(0, 0);
+        hasher.write(id);
+        bubblebabble(&hasher.finish().to_be_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hostname() {
+        for len in 0..64 {
+            let id = vec![0xa5; len];
+            let name = hostname(&id);
+            assert!(!name.is_empty() && name.len() <= MAX_LABEL_LEN);
+            assert!(name
+                .bytes()
+                .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-'));
+            assert!(!name.starts_with('-') && !name.ends_with('-'));
+            assert_eq!(crate::debabble(&name).unwrap() == id, len < 20);
+        }
+        assert_eq!(hostname(&[0xa5; 19]).len(), 59);
+        assert_ne!(hostname(&[0xa5; 20]), hostname(&[0xa5; 21]));
+        assert_eq!(
+            hostname(b"0123456789abcdefghij"),
+            "xomil-dinyc-nukit-raryf-hixax"
+        );
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Memorable names from content hashes.
+//!
+//! Servers, builds, or datasets can be named after their content hash
+//! with the first Babble words of the hash, like `xepib-panus`.  The
+//! name is a prefix of the Babble of the full hash, so it can be
+//! checked against the fingerprint of the content.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::ident::{self, Ident};
+//!
+//! let hash = [0x2a, 0x0a, 0xe5, 0xc0, 0, 0x2, 0, 0x5];
+//! assert_eq!(ident::name(&hash), "xepib-panus");
+//! assert_eq!(Ident::new().words(3).capitalize(true).name(&hash), "Xepib-Panus-Bubub");
+//! ```
+
+use crate::{bubblebabble, security::words_needed};
+
+/// Default number of words of a name.
+pub const DEFAULT_WORDS: usize = 2;
+
+/// Return the name of the hash with the default options.
+pub fn name(hash: &[u8]) -> String {
+    Ident::new().name(hash)
+}
+
+/// Options to create names from hashes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ident {
+    words: usize,
+    capitalize: bool,
+}
+
+impl Ident {
+    /// Create options for lowercase names of two words.
+    pub fn new() -> Self {
+        Ident {
+            words: DEFAULT_WORDS,
+            capitalize: false,
+        }
+    }
+
+    /// Create options with enough words to name `items` hashes with a
+    /// collision probability of at most `probability`.
+    ///
+    /// The number of bits follows from the birthday bound `n^2 / 2^(b+1)`
+    /// and names have at least two words.  A thousand builds with a 1%
+    /// chance of a collision need two words, a million datasets with a
+    /// chance of one in a million need four.
+    pub fn for_collisions(items: u64, probability: f64) -> Self {
+        let probability = probability.clamp(f64::MIN_POSITIVE, 1.0);
+        let bits = 2.0 * (items.max(1) as f64).log2() - 1.0 - probability.log2();
+        let words = words_needed(bits.max(0.0).ceil() as u32);
+        Self::new().words(words.max(DEFAULT_WORDS))
+    }
+
+    /// Set the number of words, at least one.
+    pub fn words(mut self, words: usize) -> Self {
+        self.words = words.max(1);
+        self
+    }
+
+    /// Capitalize each word, like `Xepib-Panus`.
+    pub fn capitalize(mut self, capitalize: bool) -> Self {
+        self.capitalize = capitalize;
+        self
+    }
+
+    /// Return the name of the hash.
+    ///
+    /// The hash needs at least `2 * words` bytes, shorter input
+    /// gives fewer words.
+    pub fn name(&self, hash: &[u8]) -> String {
+        let len = hash.len().min(self.words * 2);
+        bubblebabble(&hash[..len])
+            .split('-')
+            .take(self.words)
+            .map(|word| {
+                if self.capitalize {
+                    word[..1].to_ascii_uppercase() + &word[1..]
+                } else {
+                    word.to_string()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+}
+
+impl Default for Ident {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ident() {
+        let hash = (0..32).collect::<Vec<u8>>();
+        let babble = bubblebabble(&hash);
+        for words in 1..=4 {
+            let name = Ident::new().words(words).name(&hash);
+            assert_eq!(name.split('-').count(), words);
+            assert!(babble.starts_with(&name));
+        }
+        assert_eq!(name(b"\x2a"), bubblebabble(b"\x2a"));
+        assert_eq!(Ident::new().words(0), Ident::new().words(1));
+
+        let capitalized = Ident::new().capitalize(true).name(&hash);
+        assert_eq!(capitalized.to_lowercase(), name(&hash));
+        assert!(capitalized
+            .split('-')
+            .all(|word| word.starts_with(char::is_uppercase)));
+
+        assert_eq!(Ident::for_collisions(1000, 0.01), Ident::new());
+        assert_eq!(
+            Ident::for_collisions(1_000_000, 1e-6),
+            Ident::new().words(4)
+        );
+        assert_eq!(Ident::for_collisions(0, 1.0), Ident::new());
+    }
+}
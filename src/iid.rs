@@ -0,0 +1,94 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! IPv6 interface identifiers.
+
+use crate::{bubblebabble, MacAddr};
+use std::{convert::TryInto, net::Ipv6Addr};
+
+/// Return the 64-bit interface identifier of an IPv6 address.
+pub fn interface_id(addr: &Ipv6Addr) -> [u8; 8] {
+    addr.octets()[8..].try_into().unwrap()
+}
+
+/// Return the Babble of the interface identifier of an IPv6 address.
+///
+/// The identifier stays the same when a device moves between networks
+/// or its prefix is renumbered, so a device with a SLAAC address can be
+/// recognized by the Babble of its identifier alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{iid_babble, MacAddr};
+///
+/// let mac: MacAddr = "00:1b:21:3a:9f:c4".parse().unwrap();
+/// let addr = "2001:db8::21b:21ff:fe3a:9fc4".parse().unwrap();
+/// assert_eq!(iid_babble(&addr), "xebic-rumiz-zyzef-palys-goxox");
+/// assert_eq!(iid_babble(&addr), bubblebabble::bubblebabble(&mac.interface_id()));
+/// ```
+pub fn iid_babble(addr: &Ipv6Addr) -> String {
+    bubblebabble(&interface_id(addr))
+}
+
+/// Return the SLAAC address of a MAC address in a /64 prefix.
+///
+/// The upper 64 bits of `prefix` are combined with the modified EUI-64
+/// interface identifier of `mac`.
+pub fn slaac_addr(prefix: &Ipv6Addr, mac: &MacAddr) -> Ipv6Addr {
+    let mut octets = prefix.octets();
+    octets[8..].copy_from_slice(&mac.interface_id());
+    octets.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interface_id() {
+        let mac: MacAddr = "00:1b:21:3a:9f:c4".parse().unwrap();
+        let prefix: Ipv6Addr = "2001:db8:1:2::".parse().unwrap();
+        let addr = slaac_addr(&prefix, &mac);
+
+        assert_eq!(
+            addr,
+            "2001:db8:1:2:21b:21ff:fe3a:9fc4"
+                .parse::<Ipv6Addr>()
+                .unwrap()
+        );
+        assert_eq!(interface_id(&addr), mac.interface_id());
+
+        let moved = slaac_addr(&"fd00::".parse().unwrap(), &mac);
+        assert_ne!(moved, addr);
+        assert_eq!(iid_babble(&moved), iid_babble(&addr));
+        assert_eq!(
+            iid_babble(&Ipv6Addr::LOCALHOST),
+            bubblebabble(&[0, 0, 0, 0, 0, 0, 0, 1])
+        );
+    }
+}
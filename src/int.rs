@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Babble of integers with an explicit byte order.
+
+use crate::{bubblebabble, BabbleError, FromBabble};
+
+/// The byte order of an encoded integer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    /// Most significant byte first, the network byte order.
+    #[default]
+    Big,
+    /// Least significant byte first.
+    Little,
+}
+
+macro_rules! int_babble {
+    ($($int:ident, $encode:ident, $decode:ident;)*) => {
+        $(
+            #[doc = concat!("Convert a `", stringify!($int), "` to Babble in the byte order `endian`.")]
+            pub fn $encode(value: $int, endian: Endianness) -> String {
+                match endian {
+                    Endianness::Big => bubblebabble(&value.to_be_bytes()),
+                    Endianness::Little => bubblebabble(&value.to_le_bytes()),
+                }
+            }
+
+            #[doc = concat!("Convert Babble in the byte order `endian` back to a `", stringify!($int), "`.")]
+            ///
+            /// A string of the wrong length is returned as
+            /// `BabbleError::InvalidLength`.
+            pub fn $decode(babble: &str, endian: Endianness) -> Result<$int, BabbleError> {
+                let bytes = FromBabble::from_babble(babble)?;
+                Ok(match endian {
+                    Endianness::Big => $int::from_be_bytes(bytes),
+                    Endianness::Little => $int::from_le_bytes(bytes),
+                })
+            }
+        )*
+    };
+}
+
+int_babble! {
+    u16, babble_u16, debabble_u16;
+    u32, babble_u32, debabble_u32;
+    u64, babble_u64, debabble_u64;
+    u128, babble_u128, debabble_u128;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_int_babble() {
+        let value = 0x0102_0304;
+        let babble = babble_u32(value, Endianness::Big);
+        assert_eq!(babble, bubblebabble(&[1, 2, 3, 4]));
+        assert_eq!(
+            babble_u32(value, Endianness::Little),
+            bubblebabble(&[4, 3, 2, 1])
+        );
+        assert_eq!(debabble_u32(&babble, Endianness::Big), Ok(value));
+        assert_eq!(debabble_u32(&babble, Endianness::Little), Ok(0x0403_0201));
+
+        assert_eq!(
+            babble_u16(22, Endianness::default()),
+            bubblebabble(&[0, 22])
+        );
+        let babble = babble_u128(u128::MAX, Endianness::Little);
+        assert_eq!(debabble_u128(&babble, Endianness::Little), Ok(u128::MAX));
+        assert_eq!(
+            debabble_u64(&babble, Endianness::Big),
+            Err(BabbleError::InvalidLength(babble.len()))
+        );
+    }
+}
@@ -0,0 +1,100 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! IP addresses from Babble in either format.
+
+use crate::{debabble, debabble_stable, traits::ip_from_bytes, BabbleError};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Parse an IPv6 address from Bubble Babble or stable Babble.
+///
+/// The string is decoded as Bubble Babble first, which is verified by
+/// its checksum, and as stable Babble otherwise.  Strings that do not
+/// encode 16 bytes are returned as `BabbleError::InvalidLength`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::ipv6_from_babble;
+/// use std::net::Ipv6Addr;
+///
+/// assert_eq!(ipv6_from_babble("xebab-7wa-caxax"), Ok(Ipv6Addr::LOCALHOST));
+/// assert_eq!(
+///     ipv6_from_babble("xepib-panus-bubub-dubyb-hilyz-nefas-myzug-mihos-bexux"),
+///     Ok("2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0".parse().unwrap())
+/// );
+/// ```
+pub fn ipv6_from_babble(babble: &str) -> Result<Ipv6Addr, BabbleError> {
+    match ip_from_babble(babble)? {
+        IpAddr::V6(addr) => Ok(addr),
+        IpAddr::V4(_) => Err(BabbleError::InvalidLength(babble.len())),
+    }
+}
+
+/// Parse an IPv4 address from Bubble Babble or stable Babble.
+pub fn ipv4_from_babble(babble: &str) -> Result<Ipv4Addr, BabbleError> {
+    match ip_from_babble(babble)? {
+        IpAddr::V4(addr) => Ok(addr),
+        IpAddr::V6(_) => Err(BabbleError::InvalidLength(babble.len())),
+    }
+}
+
+/// Parse an IPv4 or IPv6 address of 4 or 16 bytes from Bubble Babble
+/// or stable Babble.
+pub fn ip_from_babble(babble: &str) -> Result<IpAddr, BabbleError> {
+    let babble = babble.trim();
+    let bytes = debabble(babble).or_else(|err| debabble_stable(babble).map_err(|_| err))?;
+    ip_from_bytes(bytes).ok_or(BabbleError::InvalidLength(babble.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ToBabble;
+
+    #[test]
+    fn test_ip_from_babble() {
+        let v6: Ipv6Addr = "2001:db8::1".parse().unwrap();
+        let v4 = Ipv4Addr::new(192, 0, 2, 1);
+        for babble in &[v6.to_babble(), v6.to_stablebabble()] {
+            assert_eq!(ipv6_from_babble(babble), Ok(v6));
+            assert_eq!(ip_from_babble(babble), Ok(IpAddr::V6(v6)));
+            assert!(ipv4_from_babble(babble).is_err());
+        }
+        for babble in &[v4.to_babble(), v4.to_stablebabble()] {
+            assert_eq!(ipv4_from_babble(babble), Ok(v4));
+            assert!(ipv6_from_babble(babble).is_err());
+        }
+
+        let pineapple = "xigak-nyryk-humil-bosek-sonax";
+        assert_eq!(
+            ip_from_babble(pineapple),
+            Err(BabbleError::InvalidLength(29))
+        );
+        assert!(ip_from_babble("xigak-nyryk-humil-bosek-sonox").is_err());
+    }
+}
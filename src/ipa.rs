@@ -0,0 +1,174 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Pronunciation guides of Babble words.
+//!
+//! Every letter of a Babble word has a single sound, so the words can
+//! be transcribed to the International Phonetic Alphabet or to a
+//! simplified English respelling.  Readers who are not native speakers
+//! then pronounce the words the same way and the listener can match
+//! them.  `c` is spoken like "ch" and `x` like "ks" to keep them apart
+//! from `k`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::ipa;
+//!
+//! assert_eq!(ipa::transcribe("xebab-7wa-caxax"), "/ksɛ.bab/ 7 /wa/ /tʃa.ksaks/");
+//! assert_eq!(ipa::respell("xigak-nyryk"), "ksee-gahk nih-rihk");
+//! ```
+
+/// A letter with its IPA and respelling.
+type Sound = (char, &'static str, &'static str);
+
+/// IPA and respelling of each letter of the Babble alphabet, including
+/// the `w` of the stable format.
+const SOUNDS: [Sound; 24] = [
+    // Vowels
+    ('a', "a", "ah"),
+    ('e', "ɛ", "eh"),
+    ('i', "i", "ee"),
+    ('o', "o", "oh"),
+    ('u', "u", "oo"),
+    ('y', "ɪ", "ih"),
+    // Consonants
+    ('b', "b", "b"),
+    ('c', "tʃ", "ch"),
+    ('d', "d", "d"),
+    ('f', "f", "f"),
+    ('g', "ɡ", "g"),
+    ('h', "h", "h"),
+    ('k', "k", "k"),
+    ('l', "l", "l"),
+    ('m', "m", "m"),
+    ('n', "n", "n"),
+    ('p', "p", "p"),
+    ('r', "r", "r"),
+    ('s', "s", "s"),
+    ('t', "t", "t"),
+    ('v', "v", "v"),
+    ('z', "z", "z"),
+    ('x', "ks", "ks"),
+    ('w', "w", "w"),
+];
+
+/// Transcribe each word to IPA between slashes, separated by spaces.
+///
+/// Syllables are separated by a dot and counters of the stable format
+/// are kept as they are.
+pub fn transcribe(babble: &str) -> String {
+    guide(babble, |sound| sound.1, '.')
+        .iter()
+        .map(|word| match word.chars().next() {
+            Some(c) if c.is_ascii_digit() => word.clone(),
+            _ => format!("/{}/", word),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Respell each word with English sounds, separated by spaces.
+///
+/// Syllables are separated by a dash.
+pub fn respell(babble: &str) -> String {
+    guide(babble, |sound| sound.2, '-').join(" ")
+}
+
+fn guide(babble: &str, sound: fn(&Sound) -> &'static str, dot: char) -> Vec<String> {
+    babble
+        .split(|c: char| c == '-' || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            // Split counters like `7wa` from the word
+            let digits = word
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(word.len());
+            let (counter, word) = word.split_at(digits);
+            let counter = Some(counter.to_string()).filter(|c| !c.is_empty());
+            counter
+                .into_iter()
+                .chain(Some(word_guide(word, sound, dot)))
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+fn word_guide(word: &str, sound: fn(&Sound) -> &'static str, dot: char) -> String {
+    let is_vowel = |c: Option<char>| c.is_some_and(|c| "aeiouy".contains(c));
+    let chars = word
+        .chars()
+        .map(|c| c.to_ascii_lowercase())
+        .collect::<Vec<_>>();
+    let mut guide = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        // A consonant between two vowels starts the next syllable
+        if i > 0
+            && !is_vowel(Some(c))
+            && is_vowel(chars.get(i - 1).copied())
+            && is_vowel(chars.get(i + 1).copied())
+        {
+            guide.push(dot);
+        }
+        match SOUNDS.iter().find(|s| s.0 == c) {
+            Some(s) => guide.push_str(sound(s)),
+            None => guide.push(c),
+        }
+    }
+
+    guide
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ipa() {
+        let babble = "xigak-nyryk-humil-bosek-sonax";
+        assert_eq!(
+            transcribe(babble),
+            "/ksi.ɡak/ /nɪ.rɪk/ /hu.mil/ /bo.sɛk/ /so.naks/"
+        );
+        assert_eq!(
+            respell(babble),
+            "ksee-gahk nih-rihk hoo-meel boh-sehk soh-nahks"
+        );
+        assert_eq!(transcribe("XEXAX"), "/ksɛ.ksaks/");
+        assert_eq!(respell("xebab-12wa"), "kseh-bahb 12 wah");
+        assert_eq!(transcribe(""), "");
+
+        // Every sound is distinct
+        for (i, a) in SOUNDS.iter().enumerate() {
+            for b in &SOUNDS[i + 1..] {
+                assert_ne!(a.1, b.1);
+                assert_ne!(a.2, b.2);
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! JSON Web Key thumbprints (RFC 7638).
+
+use crate::{base64, ToBabble};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::{borrow::Cow, error, fmt};
+
+/// The SHA-256 thumbprint of a JSON Web Key.
+///
+/// The thumbprint is the digest of the required members of the key in
+/// lexicographic order, so it identifies the key independently of
+/// optional members like `kid` or `use`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{JwkThumbprint, ToBabble};
+///
+/// let jwk = r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+/// let thumbprint = JwkThumbprint::from_json(jwk).unwrap();
+/// assert_eq!(
+///     thumbprint.base64url(),
+///     "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+/// );
+/// println!("{}", thumbprint.to_babble());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JwkThumbprint {
+    /// The SHA-256 digest of the canonical JSON of the key.
+    pub digest: Vec<u8>,
+}
+
+impl JwkThumbprint {
+    /// Compute the thumbprint of a JWK in JSON.
+    pub fn from_json(jwk: &str) -> Result<Self, JwkError> {
+        let jwk = serde_json::from_str::<Map<String, Value>>(jwk)
+            .map_err(|err| JwkError(err.to_string()))?;
+        let member = |name: &str| match jwk.get(name) {
+            Some(Value::String(value)) => Ok(value),
+            _ => Err(JwkError(format!("missing {} member", name))),
+        };
+
+        // The required members of each key type in lexicographic order
+        let members = match member("kty")?.as_str() {
+            "EC" => ["crv", "kty", "x", "y"].as_ref(),
+            "OKP" => ["crv", "kty", "x"].as_ref(),
+            "RSA" => ["e", "kty", "n"].as_ref(),
+            "oct" => ["k", "kty"].as_ref(),
+            kty => return Err(JwkError(format!("unsupported key type {}", kty))),
+        };
+        let canonical = members
+            .iter()
+            .map(|name| Ok((name.to_string(), member(name)?.clone().into())))
+            .collect::<Result<Map<_, _>, _>>()?;
+        let json = serde_json::to_string(&canonical).map_err(|err| JwkError(err.to_string()))?;
+
+        Ok(JwkThumbprint {
+            digest: Sha256::digest(json.as_bytes()).to_vec(),
+        })
+    }
+
+    /// Return the thumbprint in the unpadded base64url form of RFC 7638.
+    pub fn base64url(&self) -> String {
+        base64::encode(&self.digest)
+            .trim_end_matches('=')
+            .replace('+', "-")
+            .replace('/', "_")
+    }
+}
+
+impl fmt::Display for JwkThumbprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.base64url(), self.to_babble())
+    }
+}
+
+impl ToBabble for JwkThumbprint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.digest)
+    }
+}
+
+/// Error returned for invalid JSON Web Keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JwkError(String);
+
+impl fmt::Display for JwkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid JWK: {}", self.0)
+    }
+}
+
+impl error::Error for JwkError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jwk_thumbprint() {
+        // RFC 7638, section 3.1
+        let jwk = r#"{
+            "kty": "RSA",
+            "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+            "e": "AQAB",
+            "alg": "RS256",
+            "kid": "2011-04-29"
+        }"#;
+        let thumbprint = JwkThumbprint::from_json(jwk).unwrap();
+
+        assert_eq!(
+            thumbprint.base64url(),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+        assert_eq!(
+            thumbprint.to_string(),
+            format!("{} {}", thumbprint.base64url(), thumbprint.to_babble())
+        );
+
+        for jwk in &[
+            "",
+            "[]",
+            r#"{"kty":"EC","crv":"P-256"}"#,
+            r#"{"kty":"XYZ"}"#,
+        ] {
+            assert!(JwkThumbprint::from_json(jwk).is_err());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Verification strings derived from session keys.
+
+use crate::bubblebabble;
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/// Derive a short Babble string of `words` words from key material
+/// with HKDF-SHA256.
+///
+/// Both endpoints of a session derive the same string from the shared
+/// input key material `ikm`, an optional `salt`, and the `info` that
+/// binds the string to its context, like a protocol name and the
+/// session identifiers.  The words encode the first `2 * words - 1`
+/// bytes of the output.  Returns `None` if `words` is zero or larger
+/// than the output of HKDF allows.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::derive_verification_babble;
+///
+/// let local = derive_verification_babble(b"shared secret", b"", b"example call 42", 3);
+/// let remote = derive_verification_babble(b"shared secret", b"", b"example call 42", 3);
+/// assert_eq!(local, remote);
+/// assert_eq!(local.unwrap().split('-').count(), 3);
+/// ```
+pub fn derive_verification_babble(
+    ikm: &[u8],
+    salt: &[u8],
+    info: &[u8],
+    words: usize,
+) -> Option<String> {
+    if words == 0 {
+        return None;
+    }
+    let mut okm = vec![0; words * 2 - 1];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(info, &mut okm)
+        .ok()?;
+    Some(bubblebabble(&okm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_verification_babble() {
+        // RFC 5869 test case 1, the OKM starts with 3cb25f25fa
+        let ikm = [0x0b; 22];
+        let salt = (0x00..=0x0c).collect::<Vec<u8>>();
+        let info = (0xf0..=0xf9).collect::<Vec<u8>>();
+        assert_eq!(
+            derive_verification_babble(&ikm, &salt, &info, 3).as_deref(),
+            Some("xezar-duled-havox")
+        );
+
+        let babble = derive_verification_babble(&ikm, &salt, b"other", 3).unwrap();
+        assert_ne!(babble, "xezar-duled-havox");
+        assert_eq!(derive_verification_babble(&ikm, &salt, &info, 0), None);
+        assert_eq!(derive_verification_babble(&ikm, &salt, &info, 5000), None);
+    }
+}
@@ -0,0 +1,254 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! OpenSSH `known_hosts` files.
+
+use crate::bubblebabble;
+use hmac::{Hmac, Mac};
+use sha1::{Digest, Sha1};
+use ssh_key::known_hosts::{Entry, HostPatterns};
+use std::fmt;
+
+/// The lines of a `known_hosts` file.
+///
+/// Entries can be looked up by host name, including hashed host names,
+/// and updated.  Comments and empty lines are kept, so that the file
+/// can be written back with the `Display` implementation.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::KnownHosts;
+///
+/// let known_hosts: KnownHosts = "# comment\n\
+///     example.com,192.0.2.1 ssh-ed25519 \
+///     AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX\n"
+///     .parse()
+///     .unwrap();
+///
+/// for fingerprint in known_hosts.fingerprints() {
+///     let (host, key_type, _, babble) = fingerprint.unwrap();
+///     assert_eq!(host, "example.com,192.0.2.1");
+///     assert_eq!(key_type, "ssh-ed25519");
+///     assert_eq!(babble, "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox");
+/// }
+/// assert_eq!(known_hosts.find("192.0.2.1").count(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KnownHosts {
+    lines: Vec<Line>,
+}
+
+#[derive(Debug, Clone)]
+enum Line {
+    Entry(Entry),
+    Other(String),
+}
+
+impl KnownHosts {
+    /// Create an empty file.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the host key entries.
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.lines.iter().filter_map(|line| match line {
+            Line::Entry(entry) => Some(entry),
+            Line::Other(_) => None,
+        })
+    }
+
+    /// Return the host patterns, key type, SHA-1 fingerprint, and Babble
+    /// of each entry.
+    ///
+    /// The fingerprint is the legacy format of `ssh-keygen -B` that is
+    /// also returned by `ssh_babble`.  Hashed host names are returned in
+    /// their `|1|salt|hash` form.  An error is returned for keys that
+    /// cannot be encoded to their wire format.
+    pub fn fingerprints(
+        &self,
+    ) -> impl Iterator<Item = Result<(String, String, Vec<u8>, String), ssh_key::Error>> + '_ {
+        self.entries().map(|entry| {
+            let key = entry.public_key();
+            let fingerprint = Sha1::digest(key.to_bytes()?).to_vec();
+            let babble = bubblebabble(&fingerprint);
+            Ok((
+                entry.host_patterns().to_string(),
+                key.algorithm().as_str().to_string(),
+                fingerprint,
+                babble,
+            ))
+        })
+    }
+
+    /// Return the entries for a host name.
+    ///
+    /// Host names are compared with the patterns of the entry, including
+    /// `*` and `?` wildcards and negated `!` patterns, or with the hash of
+    /// a hashed host name.  Non-standard ports use the `[host]:port`
+    /// form.
+    pub fn find<'a>(&'a self, host: &'a str) -> impl Iterator<Item = &'a Entry> + 'a {
+        self.entries().filter(move |entry| matches(entry, host))
+    }
+
+    /// Append an entry.
+    pub fn add(&mut self, entry: Entry) {
+        self.lines.push(Line::Entry(entry));
+    }
+
+    /// Remove all entries for a host name and return their number.
+    pub fn remove(&mut self, host: &str) -> usize {
+        let len = self.lines.len();
+        self.lines.retain(|line| match line {
+            Line::Entry(entry) => !matches(entry, host),
+            Line::Other(_) => true,
+        });
+        len - self.lines.len()
+    }
+}
+
+impl std::str::FromStr for KnownHosts {
+    type Err = ssh_key::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lines = s
+            .lines()
+            .map(|line| {
+                let trimmed = line.trim();
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    Ok(Line::Other(line.to_string()))
+                } else {
+                    trimmed.parse().map(Line::Entry)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(KnownHosts { lines })
+    }
+}
+
+/// Print the file with one line for each entry or comment.
+impl fmt::Display for KnownHosts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in &self.lines {
+            match line {
+                Line::Entry(entry) => writeln!(f, "{}", entry.to_string())?,
+                Line::Other(line) => writeln!(f, "{}", line)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Return true if the host name matches the entry.
+fn matches(entry: &Entry, host: &str) -> bool {
+    match entry.host_patterns() {
+        HostPatterns::HashedName { salt, hash } => {
+            let mut mac = Hmac::<Sha1>::new_from_slice(salt).expect("HMAC accepts any key");
+            mac.update(host.as_bytes());
+            mac.verify_slice(hash).is_ok()
+        }
+        HostPatterns::Patterns(patterns) => {
+            let mut found = false;
+            for pattern in patterns {
+                match pattern.strip_prefix('!') {
+                    Some(pattern) if glob(pattern, host) => return false,
+                    Some(_) => {}
+                    None => found |= glob(pattern, host),
+                }
+            }
+            found
+        }
+    }
+}
+
+/// Match a host name with a pattern of `*` and `?` wildcards.
+fn glob(pattern: &str, host: &str) -> bool {
+    fn glob(pattern: &[u8], host: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => host.is_empty(),
+            Some((b'*', rest)) => (0..=host.len()).any(|i| glob(rest, &host[i..])),
+            Some((b'?', rest)) => !host.is_empty() && glob(rest, &host[1..]),
+            Some((c, rest)) => host
+                .split_first()
+                .is_some_and(|(h, host)| h.eq_ignore_ascii_case(c) && glob(rest, host)),
+        }
+    }
+    glob(pattern.as_bytes(), host.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX";
+
+    #[test]
+    fn test_known_hosts() {
+        let text = format!(
+            "# hosts\n\
+             |1|kIqQ6GtcMIWgqnGEBdZCxHcBHCo=|iBjuKZKbsf/eovr8bmu16bg7h8U= {key}\n\
+             \n\
+             *.example.com,!bad.example.com {key}\n\
+             @revoked [192.0.2.1]:2222 {key}\n",
+            key = KEY
+        );
+        let mut known_hosts: KnownHosts = text.parse().unwrap();
+
+        assert_eq!(known_hosts.to_string(), text);
+        assert_eq!(known_hosts.entries().count(), 3);
+        assert_eq!(known_hosts.find("other.org").count(), 1);
+        assert_eq!(known_hosts.find("www.example.com").count(), 1);
+        assert_eq!(known_hosts.find("WWW.Example.com").count(), 1);
+        assert_eq!(known_hosts.find("bad.example.com").count(), 0);
+        assert_eq!(known_hosts.find("example.com").count(), 0);
+        assert_eq!(known_hosts.find("[192.0.2.1]:2222").count(), 1);
+
+        let (host, key_type, fingerprint, babble) =
+            known_hosts.fingerprints().nth(2).unwrap().unwrap();
+        assert_eq!(host, "[192.0.2.1]:2222");
+        assert_eq!(key_type, "ssh-ed25519");
+        assert_eq!(fingerprint.len(), 20);
+        assert_eq!(
+            babble,
+            "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox"
+        );
+
+        assert_eq!(known_hosts.remove("other.org"), 1);
+        known_hosts.add(format!("new.example.org {}", KEY).parse().unwrap());
+        assert_eq!(known_hosts.find("new.example.org").count(), 1);
+        assert!(known_hosts
+            .to_string()
+            .ends_with(&format!("\nnew.example.org {}\n", KEY)));
+
+        assert!("example.com ssh-ed25519 AAAA"
+            .parse::<KnownHosts>()
+            .is_err());
+    }
+}
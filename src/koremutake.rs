@@ -0,0 +1,218 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Koremutake encoding of 7-bit syllables.
+//!
+//! Koremutake encodes numbers as syllables of 7 bits each, like
+//! `koremutake` for 10610353957.  Byte strings are encoded as a stream
+//! of bits, the last syllable is padded with zero bits.  Koremutake is
+//! shorter than Babble and proquints but has no checksum.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::koremutake;
+//!
+//! assert_eq!(koremutake::encode_number(10610353957), "koremutake");
+//! assert_eq!(koremutake::encode(b"Pineapple"), "kuhilofralebydragrunaheji");
+//! assert_eq!(koremutake::decode("kuhilofralebydragrunaheji").unwrap(), b"Pineapple");
+//! ```
+
+use crate::BabbleError;
+
+/// Syllables in order of their 7-bit values.
+const SYLLABLES: [&str; 128] = [
+    "ba", "be", "bi", "bo", "bu", "by", "da", "de", "di", "do", "du", "dy", "fa", "fe", "fi", "fo",
+    "fu", "fy", "ga", "ge", "gi", "go", "gu", "gy", "ha", "he", "hi", "ho", "hu", "hy", "ja", "je",
+    "ji", "jo", "ju", "jy", "ka", "ke", "ki", "ko", "ku", "ky", "la", "le", "li", "lo", "lu", "ly",
+    "ma", "me", "mi", "mo", "mu", "my", "na", "ne", "ni", "no", "nu", "ny", "pa", "pe", "pi", "po",
+    "pu", "py", "ra", "re", "ri", "ro", "ru", "ry", "sa", "se", "si", "so", "su", "sy", "ta", "te",
+    "ti", "to", "tu", "ty", "va", "ve", "vi", "vo", "vu", "vy", "bra", "bre", "bri", "bro", "bru",
+    "bry", "dra", "dre", "dri", "dro", "dru", "dry", "fra", "fre", "fri", "fro", "fru", "fry",
+    "gra", "gre", "gri", "gro", "gru", "gry", "pra", "pre", "pri", "pro", "pru", "pry", "sta",
+    "ste", "sti", "sto", "stu", "sty", "tra", "tre",
+];
+
+/// Split the string into the 7-bit values of its syllables.
+///
+/// Syllables end with a vowel, they are case-insensitive and may be
+/// separated by `-` or whitespace.
+fn syllables(koremutake: &str) -> Result<Vec<u8>, BabbleError> {
+    let mut result = Vec::with_capacity(koremutake.len() / 2);
+    let mut syllable = String::new();
+    let mut start = 0;
+
+    for (offset, c) in koremutake.char_indices() {
+        if c == '-' || c.is_whitespace() {
+            if !syllable.is_empty() {
+                return Err(BabbleError::InvalidCharacter(c, offset));
+            }
+            continue;
+        }
+        if syllable.is_empty() {
+            start = offset;
+        }
+
+        let c = c.to_ascii_lowercase();
+        syllable.push(c);
+        if "aeiouy".contains(c) {
+            let value = SYLLABLES
+                .iter()
+                .position(|s| *s == syllable)
+                .ok_or_else(|| {
+                    let c = koremutake[start..].chars().next().unwrap_or_default();
+                    BabbleError::InvalidCharacter(c, start)
+                })?;
+            result.push(value as u8);
+            syllable.clear();
+        } else if syllable.len() > 2 {
+            return Err(BabbleError::InvalidCharacter(c, offset));
+        }
+    }
+
+    if !syllable.is_empty() {
+        return Err(BabbleError::InvalidLength(koremutake.len()));
+    }
+
+    Ok(result)
+}
+
+/// Encode a number as syllables.
+pub fn encode_number(mut number: u64) -> String {
+    let mut values = vec![];
+    loop {
+        values.push((number & 0x7f) as usize);
+        number >>= 7;
+        if number == 0 {
+            break;
+        }
+    }
+
+    values.iter().rev().map(|&i| SYLLABLES[i]).collect()
+}
+
+/// Decode syllables as a number.
+///
+/// Returns `BabbleError::InvalidLength` if the number does not fit in
+/// 64 bits.
+pub fn decode_number(koremutake: &str) -> Result<u64, BabbleError> {
+    let values = syllables(koremutake)?;
+    if values.is_empty() {
+        return Err(BabbleError::InvalidLength(0));
+    }
+
+    values.iter().try_fold(0u64, |number, &value| {
+        if number >> 57 != 0 {
+            return Err(BabbleError::InvalidLength(koremutake.len()));
+        }
+        Ok((number << 7) | u64::from(value))
+    })
+}
+
+/// Encode data as syllables of 7 bits each.
+pub fn encode(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len() * 3);
+    let mut bits = 0u16;
+    let mut count = 0;
+
+    for &b in data {
+        bits = (bits << 8) | u16::from(b);
+        count += 8;
+        while count >= 7 {
+            count -= 7;
+            result.push_str(SYLLABLES[usize::from((bits >> count) & 0x7f)]);
+        }
+    }
+    if count > 0 {
+        result.push_str(SYLLABLES[usize::from((bits << (7 - count)) & 0x7f)]);
+    }
+
+    result
+}
+
+/// Decode syllables to data.
+///
+/// Returns `BabbleError::InvalidLength` if the syllables do not encode
+/// whole bytes with zero padding.
+pub fn decode(koremutake: &str) -> Result<Vec<u8>, BabbleError> {
+    let values = syllables(koremutake)?;
+    let mut result = Vec::with_capacity(values.len() * 7 / 8);
+    let mut bits = 0u16;
+    let mut count = 0;
+
+    for value in values {
+        bits = (bits << 7) | u16::from(value);
+        count += 7;
+        if count >= 8 {
+            count -= 8;
+            result.push((bits >> count) as u8);
+        }
+    }
+
+    // Padding must be shorter than a syllable and zero
+    if count >= 7 || bits & ((1 << count) - 1) != 0 {
+        return Err(BabbleError::InvalidLength(koremutake.len()));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_koremutake() {
+        let tests = [
+            (0, "ba"),
+            (39, "ko"),
+            (128, "beba"),
+            (10610353957, "koremutake"),
+            (u64::MAX, "betretretretretretretretretre"),
+        ];
+        for (number, koremutake) in tests.iter() {
+            assert_eq!(encode_number(*number), *koremutake);
+            assert_eq!(decode_number(koremutake), Ok(*number));
+        }
+        assert_eq!(decode_number("KO-RE mu TA-KE"), Ok(10610353957));
+        assert!(decode_number("").is_err());
+        assert!(decode_number("bretretretretretretretretretre").is_err());
+
+        for data in [&b""[..], b"\0", b"Pineapple", &[0xff; 7], &[0xab; 8]].iter() {
+            assert_eq!(decode(&encode(data)).as_deref(), Ok(*data));
+        }
+
+        assert_eq!(
+            decode("korexmu"),
+            Err(BabbleError::InvalidCharacter('x', 4))
+        );
+        assert_eq!(decode("kowa"), Err(BabbleError::InvalidCharacter('w', 2)));
+        assert_eq!(decode("kotr"), Err(BabbleError::InvalidLength(4)));
+        assert_eq!(decode("ko"), Err(BabbleError::InvalidLength(2)));
+        assert_eq!(decode("kobe"), Err(BabbleError::InvalidLength(4)));
+    }
+}
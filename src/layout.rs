@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Layout of Babble strings in tables.
+//!
+//! The Babble of `n` bytes always has `n / 2 + 1` words, so the width of
+//! a column of fingerprints is known before they are encoded.  This
+//! holds for `bubblebabble`, `stablebabble_uncompressed`, and any
+//! `Options` that are not stable; compressed stable Babble is shorter
+//! for repeated words and is aligned by `Table`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::{bubblebabble, layout::{self, Table}};
+//!
+//! assert_eq!(layout::width(9), 29);
+//!
+//! let mut table = Table::new();
+//! table.row(["HOST", "FINGERPRINT", "BITS"]);
+//! table.row(["alpha".into(), bubblebabble(b"Pine"), "32".to_string()]);
+//! assert_eq!(
+//!     table.to_string(),
+//!     "HOST   FINGERPRINT        BITS\n\
+//!      alpha  xigak-nyryk-hoxex  32\n"
+//! );
+//! ```
+
+use std::fmt;
+
+/// Return the number of words of the Babble of `input_len` bytes.
+pub fn words(input_len: usize) -> usize {
+    input_len / 2 + 1
+}
+
+/// Return the number of characters of the Babble of `input_len` bytes,
+/// including the separators.
+pub fn width(input_len: usize) -> usize {
+    words(input_len).saturating_mul(6) - 1
+}
+
+/// Rows of text that are printed as left-aligned columns.
+///
+/// Each column is padded to its widest cell, measured in characters, and
+/// the columns are separated by two spaces.  The last column is not
+/// padded, so lines have no trailing spaces.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Table {
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Return an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a row of cells, rows may have different numbers of cells.
+    pub fn row<I, S>(&mut self, cells: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+    }
+
+    /// Return the width of each column in characters.
+    pub fn widths(&self) -> Vec<usize> {
+        let mut widths = Vec::new();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                let width = cell.chars().count();
+                match widths.get_mut(i) {
+                    Some(w) if *w < width => *w = width,
+                    Some(_) => {}
+                    None => widths.push(width),
+                }
+            }
+        }
+        widths
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let widths = self.widths();
+        for row in &self.rows {
+            for (i, cell) in row.iter().enumerate() {
+                if i + 1 == row.len() {
+                    f.write_str(cell)?;
+                } else {
+                    write!(f, "{:width$}  ", cell, width = widths[i])?;
+                }
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubblebabble, stablebabble, stablebabble_uncompressed, Alphabet, Options};
+
+    #[test]
+    fn test_layout() {
+        let options = Options::new()
+            .separator(':')
+            .unwrap()
+            .group(2)
+            .alphabet(Alphabet::new("aeiouy", "bcdfghjklmnpqrstv").unwrap());
+        for n in 0..40 {
+            let bytes = vec![0; n];
+            assert_eq!(bubblebabble(&bytes).split('-').count(), words(n));
+            assert_eq!(bubblebabble(&bytes).len(), width(n));
+            assert_eq!(stablebabble_uncompressed(&bytes).len(), width(n));
+            assert_eq!(options.encode(&bytes).chars().count(), width(n));
+        }
+
+        let mut table = Table::new();
+        table.row(["a", "ö", "c"]);
+        table.row(vec![stablebabble(&[0; 16]), "x".to_string()]);
+        table.row(Vec::<String>::new());
+        assert_eq!(table.widths(), [15, 1, 1]);
+        assert_eq!(
+            table.to_string(),
+            "a                ö  c\n\
+             xebab-7wa-baxax  x\n\
+             \n"
+        );
+    }
+}
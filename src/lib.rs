@@ -50,165 +50,210 @@
 //! assert_eq!(babbleaddr, "xebab-7wa-caxax");
 //! ```
 //!
+//! Addresses, UUIDs, and other values can also be converted with the
+//! `ToBabble` trait:
+//!
+//! ```rust
+//! use bubblebabble::ToBabble;
+//! use std::net::{Ipv6Addr, SocketAddr};
+//!
+//! assert_eq!(Ipv6Addr::LOCALHOST.to_stablebabble(), "xebab-7wa-caxax");
+//!
+//! // Sockets include the port in network byte order
+//! let addr: SocketAddr = "[::1]:22".parse().unwrap();
+//! assert_eq!(addr.to_stablebabble(), "xebab-7wa-cabac-kaxax");
+//! ```
+//!
 //! # See Also
 //!
 //! [The Bubble Babble Binary Data Encoding, Antti Huima, 2011](http://web.mit.edu/kenta/www/one/bubblebabble/spec/jrtrjwzi/draft-huima-01.txt)
 
-/// Convert bytes to Bubble Babble `String`.
-///
-/// This is the standard and human-readable format.  The Bubble Babble
-/// includes a checksum that is carried through each generated word.
-pub fn bubblebabble(bytes: &[u8]) -> String {
-    bubblebabble_impl(bytes, true)
-}
-
-/// Convert bytes to stable Babble `String`.
-///
-/// This modified format lacks the checksum but keeps every word
-/// stable as they don't include the state.  It also compresses repeated
-/// words by printing them with a prepended counter.
-pub fn stablebabble(bytes: &[u8]) -> String {
-    bubblebabble_impl(bytes, false)
-}
-
-fn bubblebabble_impl(bytes: &[u8], use_seed: bool) -> String {
-    let vowels = ['a', 'e', 'i', 'o', 'u', 'y'];
-    let consonants = [
-        'b', 'c', 'd', 'f', 'g', 'h', 'k', 'l', 'm', 'n', 'p', 'r', 's', 't', 'v', 'z', 'x',
-    ];
-    let rounds = (bytes.len() / 2) + 1;
-    let mut bubble = String::with_capacity(rounds * 6);
-    let mut seed = 1;
-
-    bubble.push('x');
-
-    // taken from OpenSSH ssh/sshkey.c
-    for i in 0..rounds {
-        let mut idx = [0usize; 5];
-
-        if (i + 1 < rounds) || bytes.len() % 2 != 0 {
-            idx[0] = ((((bytes[2 * i]) as usize >> 6) & 3) + seed) % 6;
-            idx[1] = ((bytes[2 * i]) >> 2) as usize & 15;
-            idx[2] = (((bytes[2 * i]) & 3) as usize + (seed / 6)) % 6;
-
-            bubble.push(vowels[idx[0]]);
-            bubble.push(consonants[idx[1]]);
-            bubble.push(vowels[idx[2]]);
-
-            if (i + 1) < rounds {
-                idx[3] = ((bytes[(2 * i) + 1]) as usize >> 4) & 15;
-                idx[4] = ((bytes[(2 * i) + 1]) as usize) & 15;
-
-                bubble.push(consonants[idx[3]]);
-                bubble.push('-');
-                bubble.push(consonants[idx[4]]);
-
-                seed = if use_seed {
-                    // The seed changes each word and serves as kind of a checksum
-                    ((seed * 5)
-                        + (((bytes[2 * i]) as usize * 7) as usize
-                            + ((bytes[(2 * i) as usize + 1]) as usize)))
-                        % 36
-                } else {
-                    0
-                };
-            }
-        } else {
-            idx[0] = seed % 6;
-            idx[1] = 16;
-            idx[2] = seed / 6;
-
-            bubble.push(vowels[idx[0]]);
-            bubble.push(consonants[idx[1]]);
-            bubble.push(vowels[idx[2]]);
-        }
-    }
-
-    bubble.push('x');
-
-    if use_seed {
-        return bubble;
-    }
-
-    // Find and replace repetitioins
-    let mut result = String::new();
-    let mut last = "";
-    let mut count = 1;
-    for (i, word) in bubble.split('-').enumerate() {
-        if last == word {
-            count += 1;
-        } else if i > 0 {
-            if i > 1 {
-                result.push('-');
-            }
-            if count > 1 {
-                result.push_str(&count.to_string());
-            }
-            result.push_str(last);
-            count = 1;
-        }
-        last = word;
-    }
-    result.push('-');
-    result.push_str(last);
-
-    // Use "wa" to represent a 0
-    result.replace("babab", "wa")
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::net::Ipv6Addr;
-
-    #[test]
-    fn test_bubblebabble() {
-        let tests = [
-            (
-                Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0),
-                "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-bexux",
-            ),
-            (
-                Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1),
-                "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-cixux",
-            ),
-            (
-                Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 2),
-                "xebab-bybab-bebub-bybib-bebib-bybub-bebab-bybab-doxux",
-            ),
-            (
-                "2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0".parse().unwrap(),
-                "xepib-panus-bubub-dubyb-hilyz-nefas-myzug-mihos-bexux",
-            ),
-            (
-                "fe80::4685:ff:fe76:1722".parse().unwrap(),
-                "xuzim-bobab-bobib-bobab-bucum-hibiz-zuzil-kyhed-duxix",
-            ),
-        ];
-
-        for addr in tests.iter() {
-            assert_eq!(bubblebabble(&(addr.0).octets()), addr.1);
-        }
-    }
+mod age;
+mod align;
+#[cfg(feature = "async")]
+mod async_io;
+mod base32;
+mod base64;
+mod bech32;
+mod blocklist;
+mod cidr;
+mod color;
+mod compare;
+pub mod convert;
+#[cfg(feature = "macros")]
+mod debug;
+mod diff;
+#[cfg(feature = "digest")]
+mod digest;
+mod dnssec;
+mod dual;
+mod duid;
+mod dump;
+pub mod emoji;
+mod fingerprint;
+mod format;
+#[cfg(any(feature = "sha2", feature = "blake3"))]
+mod hash;
+mod hex;
+mod hostname;
+pub mod ident;
+mod iid;
+mod int;
+mod ip;
+pub mod ipa;
+#[cfg(feature = "jose")]
+mod jose;
+#[cfg(feature = "hkdf")]
+mod kdf;
+#[cfg(feature = "ssh")]
+mod known_hosts;
+pub mod koremutake;
+pub mod layout;
+mod mac;
+mod minisign;
+#[cfg(feature = "bip39")]
+mod mnemonic;
+pub mod nato;
+#[cfg(feature = "onion")]
+mod onion;
+mod openpgp;
+mod otr;
+pub mod pairing;
+mod pattern;
+pub mod pgpwords;
+mod phonetic;
+pub mod proquint;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "rand")]
+mod random;
+mod randomart;
+mod reader;
+#[cfg(feature = "sha2")]
+mod safety;
+#[cfg(feature = "sha2")]
+pub mod sas;
+#[cfg(feature = "secrecy")]
+mod secret;
+mod security;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod short;
+mod siphash;
+pub mod skey;
+pub mod sortable;
+#[cfg(feature = "ssh")]
+mod ssh;
+mod sshfp;
+#[cfg(feature = "test-strategies")]
+pub mod strategy;
+#[cfg(feature = "totp")]
+mod totp;
+#[cfg(feature = "tracing")]
+mod trace;
+mod traits;
+#[cfg(feature = "tts")]
+pub mod tts;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod urbit;
+mod uri;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod wireguard;
+mod writer;
+#[cfg(feature = "x509")]
+mod x509;
 
-    #[test]
-    fn test_stablebabble() {
-        let tests = [
-            (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 0), "xebab-7wa-baxax"),
-            (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1), "xebab-7wa-caxax"),
-            (Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 2), "xebab-7wa-daxax"),
-            (
-                "2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0".parse().unwrap(),
-                "xepib-pones-wa-dabab-helaz-nofas-mezag-mihos-baxax",
-            ),
-            (
-                "fe80::4685:ff:fe76:1722".parse().unwrap(),
-                "xuzim-3wa-becim-habaz-zozil-kahod-daxax",
-            ),
-        ];
+// The derive macros refer to items of the crate by its name
+#[cfg(feature = "macros")]
+extern crate self as bubblebabble;
 
-        for addr in tests.iter() {
-            assert_eq!(stablebabble(&(addr.0).octets()), addr.1);
-        }
-    }
-}
+#[cfg(feature = "async")]
+pub use crate::async_io::{AsyncBabbleWriter, AsyncDebabbleReader};
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use crate::debug::DebugBabble as __DebugBabble;
+#[cfg(feature = "digest")]
+pub use crate::digest::{babble_digest, babble_digest_reader};
+#[cfg(feature = "blake3")]
+pub use crate::hash::blake3_babble;
+#[cfg(feature = "sha2")]
+pub use crate::hash::{sha256_babble, sha512_babble};
+#[cfg(feature = "jose")]
+pub use crate::jose::{JwkError, JwkThumbprint};
+#[cfg(feature = "hkdf")]
+pub use crate::kdf::derive_verification_babble;
+#[cfg(feature = "ssh")]
+pub use crate::known_hosts::KnownHosts;
+#[cfg(feature = "bip39")]
+pub use crate::mnemonic::{bip39_entropy, bip39_mnemonic, Bip39Error};
+#[cfg(feature = "onion")]
+pub use crate::onion::{OnionAddress, OnionAddressParseError};
+#[cfg(feature = "rand")]
+pub use crate::random::random_babble;
+#[cfg(feature = "sha2")]
+pub use crate::safety::SafetyNumber;
+#[cfg(feature = "secrecy")]
+pub use crate::secret::SecretBabble;
+#[cfg(feature = "serde")]
+pub use crate::serde::BabbleBytes;
+#[cfg(feature = "ssh")]
+pub use crate::ssh::{ssh_babble, ssh_babble_sha256, ssh_parse_line};
+#[cfg(feature = "totp")]
+pub use crate::totp::Totp;
+#[cfg(feature = "tracing")]
+pub use crate::trace::{BabbleFields, BabbleVisitor, Babbled};
+#[cfg(feature = "x509")]
+pub use crate::x509::{ikev2_key_hash, CertificateError, CertificateFingerprint, SpkiPin};
+pub use crate::{
+    age::{AgeRecipient, AgeRecipientParseError},
+    align::{align, Alignment, WordEdit},
+    blocklist::Blocklist,
+    cidr::{IpPrefix, IpPrefixParseError},
+    color::{color_hash, Rgb},
+    compare::{constant_time_eq, constant_time_eq_bytes},
+    diff::{diff, BabbleDiff, WordDiff},
+    dnssec::{Dnskey, DnssecParseError, Ds},
+    dual::HexAndBabble,
+    duid::{Duid, DuidParseError},
+    dump::Dump,
+    fingerprint::{BabbleFingerprint, BabbleFingerprintParseError},
+    format::{Alphabet, Options},
+    hostname::{hostname, MAX_LABEL_LEN},
+    iid::{iid_babble, interface_id, slaac_addr},
+    int::{
+        babble_u128, babble_u16, babble_u32, babble_u64, debabble_u128, debabble_u16, debabble_u32,
+        debabble_u64, Endianness,
+    },
+    ip::{ip_from_babble, ipv4_from_babble, ipv6_from_babble},
+    mac::{MacAddr, MacAddrParseError},
+    minisign::{MinisignKey, MinisignKeyParseError},
+    openpgp::{PgpFingerprint, PgpFingerprintParseError},
+    otr::{OtrFingerprint, OtrFingerprintParseError},
+    pattern::babble_regex,
+    phonetic::confusability,
+    randomart::randomart,
+    reader::BabbleReader,
+    security::{bits_of, words_needed, BabbleInfo, LETTER_ERROR_RATE},
+    short::{short, short_matches, ELLIPSIS},
+    siphash::babble_of_hash,
+    sshfp::{Sshfp, SshfpParseError},
+    traits::{FromBabble, ToBabble},
+    uri::{BabbleUri, BabbleUriParseError},
+    wireguard::{WireGuardKey, WireGuardKeyParseError},
+    writer::BabbleWriter,
+};
+#[cfg(feature = "diagnostics")]
+pub use bubblebabble_core::BabbleDiagnostic;
+pub use bubblebabble_core::{
+    babble_eq, babble_prefix, bubblebabble, bubblebabble_from_iter, bubblebabble_seeded,
+    bubblebabble_try_from_iter, debabble, debabble_seeded, debabble_stable, debabble_to_slice,
+    destabilize, restabilize, stablebabble, stablebabble_uncompressed, BabbleError, BabblePrefix,
+    Decoder, Encoder,
+};
+#[cfg(feature = "bytes")]
+pub use bubblebabble_core::{bubblebabble_buf, stablebabble_buf};
+use bubblebabble_core::{CONSONANTS, VOWELS};
+#[cfg(feature = "macros")]
+pub use bubblebabble_macros::{babble_bytes, BabbleDebug};
@@ -0,0 +1,202 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! MAC addresses in the EUI-48 and EUI-64 formats.
+
+use crate::{debabble, BabbleError, FromBabble, ToBabble};
+use std::{borrow::Cow, convert::TryInto, error, fmt, str::FromStr};
+
+/// A hardware address in the EUI-48 or EUI-64 format.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{MacAddr, ToBabble};
+///
+/// let mac: MacAddr = "00:1b:21:3a:9f:c4".parse().unwrap();
+/// assert_eq!(mac, "001b.213a.9fc4".parse().unwrap());
+/// assert_eq!(mac.to_babble(), "xebac-rimaf-pelys-gixux");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MacAddr {
+    /// A 48-bit MAC address.
+    Eui48([u8; 6]),
+    /// A 64-bit extended unique identifier.
+    Eui64([u8; 8]),
+}
+
+impl MacAddr {
+    /// Return the bytes of the address.
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            MacAddr::Eui48(bytes) => bytes,
+            MacAddr::Eui64(bytes) => bytes,
+        }
+    }
+
+    /// Return the modified EUI-64 interface identifier of the address.
+    ///
+    /// This is the lower half of IPv6 addresses that are configured by
+    /// SLAAC (RFC 4291, appendix A): `ff:fe` is inserted in the middle
+    /// of a 48-bit address and the universal/local bit is inverted.
+    pub fn interface_id(&self) -> [u8; 8] {
+        let mut iid = match self {
+            MacAddr::Eui48(b) => [b[0], b[1], b[2], 0xff, 0xfe, b[3], b[4], b[5]],
+            MacAddr::Eui64(bytes) => *bytes,
+        };
+        iid[0] ^= 0x02;
+        iid
+    }
+
+    /// Create an address from 6 or 8 bytes.
+    pub fn from_slice(bytes: &[u8]) -> Option<Self> {
+        match bytes.len() {
+            6 => bytes.try_into().ok().map(MacAddr::Eui48),
+            8 => bytes.try_into().ok().map(MacAddr::Eui64),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MacAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, b) in self.as_bytes().iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{:02x}", b)?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing an invalid MAC address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacAddrParseError;
+
+impl fmt::Display for MacAddrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid MAC address syntax")
+    }
+}
+
+impl error::Error for MacAddrParseError {}
+
+impl FromStr for MacAddr {
+    type Err = MacAddrParseError;
+
+    /// Parse the `00:1b:21:3a:9f:c4`, `00-1B-21-3A-9F-C4`,
+    /// `001b.213a.9fc4`, or `001b213a9fc4` forms.
+    ///
+    /// Addresses have exactly 12 hex digits, or 16 digits for EUI-64,
+    /// with or without separators.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (groups, digits) = if s.contains(':') {
+            (s.split(':').collect::<Vec<_>>(), 2)
+        } else if s.contains('-') {
+            (s.split('-').collect(), 2)
+        } else if s.contains('.') {
+            (s.split('.').collect(), 4)
+        } else {
+            (vec![s], s.len())
+        };
+        if groups.len() * digits != 12 && groups.len() * digits != 16 {
+            return Err(MacAddrParseError);
+        }
+
+        let mut bytes = Vec::with_capacity(8);
+        for group in groups {
+            if group.len() != digits || !group.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(MacAddrParseError);
+            }
+            // Split the groups into bytes
+            for i in (0..group.len()).step_by(2) {
+                bytes
+                    .push(u8::from_str_radix(&group[i..i + 2], 16).map_err(|_| MacAddrParseError)?);
+            }
+        }
+
+        MacAddr::from_slice(&bytes).ok_or(MacAddrParseError)
+    }
+}
+
+impl ToBabble for MacAddr {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl FromBabble for MacAddr {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        MacAddr::from_slice(&debabble(babble)?).ok_or(BabbleError::InvalidLength(babble.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mac_addr() {
+        let eui48 = MacAddr::Eui48([0x00, 0x1b, 0x21, 0x3a, 0x9f, 0xc4]);
+        let eui64 = MacAddr::Eui64([0x02, 0x1b, 0x21, 0xff, 0xfe, 0x3a, 0x9f, 0xc4]);
+
+        for s in &[
+            "00:1b:21:3a:9f:c4",
+            "00-1B-21-3A-9F-C4",
+            "001b.213a.9fc4",
+            "001b213a9fc4",
+        ] {
+            assert_eq!(s.parse(), Ok(eui48));
+        }
+        for s in &["02:1b:21:ff:fe:3a:9f:c4", "021b.21ff.fe3a.9fc4"] {
+            assert_eq!(s.parse(), Ok(eui64));
+        }
+        for s in &[
+            "",
+            "00:1b:21:3a:9f",
+            "0:1b:21:3a:9f:c4",
+            "001b:213a:9fc4",
+            "00:1b:21:3a:9f:g4",
+            "001b213a9fc",
+            "001b213a9fc45",
+            "001b213a9fc4567",
+            "001b.213a.9fc",
+        ] {
+            assert_eq!(s.parse::<MacAddr>(), Err(MacAddrParseError));
+        }
+
+        assert_eq!(eui48.to_string(), "00:1b:21:3a:9f:c4");
+        assert_eq!(
+            eui48.interface_id(),
+            [0x02, 0x1b, 0x21, 0xff, 0xfe, 0x3a, 0x9f, 0xc4]
+        );
+        assert_eq!(eui64.interface_id()[0], 0x00);
+        assert_eq!(MacAddr::from_babble(&eui48.to_babble()), Ok(eui48));
+        assert_eq!(MacAddr::from_babble(&eui64.to_babble()), Ok(eui64));
+    }
+}
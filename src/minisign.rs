@@ -0,0 +1,144 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! minisign and signify public keys.
+
+use crate::{base64, ToBabble};
+use std::{borrow::Cow, convert::TryInto, error, fmt, str::FromStr};
+
+/// Signature algorithm of Ed25519 keys.
+const ALGORITHM: &[u8; 2] = b"Ed";
+
+/// A minisign or signify Ed25519 public key.
+///
+/// Both tools use the same base64 encoding of the algorithm, the key
+/// ID, and the public key.  Keys are parsed from the base64 line or a
+/// whole public key file with its `untrusted comment:` line.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{MinisignKey, ToBabble};
+///
+/// let key: MinisignKey = "untrusted comment: minisign public key BEB3E60C274561A5
+/// RWSlYUUnDOazvmuTuTO4KHplocx/B4+xLci/qzTMjQJsNq6iKzt3Y5W3"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(format!("{:016X}", key.key_id()), "BEB3E60C274561A5");
+/// assert!(key.to_babble().starts_with("xipon-fovef-fovod-mivyk"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MinisignKey {
+    /// Key ID, used to match signatures with the key.
+    pub key_id: [u8; 8],
+    /// Ed25519 public key.
+    pub key: [u8; 32],
+}
+
+impl MinisignKey {
+    /// Return the key ID as the number that is printed by `minisign`.
+    pub fn key_id(&self) -> u64 {
+        u64::from_le_bytes(self.key_id)
+    }
+}
+
+impl fmt::Display for MinisignKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = ALGORITHM.to_vec();
+        bytes.extend_from_slice(&self.key_id);
+        bytes.extend_from_slice(&self.key);
+        f.write_str(&base64::encode(&bytes))
+    }
+}
+
+/// Error returned when parsing an invalid minisign or signify key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinisignKeyParseError;
+
+impl fmt::Display for MinisignKeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid minisign key syntax")
+    }
+}
+
+impl error::Error for MinisignKeyParseError {}
+
+impl FromStr for MinisignKey {
+    type Err = MinisignKeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("untrusted comment:"));
+        let bytes = match (lines.next(), lines.next()) {
+            (Some(line), None) => base64::decode(line).ok_or(MinisignKeyParseError)?,
+            _ => return Err(MinisignKeyParseError),
+        };
+        if bytes.len() != 42 || &bytes[..2] != ALGORITHM {
+            return Err(MinisignKeyParseError);
+        }
+
+        Ok(MinisignKey {
+            key_id: bytes[2..10].try_into().unwrap(),
+            key: bytes[10..].try_into().unwrap(),
+        })
+    }
+}
+
+impl ToBabble for MinisignKey {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_minisign_key() {
+        let base64 = "RWSlYUUnDOazvmuTuTO4KHplocx/B4+xLci/qzTMjQJsNq6iKzt3Y5W3";
+        let key: MinisignKey = base64.parse().unwrap();
+
+        assert_eq!(key.to_string(), base64);
+        assert_eq!(key.key_id(), 0xBEB3_E60C_2745_61A5);
+        assert_eq!(key.to_babble(), crate::bubblebabble(&key.key));
+
+        let file = format!("untrusted comment: signify public key\n{}\n", base64);
+        assert_eq!(file.parse(), Ok(key));
+
+        for s in &[
+            "",
+            "untrusted comment: x",
+            "UGluZWFwcGxl",
+            "RWSlYUUnDOazvmuTuTO4",
+        ] {
+            assert_eq!(s.parse::<MinisignKey>(), Err(MinisignKeyParseError));
+        }
+    }
+}
@@ -0,0 +1,114 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! BIP39 mnemonic sentences with the `bip39` crate.
+
+use std::{error, fmt};
+
+/// Error returned for invalid BIP39 entropy or mnemonics.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bip39Error(::bip39::Error);
+
+impl fmt::Display for Bip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid BIP39 mnemonic: {}", self.0)
+    }
+}
+
+impl error::Error for Bip39Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Encode 128 to 256 bits of entropy as an English BIP39 mnemonic.
+///
+/// The entropy must be 16, 20, 24, 28, or 32 bytes long and the
+/// mnemonic has 12 to 24 words including the checksum.
+///
+/// # Examples
+///
+/// ```rust
+/// let mnemonic = bubblebabble::bip39_mnemonic(&[0x7f; 16]).unwrap();
+/// assert_eq!(
+///     mnemonic,
+///     "legal winner thank year wave sausage worth useful legal winner thank yellow"
+/// );
+/// assert_eq!(bubblebabble::bip39_entropy(&mnemonic).unwrap(), [0x7f; 16]);
+/// ```
+pub fn bip39_mnemonic(entropy: &[u8]) -> Result<String, Bip39Error> {
+    ::bip39::Mnemonic::from_entropy(entropy)
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(Bip39Error)
+}
+
+/// Decode an English BIP39 mnemonic and verify its checksum.
+pub fn bip39_entropy(mnemonic: &str) -> Result<Vec<u8>, Bip39Error> {
+    ::bip39::Mnemonic::parse_normalized(&mnemonic.to_lowercase())
+        .map(|mnemonic| mnemonic.to_entropy())
+        .map_err(Bip39Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip39() {
+        let tests: [(&[u8], &str); 3] = [
+            (
+                &[0; 16],
+                "abandon abandon abandon abandon abandon abandon abandon abandon \
+                 abandon abandon abandon about",
+            ),
+            (
+                &[0xff; 16],
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+            ),
+            (
+                &[0x80; 32],
+                "letter advice cage absurd amount doctor acoustic avoid letter advice \
+                 cage absurd amount doctor acoustic avoid letter advice cage absurd \
+                 amount doctor acoustic bless",
+            ),
+        ];
+
+        for (entropy, mnemonic) in tests.iter() {
+            assert_eq!(bip39_mnemonic(entropy).as_deref(), Ok(*mnemonic));
+            assert_eq!(bip39_entropy(mnemonic).as_deref(), Ok(*entropy));
+        }
+
+        assert_eq!(
+            bip39_entropy("ZOO zoo zoo zoo zoo zoo zoo zoo zoo zoo  zoo wrong\n"),
+            Ok(vec![0xff; 16])
+        );
+        assert!(bip39_mnemonic(&[0; 15]).is_err());
+        assert!(bip39_mnemonic(&[0; 36]).is_err());
+        assert!(bip39_entropy("zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo").is_err());
+        assert!(bip39_entropy("zoo zoo zoo").is_err());
+    }
+}
@@ -0,0 +1,179 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! NATO phonetic alphabet spelling.
+//!
+//! Babble words are pronounceable but can still be misheard over radio
+//! or a bad phone line.  The NATO phonetic alphabet spells each letter
+//! and digit of a Babble or hex string as a distinct word.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::nato;
+//!
+//! assert_eq!(nato::spell("xebab"), "x-ray echo bravo alfa bravo");
+//! assert_eq!(nato::spell("7wa"), "seven whiskey alfa");
+//! assert_eq!(nato::parse("X-RAY echo bravo alpha bravo").unwrap(), "xebab");
+//! ```
+
+use std::{error, fmt};
+
+/// Words in order of the letters `a` to `z`.
+const LETTERS: [&str; 26] = [
+    "alfa", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliett",
+    "kilo", "lima", "mike", "november", "oscar", "papa", "quebec", "romeo", "sierra", "tango",
+    "uniform", "victor", "whiskey", "x-ray", "yankee", "zulu",
+];
+
+/// Words in order of the digits `0` to `9`.
+const DIGITS: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+
+/// Word for the `-` separator.
+const DASH: &str = "dash";
+
+/// Common alternative spellings of the words.
+const ALIASES: [(&str, char); 5] = [
+    ("alpha", 'a'),
+    ("juliet", 'j'),
+    ("xray", 'x'),
+    ("niner", '9'),
+    ("hyphen", '-'),
+];
+
+/// Error returned when parsing an unknown word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NatoParseError {
+    /// The unknown word.
+    pub word: String,
+    /// The position of the word.
+    pub position: usize,
+}
+
+impl fmt::Display for NatoParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown word '{}' at position {}",
+            self.word, self.position
+        )
+    }
+}
+
+impl error::Error for NatoParseError {}
+
+/// Spell a string as words separated by spaces.
+///
+/// Letters are case-insensitive, `-` is spelled as `dash`, whitespace
+/// is skipped, and other characters are kept as they are.
+pub fn spell(s: &str) -> String {
+    let mut words = vec![];
+    let mut buf = [0; 4];
+
+    for c in s.chars() {
+        let word = match c.to_ascii_lowercase() {
+            c @ 'a'..='z' => LETTERS[usize::from(c as u8 - b'a')],
+            c @ '0'..='9' => DIGITS[usize::from(c as u8 - b'0')],
+            '-' => DASH,
+            c if c.is_whitespace() => continue,
+            c => c.encode_utf8(&mut buf),
+        };
+        words.push(word.to_string());
+    }
+
+    words.join(" ")
+}
+
+/// Parse words separated by whitespace back to a string.
+///
+/// The words are case-insensitive and single characters other than
+/// letters and digits are kept as they are.
+pub fn parse(s: &str) -> Result<String, NatoParseError> {
+    s.split_whitespace()
+        .enumerate()
+        .map(|(position, word)| {
+            let lower = word.to_lowercase();
+            let mut chars = word.chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if !c.is_alphanumeric() {
+                    return Ok(c);
+                }
+            }
+
+            if let Some(i) = LETTERS.iter().position(|w| *w == lower) {
+                Ok((b'a' + i as u8) as char)
+            } else if let Some(i) = DIGITS.iter().position(|w| *w == lower) {
+                Ok((b'0' + i as u8) as char)
+            } else if lower == DASH {
+                Ok('-')
+            } else {
+                ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == lower)
+                    .map(|(_, c)| *c)
+                    .ok_or_else(|| NatoParseError {
+                        word: word.to_string(),
+                        position,
+                    })
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nato() {
+        let babble = "xigak-nyryk";
+        let words = "x-ray india golf alfa kilo dash november yankee romeo yankee kilo";
+
+        assert_eq!(spell(babble), words);
+        assert_eq!(spell("XIGAK NYRYK"), words.replace(" dash", ""));
+        assert_eq!(parse(words).as_deref(), Ok(babble));
+        assert_eq!(spell("0x2a:ff"), "zero x-ray two alfa : foxtrot foxtrot");
+        assert_eq!(
+            parse("zero xray two alpha : Foxtrot foxtrot").as_deref(),
+            Ok("0x2a:ff")
+        );
+
+        let alphabet = ('a'..='z').chain('0'..='9').collect::<String>();
+        assert_eq!(parse(&spell(&alphabet)), Ok(alphabet));
+        assert_eq!(parse(""), Ok(String::new()));
+
+        assert_eq!(
+            parse("x-ray india golf alfa kilogram"),
+            Err(NatoParseError {
+                word: "kilogram".to_string(),
+                position: 4
+            })
+        );
+    }
+}
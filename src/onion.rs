@@ -0,0 +1,168 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Tor onion service addresses.
+
+use crate::{base32, debabble, BabbleError, FromBabble, ToBabble};
+use sha3::{Digest, Sha3_256};
+use std::{borrow::Cow, convert::TryInto, error, fmt, str::FromStr};
+
+/// Version byte of v3 addresses.
+const VERSION: u8 = 3;
+
+/// A Tor v3 onion service address with its ed25519 public key.
+///
+/// The address is the base32 encoding of the public key, a checksum,
+/// and the version, followed by `.onion`.  The Babble of the public key
+/// can be used to verify the address out of band.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{OnionAddress, ToBabble};
+///
+/// let onion: OnionAddress = "duckduckgogg42xjoc72x3sjasowoarfbgcmvfimaftt6twagswzczad.onion"
+///     .parse()
+///     .unwrap();
+/// let babble = onion.to_babble();
+/// assert!(onion.matches_babble(&babble));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OnionAddress(pub [u8; 32]);
+
+impl OnionAddress {
+    /// Return the ed25519 public key of the onion service.
+    pub fn public_key(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Check if the Babble string encodes the public key of the address.
+    pub fn matches_babble(&self, babble: &str) -> bool {
+        debabble(babble.trim()).is_ok_and(|key| key == self.0)
+    }
+
+    fn checksum(&self) -> [u8; 2] {
+        let digest = Sha3_256::new()
+            .chain_update(b".onion checksum")
+            .chain_update(self.0)
+            .chain_update([VERSION])
+            .finalize();
+        [digest[0], digest[1]]
+    }
+}
+
+/// Print the address including the `.onion` suffix.
+impl fmt::Display for OnionAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = self.0.to_vec();
+        bytes.extend_from_slice(&self.checksum());
+        bytes.push(VERSION);
+
+        write!(f, "{}.onion", base32::encode(&bytes).to_ascii_lowercase())
+    }
+}
+
+/// Error returned when parsing an invalid onion address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnionAddressParseError;
+
+impl fmt::Display for OnionAddressParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid onion v3 address")
+    }
+}
+
+impl error::Error for OnionAddressParseError {}
+
+impl FromStr for OnionAddress {
+    type Err = OnionAddressParseError;
+
+    /// Parse a v3 address, with or without the `.onion` suffix and
+    /// ignoring any subdomains.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        let s = s.strip_suffix(".onion").unwrap_or(&s);
+        let label = s.rsplit('.').next().unwrap_or_default();
+        if label.len() != 56 {
+            return Err(OnionAddressParseError);
+        }
+
+        let bytes = base32::decode(label)
+            .filter(|bytes| bytes.len() == 35)
+            .ok_or(OnionAddressParseError)?;
+
+        let onion = OnionAddress(bytes[..32].try_into().unwrap());
+        if bytes[34] != VERSION || bytes[32..34] != onion.checksum() {
+            return Err(OnionAddressParseError);
+        }
+
+        Ok(onion)
+    }
+}
+
+impl ToBabble for OnionAddress {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+}
+
+impl FromBabble for OnionAddress {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 32]>::from_babble(babble).map(OnionAddress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_onion_address() {
+        let address = "fcexczywqw4nuvtcgnrmqlq6vvubq3crkcrv4ozvwxxxjtl4526d4ayd.onion";
+        let onion: OnionAddress = address.parse().unwrap();
+
+        assert_eq!(onion.to_string(), address);
+        assert_eq!(onion.public_key(), &*sha2::Sha256::digest(b"onion"));
+        assert_eq!(
+            "www.FCEXCZYWQW4NUVTCGNRMQLQ6VVUBQ3CRKCRV4OZVWXXXJTL4526D4AYD".parse(),
+            Ok(onion)
+        );
+        assert_eq!(OnionAddress::from_babble(&onion.to_babble()), Ok(onion));
+        assert!(onion.matches_babble(&onion.to_babble()));
+        assert!(!onion.matches_babble("xigak-nyryk-humil-bosek-sonax"));
+
+        for s in &[
+            "",
+            "duckduckgo.onion",
+            "fcexczywqw4nuvtcgnrmqlq6vvubq3crkcrv4ozvwxxxjtl4526d4ayc.onion",
+            "fcexczywqw4nuvtcgnrmqlq6vvubq3crkcrv4ozvwxxxjtl4526d4ay1.onion",
+            "fcexczywqw4nuvtcgnrmqlq6vvubq3crkcrv4ozvwxxxjtl4526d4ay=.onion",
+        ] {
+            assert_eq!(s.parse::<OnionAddress>(), Err(OnionAddressParseError));
+        }
+    }
+}
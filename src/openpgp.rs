@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! OpenPGP key fingerprints.
+
+use crate::{hex, pgpwords, BabbleError, FromBabble, ToBabble};
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+/// A v4 (20 bytes) or v5/v6 (32 bytes) OpenPGP fingerprint.
+///
+/// The `Display` implementation prints the spaced uppercase hex form of
+/// GnuPG, the alternate form `{:#}` adds the Babble in a second line to
+/// compare either representation.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::PgpFingerprint;
+///
+/// let fpr: PgpFingerprint = "7D0E 0B3A 5F28 C3D4 A1B2  9C8E 6F5A 4B3D 2C1E 0F9A"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(
+///     format!("{:#}", fpr),
+///     "7D0E 0B3A 5F28 C3D4 A1B2  9C8E 6F5A 4B3D 2C1E 0F9A\n\
+///      xizeb-vadif-pulid-mobut-gemur-dalim-verah-pudaf-terec-vefyn-paxax"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgpFingerprint(Vec<u8>);
+
+impl PgpFingerprint {
+    /// Create a fingerprint from 20 or 32 bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        match bytes.len() {
+            20 | 32 => Some(PgpFingerprint(bytes.to_vec())),
+            _ => None,
+        }
+    }
+
+    /// Return the bytes of the fingerprint.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Return the fingerprint in the PGP word list encoding.
+    pub fn words(&self) -> String {
+        pgpwords::encode(&self.0)
+    }
+}
+
+impl fmt::Display for PgpFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = hex::encode(&self.0).to_ascii_uppercase();
+        let groups = hex.len() / 4;
+
+        for i in 0..groups {
+            if i == groups / 2 {
+                f.write_str("  ")?;
+            } else if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(&hex[i * 4..(i + 1) * 4])?;
+        }
+        if f.alternate() {
+            write!(f, "\n{}", self.to_babble())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing an invalid OpenPGP fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgpFingerprintParseError;
+
+impl fmt::Display for PgpFingerprintParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid OpenPGP fingerprint syntax")
+    }
+}
+
+impl error::Error for PgpFingerprintParseError {}
+
+impl FromStr for PgpFingerprint {
+    type Err = PgpFingerprintParseError;
+
+    /// Parse 40 or 64 hex digits, optionally spaced or prefixed by `0x`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        let hex = s.split_whitespace().collect::<String>();
+
+        hex::decode(&hex)
+            .and_then(|bytes| Self::from_bytes(&bytes))
+            .ok_or(PgpFingerprintParseError)
+    }
+}
+
+impl ToBabble for PgpFingerprint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+}
+
+impl FromBabble for PgpFingerprint {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        Vec::from_babble(babble)
+            .ok()
+            .and_then(|bytes| Self::from_bytes(&bytes))
+            .ok_or(BabbleError::InvalidLength(babble.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pgp_fingerprint() {
+        let v4 = "7D0E 0B3A 5F28 C3D4 A1B2  9C8E 6F5A 4B3D 2C1E 0F9A";
+        let fpr: PgpFingerprint = v4.parse().unwrap();
+
+        assert_eq!(fpr.to_string(), v4);
+        assert_eq!(
+            "0x7d0e0b3a5f28c3d4a1b29c8e6f5a4b3d2c1e0f9a".parse(),
+            Ok(fpr.clone())
+        );
+        assert_eq!(pgpwords::decode(&fpr.words()).unwrap(), fpr.as_bytes());
+        assert_eq!(PgpFingerprint::from_babble(&fpr.to_babble()), Ok(fpr));
+
+        let v6 = PgpFingerprint::from_bytes(&[0xab; 32]).unwrap();
+        assert_eq!(v6.to_string().parse(), Ok(v6.clone()));
+        assert_eq!(v6.to_string().split_whitespace().count(), 16);
+
+        for s in &[
+            "",
+            "7D0E 0B3A",
+            "7D0E 0B3A 5F28 C3D4 A1B2 9C8E 6F5A 4B3D 2C1E 0F9G",
+        ] {
+            assert_eq!(s.parse::<PgpFingerprint>(), Err(PgpFingerprintParseError));
+        }
+        assert!(PgpFingerprint::from_babble("xigak-nyryk-humil-bosek-sonax").is_err());
+    }
+}
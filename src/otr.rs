@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! OTR fingerprints.
+
+use crate::{hex, BabbleError, FromBabble, ToBabble};
+use std::{borrow::Cow, convert::TryFrom, error, fmt, str::FromStr};
+
+/// An OTR fingerprint, the SHA-1 digest of a DSA public key.
+///
+/// The `Display` implementation prints the five groups of eight
+/// uppercase hex digits of libotr, the alternate form `{:#}` adds the
+/// Babble in a second line.  The hex and Babble forms are converted
+/// with `FromStr` and `FromBabble`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{FromBabble, OtrFingerprint, ToBabble};
+///
+/// let fpr: OtrFingerprint = "3F2E8B4D 9C1A0E7F 5B6D4C3A 2E1F0D9C 8B7A6F5E"
+///     .parse()
+///     .unwrap();
+/// let babble = fpr.to_babble();
+/// assert_eq!(
+///     OtrFingerprint::from_babble(&babble).unwrap().to_string(),
+///     "3F2E8B4D 9C1A0E7F 5B6D4C3A 2E1F0D9C 8B7A6F5E"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OtrFingerprint(pub [u8; 20]);
+
+impl fmt::Display for OtrFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hex = hex::encode(&self.0).to_ascii_uppercase();
+
+        for (i, group) in hex.as_bytes().chunks(8).enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(std::str::from_utf8(group).unwrap())?;
+        }
+        if f.alternate() {
+            write!(f, "\n{}", self.to_babble())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing an invalid OTR fingerprint.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtrFingerprintParseError;
+
+impl fmt::Display for OtrFingerprintParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid OTR fingerprint syntax")
+    }
+}
+
+impl error::Error for OtrFingerprintParseError {}
+
+impl FromStr for OtrFingerprint {
+    type Err = OtrFingerprintParseError;
+
+    /// Parse 40 hex digits, optionally spaced.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.split_whitespace().collect::<String>();
+
+        hex::decode(&hex)
+            .and_then(|bytes| <[u8; 20]>::try_from(bytes).ok())
+            .map(OtrFingerprint)
+            .ok_or(OtrFingerprintParseError)
+    }
+}
+
+impl ToBabble for OtrFingerprint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+}
+
+impl FromBabble for OtrFingerprint {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 20]>::from_babble(babble).map(OtrFingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_otr_fingerprint() {
+        let human = "3F2E8B4D 9C1A0E7F 5B6D4C3A 2E1F0D9C 8B7A6F5E";
+        let fpr: OtrFingerprint = human.parse().unwrap();
+
+        assert_eq!(fpr.to_string(), human);
+        assert_eq!("3f2e8b4d9c1a0e7f5b6d4c3a2e1f0d9c8b7a6f5e".parse(), Ok(fpr));
+        assert_eq!(
+            format!("{:#}", fpr),
+            format!("{}\n{}", human, fpr.to_babble())
+        );
+        assert_eq!(OtrFingerprint::from_babble(&fpr.to_babble()), Ok(fpr));
+
+        for s in &[
+            "",
+            "3F2E8B4D",
+            "3F2E8B4D 9C1A0E7F 5B6D4C3A 2E1F0D9C 8B7A6F5G",
+        ] {
+            assert_eq!(s.parse::<OtrFingerprint>(), Err(OtrFingerprintParseError));
+        }
+        assert!(OtrFingerprint::from_babble("xigak-nyryk-humil-bosek-sonax").is_err());
+    }
+}
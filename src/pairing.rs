@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Short pairing codes with a check byte.
+//!
+//! Device pairing shows a short code on one device that the user types
+//! or speaks into the other one.  A pairing code consists of 2 to 4
+//! words of 16-bit secret followed by a check byte, encoded as Babble
+//! of 3 to 5 words, so that typos are detected before the code is
+//! compared.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::pairing::PairingCode;
+//!
+//! // A code derived from a shared secret, like a key exchange
+//! let code = PairingCode::derive(b"shared secret", 2).unwrap();
+//! let text = code.to_string();
+//! assert_eq!(text.split('-').count(), 3);
+//!
+//! let typed: PairingCode = text.parse().unwrap();
+//! assert!(typed.verify(&code));
+//! assert!("xigak-nyryk-humil".parse::<PairingCode>().is_err());
+//! ```
+
+use crate::{bubblebabble, compare::constant_time_eq_bytes, debabble, siphash::SipHasher};
+use std::{error, fmt, hash::Hasher, str::FromStr};
+
+/// Minimum number of secret words.
+pub const MIN_WORDS: usize = 2;
+/// Maximum number of secret words.
+pub const MAX_WORDS: usize = 4;
+
+/// A pairing code with 2 to 4 words of secret.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingCode {
+    secret: Vec<u8>,
+}
+
+impl PairingCode {
+    /// Create a code from the first bytes of a derived secret.
+    ///
+    /// The secret must have at least two bytes for each word, like the
+    /// output of a key derivation function.  Returns `None` if `words`
+    /// is not between 2 and 4 or the secret is too short.
+    pub fn derive(secret: &[u8], words: usize) -> Option<Self> {
+        if !(MIN_WORDS..=MAX_WORDS).contains(&words) || secret.len() < words * 2 {
+            return None;
+        }
+        Some(PairingCode {
+            secret: secret[..words * 2].to_vec(),
+        })
+    }
+
+    /// Generate a random code.
+    ///
+    /// Returns `None` if `words` is not between 2 and 4.
+    #[cfg(feature = "rand")]
+    pub fn generate(words: usize, rng: &mut impl rand_core::RngCore) -> Option<Self> {
+        let mut secret = [0; MAX_WORDS * 2];
+        rng.fill_bytes(&mut secret);
+        Self::derive(&secret, words)
+    }
+
+    /// Return the secret of the code.
+    pub fn secret(&self) -> &[u8] {
+        &self.secret
+    }
+
+    /// Compare the code with the expected code in constant time.
+    pub fn verify(&self, expected: &PairingCode) -> bool {
+        constant_time_eq_bytes(&self.secret, &expected.secret)
+    }
+}
+
+/// Return the check byte of the secret.
+fn check(secret: &[u8]) -> u8 {
+    let mut hasher = SipHasher::new(0, 0);
+    hasher.write(secret);
+    hasher.finish() as u8
+}
+
+impl fmt::Display for PairingCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bytes = self.secret.clone();
+        bytes.push(check(&self.secret));
+        f.write_str(&bubblebabble(&bytes))
+    }
+}
+
+/// Error returned when parsing an invalid or mistyped pairing code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairingCodeParseError;
+
+impl fmt::Display for PairingCodeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid pairing code")
+    }
+}
+
+impl error::Error for PairingCodeParseError {}
+
+impl FromStr for PairingCode {
+    type Err = PairingCodeParseError;
+
+    /// Parse and validate a code, ignoring case and whitespace around it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut bytes =
+            debabble(&s.trim().to_ascii_lowercase()).map_err(|_| PairingCodeParseError)?;
+        let check_byte = bytes.pop().ok_or(PairingCodeParseError)?;
+        if check(&bytes) != check_byte {
+            return Err(PairingCodeParseError);
+        }
+        Self::derive(&bytes, bytes.len() / 2)
+            .filter(|code| code.secret.len() == bytes.len())
+            .ok_or(PairingCodeParseError)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pairing_code() {
+        for words in MIN_WORDS..=MAX_WORDS {
+            let code = PairingCode::derive(&[0xab; 8], words).unwrap();
+            let text = code.to_string();
+            assert_eq!(text.split('-').count(), words + 1);
+            assert_eq!(text.parse(), Ok(code.clone()));
+            assert_eq!(text.to_uppercase().parse(), Ok(code));
+        }
+
+        assert_eq!(PairingCode::derive(&[0; 8], 1), None);
+        assert_eq!(PairingCode::derive(&[0; 8], 5), None);
+        assert_eq!(PairingCode::derive(&[0; 3], 2), None);
+
+        let code = PairingCode::derive(b"Pine", 2).unwrap();
+        let other = PairingCode::derive(b"Pina", 2).unwrap();
+        assert!(code.verify(&code));
+        assert!(!code.verify(&other));
+
+        // Valid Babble with a wrong check byte or length
+        for s in &[
+            "",
+            "xigak-nyryk-humil-bosek-sonax",
+            &bubblebabble(b"Pine\0"),
+            &bubblebabble(b"Pi"),
+        ] {
+            assert_eq!(s.parse::<PairingCode>(), Err(PairingCodeParseError));
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn test_generate_pairing_code() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let code = PairingCode::generate(3, &mut rng).unwrap();
+        assert_eq!(code.secret().len(), 6);
+        assert_eq!(code.to_string().parse(), Ok(code));
+        assert!(PairingCode::generate(0, &mut rng).is_none());
+    }
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Regular expressions of valid Babble.
+
+use crate::{CONSONANTS, VOWELS};
+
+/// Return a regular expression that matches syntactically valid Babble
+/// of `len` bytes, or of any length if `len` is `None`.
+///
+/// The pattern only uses character classes, groups, and repetitions,
+/// so it works with POSIX extended regular expressions like `grep -E`
+/// and the regex engines of most languages.  It is not anchored and
+/// does not verify the checksums, use `debabble` to decode a match.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::babble_regex;
+///
+/// assert_eq!(babble_regex(Some(1)), "x[aeiouy][bcdfghklmnprstvz][aeiouy]x");
+/// assert_eq!(
+///     babble_regex(None),
+///     "x([aeiouy][bcdfghklmnprstvz][aeiouy][bcdfghklmnprstvz]-[bcdfghklmnprstvz])*\
+///      [aeiouy][bcdfghklmnprstvzx][aeiouy]x"
+/// );
+/// ```
+pub fn babble_regex(len: Option<usize>) -> String {
+    let vowel = format!("[{}]", VOWELS.iter().collect::<String>());
+    // The last consonant `x` only appears in the final tuple of even input
+    let consonants = CONSONANTS[..16].iter().collect::<String>();
+    let consonant = format!("[{}]", consonants);
+
+    let tuple = format!("({v}{c}{v}{c}-{c})", v = vowel, c = consonant);
+    let (tuples, last) = match len {
+        None => (format!("{}*", tuple), format!("[{}x]", consonants)),
+        Some(len) => {
+            let tuples = match len / 2 {
+                0 => String::new(),
+                1 => tuple,
+                n => format!("{}{{{}}}", tuple, n),
+            };
+            let last = if len % 2 == 1 {
+                consonant
+            } else {
+                "x".to_string()
+            };
+            (tuples, last)
+        }
+    };
+
+    format!("x{}{v}{}{v}x", tuples, last, v = vowel)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bubblebabble;
+    use regex::Regex;
+
+    #[test]
+    fn test_babble_regex() {
+        let any = Regex::new(&format!("^{}$", babble_regex(None))).unwrap();
+        for len in 0..16 {
+            let exact = Regex::new(&format!("^{}$", babble_regex(Some(len)))).unwrap();
+            for data in &[vec![0; len], vec![0xff; len], (0..len as u8).collect()] {
+                let babble = bubblebabble(data);
+                assert!(any.is_match(&babble), "{}", babble);
+                assert!(exact.is_match(&babble), "{}", babble);
+                assert!(!exact.is_match(&bubblebabble(&[&data[..], &[0]].concat())));
+                assert!(!any.is_match(&babble.replace('-', "")) || len < 2);
+            }
+        }
+
+        let logs = "key xigak-nyryk-humil-bosek-sonax accepted";
+        let found = Regex::new(&babble_regex(None)).unwrap().find(logs).unwrap();
+        assert_eq!(found.as_str(), "xigak-nyryk-humil-bosek-sonax");
+    }
+}
@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Phonetic confusability of Babble strings.
+
+/// Pairs of letters that sound alike and the cost of confusing them,
+/// from 0 for the same sound to 1 for clearly different sounds.
+const CONFUSABLE: [(u8, u8, f64); 22] = [
+    // Vowels
+    (b'e', b'i', 0.25),
+    (b'i', b'y', 0.25),
+    (b'o', b'u', 0.25),
+    (b'a', b'e', 0.5),
+    (b'a', b'o', 0.5),
+    (b'e', b'y', 0.5),
+    (b'u', b'y', 0.5),
+    // Voiced and voiceless pairs
+    (b'b', b'p', 0.25),
+    (b'd', b't', 0.25),
+    (b'g', b'k', 0.25),
+    (b'f', b'v', 0.25),
+    (b's', b'z', 0.25),
+    // Same sound
+    (b'c', b'k', 0.25),
+    (b'c', b's', 0.5),
+    (b'k', b'x', 0.5),
+    (b'x', b'z', 0.5),
+    // Similar sounds
+    (b'm', b'n', 0.25),
+    (b'b', b'v', 0.5),
+    (b'b', b'd', 0.5),
+    (b'f', b's', 0.5),
+    (b'l', b'r', 0.5),
+    (b'p', b't', 0.5),
+];
+
+/// Return the cost of confusing two letters.
+fn cost(a: u8, b: u8) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+    CONFUSABLE
+        .iter()
+        .find(|(x, y, _)| (*x, *y) == (a, b) || (*x, *y) == (b, a))
+        .map_or(1.0, |(_, _, cost)| *cost)
+}
+
+/// Score how easily two Babble strings are confused when spoken.
+///
+/// The letters are compared position by position with the cost of
+/// confusing letters that sound alike, like `b` and `p` or `m` and
+/// `n`.  The result is 1.0 for strings that sound the same and 0.0 for
+/// strings without similar sounds.  Case and separators are ignored.
+///
+/// Tools that display a fingerprint can compare it with the other
+/// known keys and warn about a score close to 1.0, even if the Babble
+/// strings are different.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::confusability;
+///
+/// let babble = "xigak-nyryk-humil-bosek-sonax";
+/// assert_eq!(confusability(babble, babble), 1.0);
+/// assert!(confusability(babble, "xigak-nyryk-humil-posek-sunax") > 0.9);
+/// assert!(confusability(babble, "xenem-cirek-lomad-hupyh-zevix") < 0.5);
+/// ```
+pub fn confusability(a: &str, b: &str) -> f64 {
+    let letters = |s: &str| {
+        s.bytes()
+            .filter(u8::is_ascii_alphanumeric)
+            .map(|b| b.to_ascii_lowercase())
+            .collect::<Vec<_>>()
+    };
+    let (a, b) = (letters(a), letters(b));
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 1.0;
+    }
+
+    let distance = (0..len)
+        .map(|i| match (a.get(i), b.get(i)) {
+            (Some(&x), Some(&y)) => cost(x, y),
+            _ => 1.0,
+        })
+        .sum::<f64>();
+
+    1.0 - distance / len as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_confusability() {
+        let babble = "xigak-nyryk-humil-bosek-sonax";
+
+        assert_eq!(confusability(babble, "XIGAK NYRYK HUMIL BOSEK SONAX"), 1.0);
+        assert_eq!(confusability("", ""), 1.0);
+        assert_eq!(confusability("xebab", ""), 0.0);
+        assert_eq!(confusability("bab", "pab"), 1.0 - 0.25 / 3.0);
+
+        // Similar sounds are more confusable than different sounds
+        let similar = confusability(babble, "xigak-nyryk-humil-posek-sonax");
+        let different = confusability(babble, "xigak-nyryk-humil-rosek-sonax");
+        assert!(similar > different);
+        assert!(different < 1.0);
+        assert_eq!(
+            confusability("xigak", "zigak"),
+            confusability("zigak", "xigak")
+        );
+    }
+}
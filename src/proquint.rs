@@ -0,0 +1,147 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Proquint encoding of 16-bit words.
+//!
+//! Proquints are "PRO-nouncable QUINT-uplets" of alternating consonants
+//! and vowels that encode 16 bits each, like `lusab-babad` for the IPv4
+//! address `127.0.0.1`.  Unlike Babble, proquints have no checksum and
+//! only encode data of an even length.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::proquint;
+//!
+//! assert_eq!(proquint::encode(&[127, 0, 0, 1]).unwrap(), "lusab-babad");
+//! assert_eq!(proquint::decode("lusab-babad").unwrap(), [127, 0, 0, 1]);
+//! ```
+
+use crate::BabbleError;
+
+const CONSONANTS: &[u8; 16] = b"bdfghjklmnprstvz";
+const VOWELS: &[u8; 4] = b"aiou";
+
+/// Encode data as proquints separated by `-`.
+///
+/// Returns `BabbleError::InvalidLength` if the data has an odd length.
+pub fn encode(data: &[u8]) -> Result<String, BabbleError> {
+    if !data.len().is_multiple_of(2) {
+        return Err(BabbleError::InvalidLength(data.len()));
+    }
+
+    let mut result = String::with_capacity(data.len() * 3);
+    for (i, chunk) in data.chunks(2).enumerate() {
+        if i > 0 {
+            result.push('-');
+        }
+        let word = u16::from_be_bytes([chunk[0], chunk[1]]) as usize;
+        result.push(CONSONANTS[word >> 12] as char);
+        result.push(VOWELS[(word >> 10) & 0x3] as char);
+        result.push(CONSONANTS[(word >> 6) & 0xf] as char);
+        result.push(VOWELS[(word >> 4) & 0x3] as char);
+        result.push(CONSONANTS[word & 0xf] as char);
+    }
+
+    Ok(result)
+}
+
+/// Decode proquints.
+///
+/// Letters are case-insensitive and the words may be separated by `-`
+/// or whitespace.
+pub fn decode(proquint: &str) -> Result<Vec<u8>, BabbleError> {
+    let mut result = Vec::with_capacity(proquint.len() / 3);
+    let mut word = 0u16;
+    let mut letters = 0;
+
+    for (offset, c) in proquint.char_indices() {
+        if c == '-' || c.is_whitespace() {
+            if letters != 0 {
+                return Err(BabbleError::InvalidCharacter(c, offset));
+            }
+            continue;
+        }
+
+        let lower = c.to_ascii_lowercase() as u8;
+        let (table, bits): (&[u8], _) = if letters % 2 == 0 {
+            (CONSONANTS, 4)
+        } else {
+            (VOWELS, 2)
+        };
+        let value = table
+            .iter()
+            .position(|&b| c.is_ascii() && b == lower)
+            .ok_or(BabbleError::InvalidCharacter(c, offset))?;
+        word = (word << bits) | value as u16;
+
+        letters += 1;
+        if letters == 5 {
+            result.extend_from_slice(&word.to_be_bytes());
+            word = 0;
+            letters = 0;
+        }
+    }
+
+    if letters != 0 {
+        return Err(BabbleError::InvalidLength(proquint.len()));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proquint() {
+        let tests = [
+            ([127, 0, 0, 1], "lusab-babad"),
+            ([63, 84, 220, 193], "gutih-tugad"),
+            ([140, 98, 193, 141], "mudof-sakat"),
+            ([212, 58, 253, 68], "tibup-zujah"),
+            ([12, 110, 110, 204], "budov-kuras"),
+        ];
+
+        for (data, proquint) in tests.iter() {
+            assert_eq!(encode(data).as_deref(), Ok(*proquint));
+            assert_eq!(decode(proquint).as_deref(), Ok(&data[..]));
+        }
+
+        assert_eq!(encode(b""), Ok(String::new()));
+        assert_eq!(decode(""), Ok(vec![]));
+        assert_eq!(decode("LUSAB babad\n"), Ok(vec![127, 0, 0, 1]));
+        assert_eq!(encode(b"abc"), Err(BabbleError::InvalidLength(3)));
+        assert_eq!(decode("lusab-bab"), Err(BabbleError::InvalidLength(9)));
+        assert_eq!(
+            decode("lusab-baxad"),
+            Err(BabbleError::InvalidCharacter('x', 8))
+        );
+        assert_eq!(decode("lus-ab"), Err(BabbleError::InvalidCharacter('-', 3)));
+    }
+}
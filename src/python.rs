@@ -0,0 +1,80 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Python bindings.
+//!
+//! The `bubblebabble` extension module is built as a wheel with
+//! `maturin build`, see `pyproject.toml`:
+//!
+//! ```python
+//! import bubblebabble
+//!
+//! bubblebabble.encode(b"Pineapple")  # 'xigak-nyryk-humil-bosek-sonax'
+//! bubblebabble.decode("xigak-nyryk-humil-bosek-sonax")  # b'Pineapple'
+//! ```
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyBytes};
+
+/// Convert bytes to Babble.
+#[pyfunction]
+fn encode(data: &[u8]) -> String {
+    crate::bubblebabble(data)
+}
+
+/// Convert bytes to stable Babble.
+#[pyfunction]
+fn encode_stable(data: &[u8]) -> String {
+    crate::stablebabble(data)
+}
+
+/// Convert Babble back to bytes, raising `ValueError` if it is invalid.
+#[pyfunction]
+fn decode<'py>(py: Python<'py>, babble: &str) -> PyResult<Bound<'py, PyBytes>> {
+    crate::debabble(babble)
+        .map(|bytes| PyBytes::new(py, &bytes))
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// Convert stable Babble back to bytes, raising `ValueError` if it is
+/// invalid.
+#[pyfunction]
+fn decode_stable<'py>(py: Python<'py>, babble: &str) -> PyResult<Bound<'py, PyBytes>> {
+    crate::debabble_stable(babble)
+        .map(|bytes| PyBytes::new(py, &bytes))
+        .map_err(|err| PyValueError::new_err(err.to_string()))
+}
+
+/// The Bubble Babble Binary Data Encoding.
+#[pymodule]
+#[pyo3(name = "bubblebabble")]
+fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(encode, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_stable, m)?)?;
+    m.add_function(wrap_pyfunction!(decode, m)?)?;
+    m.add_function(wrap_pyfunction!(decode_stable, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Random Babble identifiers.
+
+use crate::bubblebabble;
+use rand_core::RngCore;
+
+/// Generate `n_bytes` random bytes and return them with their Babble.
+///
+/// The Babble is pronounceable for pairing codes or temporary
+/// passwords, and the bytes can be stored or compared instead of the
+/// text.  A cryptographically secure `rng` should be used for secrets.
+///
+/// # Examples
+///
+/// ```rust
+/// let (bytes, babble) = bubblebabble::random_babble(4, &mut rand::thread_rng());
+/// assert_eq!(bytes.len(), 4);
+/// assert_eq!(bubblebabble::debabble(&babble).unwrap(), bytes);
+/// ```
+pub fn random_babble(n_bytes: usize, rng: &mut impl RngCore) -> (Vec<u8>, String) {
+    let mut bytes = vec![0; n_bytes];
+    rng.fill_bytes(&mut bytes);
+    let babble = bubblebabble(&bytes);
+    (bytes, babble)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_random_babble() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (bytes, babble) = random_babble(16, &mut rng);
+
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(babble, bubblebabble(&bytes));
+        assert_ne!(random_babble(16, &mut rng), (bytes, babble));
+        assert_eq!(random_babble(0, &mut rng).1, "xexax");
+    }
+}
@@ -0,0 +1,141 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! OpenSSH randomart visual fingerprints.
+
+/// Width of the field.
+const WIDTH: usize = 17;
+/// Height of the field.
+const HEIGHT: usize = 9;
+/// Characters for the number of visits, followed by start and end.
+const SYMBOLS: &[u8] = b" .o+=*BOX@%&#/^SE";
+
+/// Return the "drunken bishop" randomart of a fingerprint.
+///
+/// This is the visual fingerprint that is printed by `ssh-keygen -lv`,
+/// usually for the digest of a public key.  The `title` and `footer`
+/// are printed in brackets in the top and bottom border, or omitted if
+/// they are empty, and the lines are separated by newlines.
+///
+/// # Examples
+///
+/// ```rust
+/// let digest = [
+///     0xa8, 0xe7, 0x0b, 0xb4, 0x69, 0x9d, 0x80, 0xf1, 0xba, 0xf8, 0x88, 0x80, 0x80, 0x74, 0xb7,
+///     0x1a,
+/// ];
+/// let art = bubblebabble::randomart(&digest, "ED25519 256", "MD5");
+/// assert_eq!(art.lines().nth(5), Some("|o .E+.. S        |"));
+/// ```
+pub fn randomart(data: &[u8], title: &str, footer: &str) -> String {
+    let mut field = [[0usize; WIDTH]; HEIGHT];
+    let (mut x, mut y) = (WIDTH / 2, HEIGHT / 2);
+    let visits = SYMBOLS.len() - 3;
+
+    // The bishop moves diagonally for each pair of bits
+    for &byte in data {
+        for step in 0..4 {
+            let bits = byte >> (step * 2);
+            x = if bits & 0x1 != 0 {
+                (x + 1).min(WIDTH - 1)
+            } else {
+                x.saturating_sub(1)
+            };
+            y = if bits & 0x2 != 0 {
+                (y + 1).min(HEIGHT - 1)
+            } else {
+                y.saturating_sub(1)
+            };
+            if field[y][x] < visits {
+                field[y][x] += 1;
+            }
+        }
+    }
+    field[HEIGHT / 2][WIDTH / 2] = SYMBOLS.len() - 2;
+    field[y][x] = SYMBOLS.len() - 1;
+
+    let mut result = border(title);
+    for row in field.iter() {
+        result.push_str("\n|");
+        result.extend(row.iter().map(|&i| SYMBOLS[i] as char));
+        result.push('|');
+    }
+    result.push('\n');
+    result.push_str(&border(footer));
+
+    result
+}
+
+/// Return a border line with the bracketed label in the center.
+fn border(label: &str) -> String {
+    let label = if label.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", label).chars().take(WIDTH).collect()
+    };
+    let len = label.chars().count();
+    let left = (WIDTH - len) / 2;
+
+    format!(
+        "+{}{}{}+",
+        "-".repeat(left),
+        label,
+        "-".repeat(WIDTH - len - left)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_randomart() {
+        // ssh-keygen -lv -E md5 of an ed25519 key
+        let digest = [
+            0xa8, 0xe7, 0x0b, 0xb4, 0x69, 0x9d, 0x80, 0xf1, 0xba, 0xf8, 0x88, 0x80, 0x80, 0x74,
+            0xb7, 0x1a,
+        ];
+        let art = "\
++--[ED25519 256]--+
+|                 |
+|                 |
+| ... .           |
+|o .+. ..         |
+|o .E+.. S        |
+|o  oo* .         |
+|o ..* +          |
+|oo o +           |
+|+.o   o.         |
++------[MD5]------+";
+        assert_eq!(randomart(&digest, "ED25519 256", "MD5"), art);
+
+        let art = randomart(b"", "", "A very long footer");
+        assert!(art.starts_with("+-----------------+\n"));
+        assert!(art.contains("|        E        |"));
+        assert!(art.ends_with("\n+[A very long foot+"));
+    }
+}
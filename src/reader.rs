@@ -0,0 +1,159 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Streaming encoder for `io::Read` sources.
+
+use crate::{writer::WrapBuf, Encoder};
+use std::io::{self, Read};
+
+/// An `io::Read` adapter that reads bytes from the inner reader and
+/// yields their Babble text.
+///
+/// The data is encoded in chunks, so `io::copy` converts a file of any
+/// size in constant memory.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleReader;
+/// use std::io::Read;
+///
+/// let mut reader = BabbleReader::new(&b"Pineapple"[..]);
+/// let mut babble = String::new();
+/// reader.read_to_string(&mut babble).unwrap();
+/// assert_eq!(babble, "xigak-nyryk-humil-bosek-sonax");
+/// ```
+#[derive(Debug)]
+pub struct BabbleReader<R: Read> {
+    inner: R,
+    encoder: Option<Encoder>,
+    babble: String,
+    text: WrapBuf,
+}
+
+impl<R: Read> BabbleReader<R> {
+    /// Return a reader that encodes to the Bubble Babble format.
+    pub fn new(inner: R) -> Self {
+        Self::with_encoder(inner, Encoder::new())
+    }
+
+    /// Return a reader that encodes to the stable Babble format.
+    pub fn stable(inner: R) -> Self {
+        Self::with_encoder(inner, Encoder::stable())
+    }
+
+    fn with_encoder(inner: R, encoder: Encoder) -> Self {
+        BabbleReader {
+            inner,
+            encoder: Some(encoder),
+            babble: String::new(),
+            text: WrapBuf::new(),
+        }
+    }
+
+    /// Wrap the text with a newline after `width` characters, 0
+    /// disables wrapping.
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.text.width = width;
+        self
+    }
+
+    /// Return a reference to the inner reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the inner reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Return the inner reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for BabbleReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while !buf.is_empty() {
+            if !self.text.is_empty() {
+                let pending = self.text.pending();
+                let len = buf.len().min(pending.len());
+                buf[..len].copy_from_slice(&pending[..len]);
+                self.text.consume(len);
+                return Ok(len);
+            }
+
+            let encoder = match self.encoder.as_mut() {
+                Some(encoder) => encoder,
+                None => break,
+            };
+            let mut chunk = [0; 4096];
+            match self.inner.read(&mut chunk)? {
+                0 => {
+                    if let Some(encoder) = self.encoder.take() {
+                        encoder.finish(&mut self.babble);
+                    }
+                }
+                len => encoder.update(&chunk[..len], &mut self.babble),
+            }
+            self.text.push_str(&self.babble);
+            self.babble.clear();
+        }
+
+        Ok(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubblebabble, stablebabble};
+
+    #[test]
+    fn test_babble_reader() {
+        let data = (0..=255).cycle().take(10000).collect::<Vec<u8>>();
+
+        let mut babble = Vec::new();
+        io::copy(&mut BabbleReader::new(&data[..]), &mut babble).unwrap();
+        assert_eq!(babble, bubblebabble(&data).as_bytes());
+
+        let mut reader = BabbleReader::stable(&data[..]).wrap(64);
+        let mut babble = String::new();
+        reader.read_to_string(&mut babble).unwrap();
+        assert!(babble.lines().all(|line| line.len() <= 64));
+        assert_eq!(babble.replace('\n', ""), stablebabble(&data));
+
+        let mut babble = [0; 3];
+        let mut reader = BabbleReader::new(&[][..]);
+        assert_eq!(reader.read(&mut babble).unwrap(), 3);
+        assert_eq!(reader.read(&mut babble).unwrap(), 2);
+        assert_eq!(&babble[..2], b"ax");
+        assert_eq!(reader.read(&mut babble).unwrap(), 0);
+    }
+}
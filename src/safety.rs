@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Signal-style safety numbers.
+
+use crate::ToBabble;
+use sha2::{Digest, Sha512};
+use std::{borrow::Cow, convert::TryInto, fmt};
+
+/// Number of SHA-512 rounds of each party's fingerprint.
+const ITERATIONS: usize = 5200;
+
+/// Length of each party's fingerprint in bytes.
+const FINGERPRINT_LEN: usize = 30;
+
+/// A safety number to verify the identity keys of two parties.
+///
+/// Each party's fingerprint is derived from the identity key and a
+/// stable identifier with the iterated SHA-512 of Signal.  The combined
+/// number is the same for both parties and can be compared as 60
+/// digits in groups of five or as Babble.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{SafetyNumber, ToBabble};
+///
+/// let alice = SafetyNumber::new(b"alice", &[0x05; 33], b"bob", &[0x06; 33]);
+/// let bob = SafetyNumber::new(b"bob", &[0x06; 33], b"alice", &[0x05; 33]);
+/// assert_eq!(alice.digits(), bob.digits());
+/// assert_eq!(alice.to_babble(), bob.to_babble());
+/// assert_eq!(alice.to_string().split(' ').count(), 12);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SafetyNumber {
+    combined: [u8; FINGERPRINT_LEN * 2],
+}
+
+impl SafetyNumber {
+    /// Derive the safety number from the stable identifiers and the
+    /// serialized identity keys of the local and the remote party.
+    pub fn new(local_id: &[u8], local_key: &[u8], remote_id: &[u8], remote_key: &[u8]) -> Self {
+        let mut local = fingerprint(local_id, local_key);
+        let mut remote = fingerprint(remote_id, remote_key);
+        if digits(&remote) < digits(&local) {
+            std::mem::swap(&mut local, &mut remote);
+        }
+
+        let mut combined = [0; FINGERPRINT_LEN * 2];
+        combined[..FINGERPRINT_LEN].copy_from_slice(&local);
+        combined[FINGERPRINT_LEN..].copy_from_slice(&remote);
+        SafetyNumber { combined }
+    }
+
+    /// Return the 60 digits of the safety number.
+    pub fn digits(&self) -> String {
+        digits(&self.combined)
+    }
+}
+
+/// Print the digits in groups of five.
+impl fmt::Display for SafetyNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = self.digits();
+
+        for (i, group) in digits.as_bytes().chunks(5).enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            f.write_str(std::str::from_utf8(group).unwrap())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ToBabble for SafetyNumber {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.combined)
+    }
+}
+
+fn fingerprint(id: &[u8], key: &[u8]) -> [u8; FINGERPRINT_LEN] {
+    let mut hash = Sha512::new()
+        .chain_update([0, 0])
+        .chain_update(key)
+        .chain_update(id)
+        .chain_update(key)
+        .finalize();
+    for _ in 1..ITERATIONS {
+        hash = Sha512::new()
+            .chain_update(hash)
+            .chain_update(key)
+            .finalize();
+    }
+    hash[..FINGERPRINT_LEN].try_into().unwrap()
+}
+
+/// Convert every five bytes to a group of five digits.
+fn digits(bytes: &[u8]) -> String {
+    bytes
+        .chunks(5)
+        .map(|chunk| {
+            let n = chunk.iter().fold(0u64, |n, &b| n << 8 | b as u64);
+            format!("{:05}", n % 100_000)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safety_number() {
+        let local = SafetyNumber::new(b"+14152222222", &[0x05; 33], b"+14153333333", &[0x06; 33]);
+        let remote = SafetyNumber::new(b"+14153333333", &[0x06; 33], b"+14152222222", &[0x05; 33]);
+
+        assert_eq!(local, remote);
+        assert_eq!(local.digits().len(), 60);
+        assert!(local.digits().bytes().all(|c| c.is_ascii_digit()));
+        assert_eq!(local.to_string().replace(' ', ""), local.digits());
+        assert_eq!(local.to_babble().split('-').count(), 31);
+
+        let other = SafetyNumber::new(b"+14152222222", &[0x05; 33], b"+14153333333", &[0x07; 33]);
+        assert_ne!(local.digits(), other.digits());
+
+        // Version 1 vector of libsignal's NumericFingerprintGeneratorTest.
+        let alice = [
+            0x05, 0x06, 0x86, 0x3b, 0xc6, 0x6d, 0x02, 0xb4, 0x0d, 0x27, 0xb8, 0xd4, 0x9c, 0xa7,
+            0xc0, 0x9e, 0x92, 0x39, 0x23, 0x6f, 0x9d, 0x7d, 0x25, 0xd6, 0xfc, 0xca, 0x5c, 0xe1,
+            0x3c, 0x70, 0x64, 0xd8, 0x68,
+        ];
+        let bob = [
+            0x05, 0xf7, 0x81, 0xb6, 0xfb, 0x32, 0xfe, 0xd9, 0xba, 0x1c, 0xf2, 0xde, 0x97, 0x8d,
+            0x4d, 0x5d, 0xa2, 0x8d, 0xc3, 0x40, 0x46, 0xae, 0x81, 0x44, 0x02, 0xb5, 0xc0, 0xdb,
+            0xd9, 0x6f, 0xda, 0x90, 0x7b,
+        ];
+
+        let number = SafetyNumber::new(b"+14152222222", &alice, b"+14153333333", &bob);
+        assert_eq!(
+            number.digits(),
+            "300354477692869396892869876765458257569162576843440918079131"
+        );
+    }
+}
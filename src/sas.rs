@@ -0,0 +1,179 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! ZRTP-style short authentication strings.
+//!
+//! After a key exchange, like the one of a VoIP or WebRTC call, both
+//! parties derive a short authentication string (SAS) from the shared
+//! key and compare it verbally.  As recommended by ZRTP, each party
+//! reads one half of the SAS aloud and checks the other half that is
+//! read by the peer, so that neither party can just repeat what it
+//! heard.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::sas::{Role, Sas};
+//!
+//! let alice = Sas::derive(b"shared key", 4).unwrap();
+//! let bob = Sas::derive(b"shared key", 4).unwrap();
+//!
+//! // Alice reads the first half and Bob confirms it
+//! let spoken = alice.spoken(Role::Initiator);
+//! assert!(bob.verify(Role::Responder, &spoken));
+//!
+//! // Bob reads the second half and Alice confirms it
+//! let spoken = bob.spoken(Role::Responder);
+//! assert!(alice.verify(Role::Initiator, &spoken));
+//! assert!(!alice.verify(Role::Initiator, &alice.spoken(Role::Initiator)));
+//! ```
+
+use crate::{bubblebabble, compare::constant_time_eq};
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Minimum number of words.
+pub const MIN_WORDS: usize = 2;
+/// Maximum number of words.
+pub const MAX_WORDS: usize = 8;
+
+/// Domain separation of the SAS hash.
+const LABEL: &[u8] = b"bubblebabble SAS";
+
+/// The role of a party in the key exchange.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    /// The party that started the exchange reads the first half.
+    Initiator,
+    /// The other party reads the second half.
+    Responder,
+}
+
+impl Role {
+    /// Return the role of the peer.
+    pub fn peer(self) -> Self {
+        match self {
+            Role::Initiator => Role::Responder,
+            Role::Responder => Role::Initiator,
+        }
+    }
+}
+
+/// A short authentication string of 2 to 8 Babble words.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sas {
+    babble: String,
+}
+
+impl Sas {
+    /// Derive the SAS from the shared key of a key exchange.
+    ///
+    /// The SAS is the Babble of the first `2 * words - 1` bytes of the
+    /// SHA-256 of the key.  Returns `None` if `words` is odd or not
+    /// between 2 and 8.
+    pub fn derive(key: &[u8], words: usize) -> Option<Self> {
+        if !(MIN_WORDS..=MAX_WORDS).contains(&words) || !words.is_multiple_of(2) {
+            return None;
+        }
+        let hash = Sha256::new()
+            .chain_update(LABEL)
+            .chain_update(key)
+            .finalize();
+        Some(Sas {
+            babble: bubblebabble(&hash[..words * 2 - 1]),
+        })
+    }
+
+    /// Return the words of the SAS.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.babble.split('-')
+    }
+
+    /// Return the half of the SAS that the party reads aloud.
+    pub fn spoken(&self, role: Role) -> String {
+        let words = self.words().collect::<Vec<_>>();
+        let (first, second) = words.split_at(words.len() / 2);
+        match role {
+            Role::Initiator => first.join("-"),
+            Role::Responder => second.join("-"),
+        }
+    }
+
+    /// Return the half of the SAS that the party expects from the peer.
+    pub fn expected(&self, role: Role) -> String {
+        self.spoken(role.peer())
+    }
+
+    /// Compare the words read by the peer with the expected half.
+    ///
+    /// The comparison runs in constant time and ignores case, spaces,
+    /// and dashes between the words.
+    pub fn verify(&self, role: Role, heard: &str) -> bool {
+        constant_time_eq(heard, &self.expected(role))
+    }
+}
+
+/// Print all words of the SAS.
+impl fmt::Display for Sas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.babble)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sas() {
+        for words in (MIN_WORDS..=MAX_WORDS).step_by(2) {
+            let sas = Sas::derive(b"key", words).unwrap();
+            assert_eq!(sas.words().count(), words);
+            assert_eq!(
+                format!(
+                    "{}-{}",
+                    sas.spoken(Role::Initiator),
+                    sas.spoken(Role::Responder)
+                ),
+                sas.to_string()
+            );
+            assert_eq!(sas.expected(Role::Initiator), sas.spoken(Role::Responder));
+        }
+
+        let sas = Sas::derive(b"key", 4).unwrap();
+        let other = Sas::derive(b"other key", 4).unwrap();
+        let spoken = sas.spoken(Role::Responder);
+        assert!(sas.verify(Role::Initiator, &spoken));
+        assert!(sas.verify(Role::Initiator, &spoken.to_uppercase().replace('-', " ")));
+        assert!(!other.verify(Role::Initiator, &spoken));
+        assert!(!sas.verify(Role::Responder, &spoken));
+
+        for words in &[0, 1, 3, 10] {
+            assert_eq!(Sas::derive(b"key", *words), None);
+        }
+    }
+}
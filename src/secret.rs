@@ -0,0 +1,119 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Integration with `secrecy` for sensitive material.
+
+use crate::{BabbleError, Decoder, Encoder};
+use secrecy::{ExposeSecret, SecretSlice, SecretString};
+use std::fmt;
+use zeroize::Zeroizing;
+
+/// A Babble string of secret key material.
+///
+/// The Babble is kept in a `secrecy::SecretString` that is zeroized
+/// on drop, and the buffers are allocated with the final size so no
+/// copies are left behind when they grow.  The `Debug` output is
+/// redacted.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::SecretBabble;
+/// use secrecy::ExposeSecret;
+///
+/// let secret = SecretBabble::encode(b"Pineapple");
+/// assert_eq!(secret.expose_secret(), "xigak-nyryk-humil-bosek-sonax");
+/// assert_eq!(secret.decode().unwrap().expose_secret(), b"Pineapple");
+/// ```
+#[derive(Clone)]
+pub struct SecretBabble(SecretString);
+
+impl SecretBabble {
+    /// Encode secret `bytes` as Babble.
+    pub fn encode(bytes: &[u8]) -> Self {
+        let mut babble = String::with_capacity((bytes.len() / 2 + 1) * 6 - 1);
+        let mut encoder = Encoder::new();
+        encoder.update(bytes, &mut babble);
+        encoder.finish(&mut babble);
+        SecretBabble(babble.into())
+    }
+
+    /// Decode the Babble to secret bytes.
+    pub fn decode(&self) -> Result<SecretSlice<u8>, BabbleError> {
+        let babble = self.0.expose_secret();
+        let mut bytes = Zeroizing::new(Vec::with_capacity(babble.len() / 3 + 1));
+        let mut decoder = Decoder::new();
+        decoder.update(babble, &mut bytes)?;
+        decoder.finish(&mut bytes)?;
+        Ok(bytes.to_vec().into())
+    }
+}
+
+impl ExposeSecret<str> for SecretBabble {
+    fn expose_secret(&self) -> &str {
+        self.0.expose_secret()
+    }
+}
+
+impl From<SecretString> for SecretBabble {
+    fn from(babble: SecretString) -> Self {
+        SecretBabble(babble)
+    }
+}
+
+impl From<String> for SecretBabble {
+    fn from(babble: String) -> Self {
+        SecretBabble(babble.into())
+    }
+}
+
+impl fmt::Debug for SecretBabble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretBabble([REDACTED])")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bubblebabble;
+
+    #[test]
+    fn test_secret_babble() {
+        use secrecy::ExposeSecret;
+
+        for data in [&b""[..], b"P", b"Pineapple", &[0xff; 32]].iter() {
+            let secret = SecretBabble::encode(data);
+            assert_eq!(secret.expose_secret(), bubblebabble(data));
+            assert_eq!(secret.decode().unwrap().expose_secret(), *data);
+        }
+
+        let secret = SecretBabble::from("xigak-nyryk".to_string());
+        assert!(secret.decode().is_err());
+        assert_eq!(format!("{:?}", secret), "SecretBabble([REDACTED])");
+    }
+}
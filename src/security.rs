@@ -0,0 +1,173 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Security and length of Babble fingerprints.
+
+use std::convert::TryFrom;
+
+/// Return the number of bits of a fingerprint that are shown by the
+/// first `words` Babble words.
+///
+/// Each word encodes two bytes, but the first word starts with the
+/// fixed `x` and the consonant that ends the last shown word only
+/// encodes half a byte.  The first `words` words of a longer
+/// fingerprint therefore show `16 * words - 4` bits.
+///
+/// # Examples
+///
+/// ```rust
+/// assert_eq!(bubblebabble::bits_of(0), 0);
+/// assert_eq!(bubblebabble::bits_of(1), 12);
+/// assert_eq!(bubblebabble::bits_of(5), 76);
+/// ```
+pub fn bits_of(words: usize) -> u32 {
+    if words == 0 {
+        return 0;
+    }
+    u32::try_from(words)
+        .ok()
+        .and_then(|words| words.checked_mul(16))
+        .map_or(u32::MAX, |bits| bits - 4)
+}
+
+/// Return the number of Babble words that must be shown and compared
+/// for the given security level in bits.
+///
+/// An attacker who can create a key that matches the shown prefix of a
+/// fingerprint needs about `2^bits` attempts for a second preimage, so
+/// `security_bits` is the number of bits to show.  If the attacker can
+/// choose both keys, a collision only needs `2^(bits/2)` attempts, so
+/// pass twice the security level.  The result is never more than the
+/// number of words of the full fingerprint, which callers must check.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{bits_of, words_needed};
+///
+/// // 64-bit second-preimage resistance
+/// assert_eq!(words_needed(64), 5);
+/// // 64-bit collision resistance
+/// assert_eq!(words_needed(2 * 64), 9);
+/// assert!(bits_of(words_needed(80)) >= 80);
+/// ```
+pub fn words_needed(security_bits: u32) -> usize {
+    if security_bits == 0 {
+        return 0;
+    }
+    (security_bits as usize + 4).div_ceil(16)
+}
+
+/// Probability that a listener mishears a spoken letter.
+///
+/// This is a rough estimate for a clear phone line, the letters of
+/// confusable pairs like `b` and `p` account for most errors.
+pub const LETTER_ERROR_RATE: f64 = 0.01;
+
+/// Length and strength of the Babble of some input.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleInfo;
+///
+/// let info = BabbleInfo::for_input_len(32);
+/// assert_eq!(info.words, 17);
+/// assert_eq!(info.len, 101);
+/// assert_eq!(info.bits, 256);
+/// assert!(info.error_probability > BabbleInfo::for_input_len(16).error_probability);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BabbleInfo {
+    /// Number of words.
+    pub words: usize,
+    /// Length of the string including the dashes.
+    pub len: usize,
+    /// Number of bits of input that are represented.
+    pub bits: usize,
+    /// Estimated probability that at least one letter is misheard when
+    /// the string is read aloud, with `LETTER_ERROR_RATE` for each
+    /// letter except for the fixed `x` at both ends.
+    pub error_probability: f64,
+}
+
+impl BabbleInfo {
+    /// Return the information for the Babble of `n` bytes.
+    pub fn for_input_len(n: usize) -> Self {
+        let words = n / 2 + 1;
+        let letters = words.saturating_mul(5) - 2;
+        let error_probability =
+            1.0 - (1.0 - LETTER_ERROR_RATE).powi(i32::try_from(letters).unwrap_or(i32::MAX));
+
+        BabbleInfo {
+            words,
+            len: words.saturating_mul(6) - 1,
+            bits: n.saturating_mul(8),
+            error_probability,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words_needed() {
+        for bits in 1..=512 {
+            let words = words_needed(bits);
+            assert!(bits_of(words) >= bits);
+            assert!(bits_of(words - 1) < bits);
+        }
+        assert_eq!(words_needed(0), 0);
+        assert_eq!(words_needed(12), 1);
+        assert_eq!(words_needed(13), 2);
+        assert_eq!(words_needed(128), 9);
+        assert_eq!(bits_of(usize::MAX), u32::MAX);
+
+        // Two words show the first three bytes and half of the fourth
+        let words = |bytes: &[u8]| crate::bubblebabble(bytes)[..11].to_string();
+        assert_eq!(words(&[0, 1, 2, 0x30, 4]), words(&[0, 1, 2, 0x3f, 5]));
+        assert_ne!(words(&[0, 1, 2, 0x30, 4]), words(&[0, 1, 2, 0x40, 4]));
+    }
+
+    #[test]
+    fn test_babble_info() {
+        for n in 0..64 {
+            let info = BabbleInfo::for_input_len(n);
+            let babble = crate::bubblebabble(&vec![0; n]);
+            assert_eq!(info.words, babble.split('-').count());
+            assert_eq!(info.len, babble.len());
+            assert_eq!(info.bits, n * 8);
+            assert!(info.error_probability > 0.0 && info.error_probability < 1.0);
+        }
+
+        let info = BabbleInfo::for_input_len(0);
+        assert!((info.error_probability - 0.0297).abs() < 0.0001);
+        assert!(BabbleInfo::for_input_len(usize::MAX).error_probability <= 1.0);
+    }
+}
@@ -0,0 +1,429 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Serialize byte fields as Babble strings with serde.
+//!
+//! The functions of this module can be used with `#[serde(with)]` on
+//! `Vec<u8>` and `[u8; N]` fields.  Human-readable formats like JSON or
+//! YAML get a Babble string and binary formats keep the raw bytes.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Host {
+//!     #[serde(with = "bubblebabble::serde")]
+//!     fingerprint: [u8; 4],
+//! }
+//!
+//! let host = Host {
+//!     fingerprint: [1, 2, 3, 4],
+//! };
+//! let json = serde_json::to_string(&host).unwrap();
+//! assert_eq!(json, r#"{"fingerprint":"xebeb-dibyb-gyxox"}"#);
+//! ```
+
+use crate::{bubblebabble, debabble, hex};
+use ::serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    convert::TryFrom,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+/// Serialize bytes as a Babble string or as raw bytes.
+pub fn serialize<T, S>(bytes: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: AsRef<[u8]> + ?Sized,
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&bubblebabble(bytes.as_ref()))
+    } else {
+        serializer.serialize_bytes(bytes.as_ref())
+    }
+}
+
+/// Deserialize bytes from a Babble string or from raw bytes.
+///
+/// Invalid Babble strings and unexpected lengths of fixed-size arrays
+/// are rejected.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: TryFrom<Vec<u8>>,
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BabbleVisitor::new(false))
+    } else {
+        deserializer.deserialize_byte_buf(BabbleVisitor::new(false))
+    }
+}
+
+/// Accept Babble or hex strings and serialize Babble.
+///
+/// Use `#[serde(with = "bubblebabble::serde::or_hex")]` on byte fields
+/// of config files that migrate from hex to Babble: existing files with
+/// hex values still load and new files get Babble.  Hex strings may have
+/// a `0x` prefix.  This is the same as `BabbleOrHex` of the `serde_with`
+/// feature without the additional dependency.
+///
+/// # Examples
+///
+/// ```rust
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Host {
+///     #[serde(with = "bubblebabble::serde::or_hex")]
+///     fingerprint: [u8; 4],
+/// }
+///
+/// let host: Host = serde_json::from_str(r#"{"fingerprint":"0x01020304"}"#).unwrap();
+/// let json = serde_json::to_string(&host).unwrap();
+/// assert_eq!(json, r#"{"fingerprint":"xebeb-dibyb-gyxox"}"#);
+/// ```
+pub mod or_hex {
+    use super::BabbleVisitor;
+    use ::serde::Deserializer;
+    use std::convert::TryFrom;
+
+    pub use super::serialize;
+
+    /// Deserialize bytes from a Babble or hex string or from raw bytes.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: TryFrom<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BabbleVisitor::new(true))
+        } else {
+            deserializer.deserialize_byte_buf(BabbleVisitor::new(true))
+        }
+    }
+}
+
+/// Wrapper that serializes bytes as Babble in human-readable formats.
+///
+/// This is the same as using `#[serde(with = "bubblebabble::serde")]`
+/// but it can be used for nested types or type parameters: config files
+/// get a readable Babble string and binary protocols the raw bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleBytes;
+///
+/// let key = BabbleBytes(vec![1, 2, 3, 4]);
+/// let json = serde_json::to_string(&[&key]).unwrap();
+/// assert_eq!(json, r#"["xebeb-dibyb-gyxox"]"#);
+///
+/// let keys: Vec<BabbleBytes<[u8; 4]>> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(keys[0].0, [1, 2, 3, 4]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BabbleBytes<T>(pub T);
+
+impl<T> BabbleBytes<T> {
+    /// Return the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for BabbleBytes<T> {
+    fn from(bytes: T) -> Self {
+        BabbleBytes(bytes)
+    }
+}
+
+impl<T> Deref for BabbleBytes<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for BabbleBytes<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: AsRef<[u8]>> AsRef<[u8]> for BabbleBytes<T> {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl<T: AsRef<[u8]>> Serialize for BabbleBytes<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(&self.0, serializer)
+    }
+}
+
+impl<'de, T: TryFrom<Vec<u8>>> Deserialize<'de> for BabbleBytes<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer).map(BabbleBytes)
+    }
+}
+
+/// `serde_with` adapter that accepts Babble or hex and emits Babble.
+///
+/// It can be used with `#[serde_as(as = "BabbleOrHex")]` on byte
+/// fields of config files that migrate from hex to Babble.  Hex strings
+/// may have a `0x` prefix.  Binary formats keep the raw bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::serde::BabbleOrHex;
+/// use serde::{Deserialize, Serialize};
+/// use serde_with::serde_as;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct Host {
+///     #[serde_as(as = "BabbleOrHex")]
+///     fingerprint: [u8; 4],
+/// }
+///
+/// let host: Host = serde_json::from_str(r#"{"fingerprint":"01020304"}"#).unwrap();
+/// let json = serde_json::to_string(&host).unwrap();
+/// assert_eq!(json, r#"{"fingerprint":"xebeb-dibyb-gyxox"}"#);
+/// ```
+#[cfg(feature = "serde_with")]
+#[derive(Clone, Copy, Debug)]
+pub struct BabbleOrHex;
+
+#[cfg(feature = "serde_with")]
+impl<T: AsRef<[u8]>> serde_with::SerializeAs<T> for BabbleOrHex {
+    fn serialize_as<S: Serializer>(source: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(source, serializer)
+    }
+}
+
+#[cfg(feature = "serde_with")]
+impl<'de, T: TryFrom<Vec<u8>>> serde_with::DeserializeAs<'de, T> for BabbleOrHex {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+        or_hex::deserialize(deserializer)
+    }
+}
+
+struct BabbleVisitor<T> {
+    // Accept hex strings as well
+    hex: bool,
+    marker: PhantomData<T>,
+}
+
+impl<T> BabbleVisitor<T>
+where
+    T: TryFrom<Vec<u8>>,
+{
+    fn new(hex: bool) -> Self {
+        BabbleVisitor {
+            hex,
+            marker: PhantomData,
+        }
+    }
+
+    fn convert<E: de::Error>(bytes: Vec<u8>) -> Result<T, E> {
+        let len = bytes.len();
+        T::try_from(bytes).map_err(|_| E::invalid_length(len, &"bytes of the expected length"))
+    }
+}
+
+impl<'de, T> de::Visitor<'de> for BabbleVisitor<T>
+where
+    T: TryFrom<Vec<u8>>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.hex {
+            f.write_str("a Babble or hex string or bytes")
+        } else {
+            f.write_str("a Babble string or bytes")
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<T, E> {
+        // Babble always contains `x`, so it is never valid hex
+        if self.hex {
+            if let Some(bytes) = hex::decode(v.strip_prefix("0x").unwrap_or(v)) {
+                return Self::convert(bytes);
+            }
+        }
+        Self::convert(debabble(v).map_err(E::custom)?)
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<T, E> {
+        Self::convert(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<T, E> {
+        Self::convert(v)
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<T, A::Error> {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Self::convert(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BabbleBytes;
+    use serde_test::{assert_tokens, Configure, Token};
+
+    #[derive(Clone, Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+    struct Key {
+        #[serde(with = "crate::serde")]
+        id: [u8; 2],
+        #[serde(with = "crate::serde")]
+        data: Vec<u8>,
+    }
+
+    #[test]
+    fn test_serde() {
+        let key = Key {
+            id: [0xff, 0x00],
+            data: b"Pineapple".to_vec(),
+        };
+        let tokens = |id, data| {
+            [
+                Token::Struct {
+                    name: "Key",
+                    len: 2,
+                },
+                Token::Str("id"),
+                id,
+                Token::Str("data"),
+                data,
+                Token::StructEnd,
+            ]
+        };
+
+        assert_tokens(
+            &key.clone().readable(),
+            &tokens(
+                Token::Str("xuzob-bixux"),
+                Token::Str("xigak-nyryk-humil-bosek-sonax"),
+            ),
+        );
+        assert_tokens(
+            &key.compact(),
+            &tokens(Token::Bytes(&[0xff, 0x00]), Token::Bytes(b"Pineapple")),
+        );
+
+        let json = r#"{"id":"xigak-nyryk-humil-bosek-sonax","data":"xexax"}"#;
+        assert!(serde_json::from_str::<Key>(json).is_err());
+        let json = r#"{"id":"xuzob-bixux","data":"xigak-nyryk-humil-bosek-sanax"}"#;
+        assert!(serde_json::from_str::<Key>(json)
+            .unwrap_err()
+            .to_string()
+            .contains("checksum mismatch"));
+    }
+
+    #[cfg(feature = "serde_with")]
+    #[test]
+    fn test_babble_or_hex() {
+        use super::BabbleOrHex;
+        use serde_with::serde_as;
+
+        #[serde_as]
+        #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        struct Host {
+            #[serde_as(as = "BabbleOrHex")]
+            key: Vec<u8>,
+        }
+
+        let host = Host {
+            key: vec![0xff, 0x00],
+        };
+        for json in &[
+            r#"{"key":"xuzob-bixux"}"#,
+            r#"{"key":"ff00"}"#,
+            r#"{"key":"0xFF00"}"#,
+        ] {
+            assert_eq!(serde_json::from_str::<Host>(json).unwrap(), host);
+        }
+        assert_eq!(
+            serde_json::to_string(&host).unwrap(),
+            r#"{"key":"xuzob-bixux"}"#
+        );
+        assert!(serde_json::from_str::<Host>(r#"{"key":"ff0"}"#).is_err());
+        assert!(serde_json::from_str::<Key>(r#"{"id":"ff00","data":"xexax"}"#).is_err());
+    }
+
+    #[test]
+    fn test_or_hex() {
+        #[derive(Debug, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        struct Host {
+            #[serde(with = "crate::serde::or_hex")]
+            key: [u8; 2],
+        }
+
+        let host = Host { key: [0xff, 0x00] };
+        for json in &[r#"{"key":"xuzob-bixux"}"#, r#"{"key":"0xff00"}"#] {
+            assert_eq!(serde_json::from_str::<Host>(json).unwrap(), host);
+        }
+        assert_eq!(
+            serde_json::to_string(&host).unwrap(),
+            r#"{"key":"xuzob-bixux"}"#
+        );
+        assert!(serde_json::from_str::<Host>(r#"{"key":"ff0000"}"#).is_err());
+        assert_tokens(
+            &host.compact(),
+            &[
+                Token::Struct {
+                    name: "Host",
+                    len: 1,
+                },
+                Token::Str("key"),
+                Token::Bytes(&[0xff, 0x00]),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn test_babble_bytes() {
+        let bytes = BabbleBytes(vec![0xff, 0x00]);
+
+        assert_tokens(&bytes.clone().readable(), &[Token::Str("xuzob-bixux")]);
+        assert_tokens(&bytes.compact(), &[Token::Bytes(&[0xff, 0x00])]);
+    }
+}
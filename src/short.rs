@@ -0,0 +1,111 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Abbreviated Babble for logs and dashboards.
+
+use crate::bubblebabble;
+
+/// Marker that is appended to abbreviated Babble.
+pub const ELLIPSIS: &str = "...";
+
+/// Return the first `words` words of the Babble of `bytes`, at least
+/// one, followed by `...` if the Babble has more words.
+///
+/// Like a short git hash, the result is a prefix of the full Babble
+/// and only the first `2 * words` bytes are encoded.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{short, short_matches};
+///
+/// let fingerprint = b"Pineapple";
+/// let abbrev = short(fingerprint, 2);
+/// assert_eq!(abbrev, "xigak-nyryk...");
+/// assert!(short_matches(&abbrev, "xigak-nyryk-humil-bosek-sonax"));
+/// assert_eq!(short(fingerprint, 5), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+pub fn short(bytes: &[u8], words: usize) -> String {
+    let words = words.max(1);
+    let len = bytes.len().min(words * 2);
+    let mut babble = bubblebabble(&bytes[..len])
+        .split('-')
+        .take(words)
+        .collect::<Vec<_>>()
+        .join("-");
+
+    if bytes.len() / 2 + 1 > words {
+        babble.push_str(ELLIPSIS);
+    }
+    babble
+}
+
+/// Check if the abbreviated Babble `short`, with or without the
+/// `...` marker, names the first words of the full `babble`.
+///
+/// Only complete words are compared and a string without the marker
+/// must match all words.
+pub fn short_matches(short: &str, babble: &str) -> bool {
+    let short = short.trim();
+    let (short, abbreviated) = match short.strip_suffix(ELLIPSIS) {
+        Some(short) => (short, true),
+        None => (short, false),
+    };
+    if short.is_empty() {
+        return false;
+    }
+
+    let mut words = babble.trim().split('-');
+    short.split('-').all(|word| words.next() == Some(word))
+        && (abbreviated || words.next().is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short() {
+        let hash = (0..32).collect::<Vec<u8>>();
+        let babble = bubblebabble(&hash);
+        for words in 1..=16 {
+            let abbrev = short(&hash, words);
+            assert!(abbrev.ends_with(ELLIPSIS));
+            assert!(babble.starts_with(abbrev.trim_end_matches(ELLIPSIS)));
+            assert!(short_matches(&abbrev, &babble));
+        }
+        assert_eq!(short(&hash, 17), babble);
+        assert_eq!(short(&hash, 0), short(&hash, 1));
+        assert_eq!(short(&[], 1), "xexax");
+
+        assert!(short_matches(&babble, &babble));
+        assert!(!short_matches("xexak-fy...", &babble));
+        assert!(!short_matches("xexak", &babble));
+        assert!(!short_matches("...", &babble));
+        assert!(!short_matches("xigak-nyryk-humil...", "xigak-nyryk"));
+    }
+}
@@ -0,0 +1,175 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Babble identities of hashable values.
+
+use crate::bubblebabble;
+use std::hash::{Hash, Hasher};
+
+/// Hash `value` with a stable hasher and convert the 64-bit result to
+/// Babble.
+///
+/// The hasher is SipHash-2-4 with zero keys, and integers are written
+/// in little-endian byte order with `usize` and `isize` widened to 64
+/// bits, so the result does not depend on the Rust release or the
+/// platform.  It still depends on the `Hash` implementation of `T`.
+/// This is a short identity for debugging, not a cryptographic digest.
+///
+/// # Examples
+///
+/// ```rust
+/// let babble = bubblebabble::babble_of_hash(&("config", 42));
+/// assert_eq!(babble, bubblebabble::babble_of_hash(&("config", 42)));
+/// assert_eq!(babble.split('-').count(), 5);
+/// ```
+pub fn babble_of_hash<T: Hash + ?Sized>(value: &T) -> String {
+    let mut hasher = SipHasher::new(0, 0);
+    value.hash(&mut hasher);
+    bubblebabble(&hasher.finish().to_be_bytes())
+}
+
+/// SipHash-2-4 with platform-independent integer encoding.
+#[derive(Debug, Clone)]
+pub(crate) struct SipHasher {
+    v: [u64; 4],
+    tail: u64,
+    ntail: usize,
+    len: usize,
+}
+
+impl SipHasher {
+    pub(crate) fn new(k0: u64, k1: u64) -> Self {
+        SipHasher {
+            v: [
+                k0 ^ 0x736f_6d65_7073_6575,
+                k1 ^ 0x646f_7261_6e64_6f6d,
+                k0 ^ 0x6c79_6765_6e65_7261,
+                k1 ^ 0x7465_6462_7974_6573,
+            ],
+            tail: 0,
+            ntail: 0,
+            len: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        let v = &mut self.v;
+        v[0] = v[0].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(13) ^ v[0];
+        v[0] = v[0].rotate_left(32);
+        v[2] = v[2].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(16) ^ v[2];
+        v[0] = v[0].wrapping_add(v[3]);
+        v[3] = v[3].rotate_left(21) ^ v[0];
+        v[2] = v[2].wrapping_add(v[1]);
+        v[1] = v[1].rotate_left(17) ^ v[2];
+        v[2] = v[2].rotate_left(32);
+    }
+
+    fn compress(&mut self, m: u64) {
+        self.v[3] ^= m;
+        self.round();
+        self.round();
+        self.v[0] ^= m;
+    }
+}
+
+impl Hasher for SipHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        self.len += bytes.len();
+        for &b in bytes {
+            self.tail |= (b as u64) << (8 * self.ntail);
+            self.ntail += 1;
+            if self.ntail == 8 {
+                let m = self.tail;
+                self.compress(m);
+                self.tail = 0;
+                self.ntail = 0;
+            }
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes())
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64)
+    }
+
+    fn finish(&self) -> u64 {
+        let mut state = self.clone();
+        let m = ((self.len as u64 & 0xff) << 56) | self.tail;
+        state.compress(m);
+        state.v[2] ^= 0xff;
+        for _ in 0..4 {
+            state.round();
+        }
+        state.v.iter().fold(0, |h, v| h ^ v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_babble_of_hash() {
+        // Reference vectors of SipHash-2-4 with the key 00 01 .. 0f
+        let k0 = u64::from_le_bytes([0, 1, 2, 3, 4, 5, 6, 7]);
+        let k1 = u64::from_le_bytes([8, 9, 10, 11, 12, 13, 14, 15]);
+        let input = (0..15).collect::<Vec<u8>>();
+        for (len, expected) in [
+            (0, 0x726f_db47_dd0e_0e31),
+            (1, 0x74f8_39c5_93dc_67fd),
+            (8, 0x93f5_f579_9a93_2462),
+            (15, 0xa129_ca61_49be_45e5),
+        ]
+        .iter()
+        {
+            let mut hasher = SipHasher::new(k0, k1);
+            hasher.write(&input[..*len]);
+            assert_eq!(hasher.finish(), *expected);
+        }
+
+        assert_eq!(babble_of_hash(&1usize), babble_of_hash(&1u64));
+        assert_ne!(babble_of_hash("Pine"), babble_of_hash("apple"));
+        assert_eq!(babble_of_hash(&42u32), "xibeb-podob-dotet-lysoc-dexax");
+    }
+}
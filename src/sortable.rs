@@ -0,0 +1,165 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Order-preserving encoding for sortable keys.
+//!
+//! Babble strings do not sort like their data because of the checksum
+//! and the order of the letters in the alphabet.  This variant encodes
+//! every 16 bits as a `CVCVC` word with the consonants and vowels in
+//! alphabetical order and without a checksum, so the strings sort like
+//! the bytes, and can be used as keys in databases and file names.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::sortable;
+//!
+//! let a = sortable::encode(&[0x2a, 0x0a]);
+//! let b = sortable::encode(&[0x2a, 0x0a, 0xe5]);
+//! let c = sortable::encode(&[0x2b]);
+//! assert_eq!(a, "dimap");
+//! assert!(a < b && b < c);
+//! assert_eq!(sortable::decode(&b).unwrap(), [0x2a, 0x0a, 0xe5]);
+//! ```
+
+use crate::BabbleError;
+
+/// 16 consonants in alphabetical order.
+const CONSONANTS: &[u8; 16] = b"bcdfghklmnprstvz";
+
+/// 4 vowels in alphabetical order.
+const VOWELS: &[u8; 4] = b"aeio";
+
+/// Encode data as `CVCVC` words separated by `-`.
+///
+/// A final odd byte is encoded as a `CVC` word whose last consonant
+/// only uses the upper two bits, so it sorts before every longer
+/// string that starts with the same byte.  Empty data is encoded as an
+/// empty string.
+pub fn encode(data: &[u8]) -> String {
+    let mut result = String::with_capacity(data.len() * 3);
+
+    for (i, chunk) in data.chunks(2).enumerate() {
+        if i > 0 {
+            result.push('-');
+        }
+        let word = u16::from_be_bytes([chunk[0], chunk.get(1).copied().unwrap_or(0)]) as usize;
+        result.push(CONSONANTS[word >> 12] as char);
+        result.push(VOWELS[(word >> 10) & 0x3] as char);
+        result.push(CONSONANTS[(word >> 6) & 0xf] as char);
+        if chunk.len() == 2 {
+            result.push(VOWELS[(word >> 4) & 0x3] as char);
+            result.push(CONSONANTS[word & 0xf] as char);
+        }
+    }
+
+    result
+}
+
+/// Decode a string of `encode` back to bytes.
+///
+/// The letters must be lowercase, like the output of `encode`, as
+/// strings of mixed case would not sort like their data.
+pub fn decode(s: &str) -> Result<Vec<u8>, BabbleError> {
+    let mut result = Vec::with_capacity(s.len() / 3 + 1);
+    if s.is_empty() {
+        return Ok(result);
+    }
+
+    let mut offset = 0;
+    let mut words = s.split('-').peekable();
+    while let Some(word) = words.next() {
+        let last = words.peek().is_none();
+        if word.len() != 5 && (word.len() != 3 || !last) {
+            return Err(BabbleError::InvalidLength(s.len()));
+        }
+
+        let mut value = 0;
+        for (i, c) in word.char_indices() {
+            let (table, bits): (&[u8], _) = if i % 2 == 0 {
+                (CONSONANTS, 4)
+            } else {
+                (VOWELS, 2)
+            };
+            let index = table
+                .iter()
+                .position(|&b| c.is_ascii() && b == c as u8)
+                .ok_or(BabbleError::InvalidCharacter(c, offset + i))?;
+            value = (value << bits) | index;
+        }
+
+        if word.len() == 5 {
+            result.extend_from_slice(&(value as u16).to_be_bytes());
+        } else if value & 0x3 == 0 {
+            result.push((value >> 2) as u8);
+        } else {
+            let c = word[2..].chars().next().unwrap_or_default();
+            return Err(BabbleError::InvalidCharacter(c, offset + 2));
+        }
+        offset += word.len() + 1;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sortable() {
+        let mut data = vec![vec![], vec![0], vec![0xff], vec![0xff, 0xff, 0xff]];
+        for a in (0..=255u8).step_by(15) {
+            for b in (0..=255u8).step_by(51) {
+                data.push(vec![a]);
+                data.push(vec![a, b]);
+                data.push(vec![a, b, a]);
+                data.push(vec![b, a, b, a]);
+            }
+        }
+        data.sort();
+        data.dedup();
+
+        let encoded = data.iter().map(|bytes| encode(bytes)).collect::<Vec<_>>();
+        let mut sorted = encoded.clone();
+        sorted.sort();
+        assert_eq!(encoded, sorted);
+        for (bytes, s) in data.iter().zip(&encoded) {
+            assert_eq!(&decode(s).unwrap(), bytes);
+        }
+
+        assert_eq!(encode(&[0xff, 0xff, 0xff]), "zozoz-zos");
+        assert_eq!(
+            decode("zozoz-zoh"),
+            Err(BabbleError::InvalidCharacter('h', 8))
+        );
+        assert_eq!(decode("zoz-zozoz"), Err(BabbleError::InvalidLength(9)));
+        assert_eq!(decode("zozoz-"), Err(BabbleError::InvalidLength(6)));
+        assert_eq!(decode("Zozoz"), Err(BabbleError::InvalidCharacter('Z', 0)));
+        assert_eq!(decode("zuzoz"), Err(BabbleError::InvalidCharacter('u', 1)));
+    }
+}
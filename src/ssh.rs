@@ -0,0 +1,110 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! OpenSSH public key fingerprints with the `ssh-key` crate.
+
+use crate::{bubblebabble, ToBabble};
+use sha1::{Digest, Sha1};
+use ssh_key::{authorized_keys, known_hosts, Fingerprint, PublicKey};
+use std::borrow::Cow;
+
+/// Return the legacy SHA-1 Babble fingerprint of a public key.
+///
+/// This is the format that is printed by `ssh-keygen -B`.  An error is
+/// returned if the key cannot be encoded to its wire format.
+///
+/// # Examples
+///
+/// ```rust
+/// let key = bubblebabble::ssh_parse_line(
+///     "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     bubblebabble::ssh_babble(&key).unwrap(),
+///     "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox"
+/// );
+/// ```
+pub fn ssh_babble(key: &PublicKey) -> Result<String, ssh_key::Error> {
+    let blob = key.to_bytes()?;
+    Ok(bubblebabble(&Sha1::digest(blob)))
+}
+
+/// Return the Babble of the SHA-256 fingerprint of a public key.
+pub fn ssh_babble_sha256(key: &PublicKey) -> String {
+    key.fingerprint(ssh_key::HashAlg::Sha256).to_babble()
+}
+
+/// Parse a public key from an OpenSSH public key, `authorized_keys`, or
+/// `known_hosts` line.
+pub fn ssh_parse_line(line: &str) -> Result<PublicKey, ssh_key::Error> {
+    let line = line.trim();
+    line.parse::<authorized_keys::Entry>()
+        .map(PublicKey::from)
+        .or_else(|_| {
+            line.parse::<known_hosts::Entry>()
+                .map(|entry| entry.public_key().clone())
+        })
+}
+
+impl ToBabble for Fingerprint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str =
+        "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX host";
+    const BABBLE: &str = "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox";
+
+    #[test]
+    fn test_ssh_babble() {
+        let lines = [
+            KEY.to_string(),
+            format!("no-pty,command=\"/bin/true\" {}", KEY),
+            format!("example.com,192.0.2.1 {}", KEY),
+            format!("@revoked * {}", KEY),
+        ];
+
+        for line in lines.iter() {
+            let key = ssh_parse_line(line).unwrap();
+            assert_eq!(ssh_babble(&key).unwrap(), BABBLE);
+        }
+
+        let key = ssh_parse_line(KEY).unwrap();
+        let fingerprint = key.fingerprint(ssh_key::HashAlg::Sha256);
+        assert_eq!(
+            ssh_babble_sha256(&key),
+            bubblebabble(fingerprint.as_bytes())
+        );
+        assert!(ssh_parse_line("ssh-ed25519 AAAA").is_err());
+    }
+}
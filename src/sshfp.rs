@@ -0,0 +1,225 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! SSHFP DNS records of SSH host key fingerprints (RFC 4255).
+
+use crate::{hex, ToBabble};
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+/// The RDATA of an SSHFP record.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{Sshfp, ToBabble};
+///
+/// let sshfp: Sshfp = "host.example. IN SSHFP 4 1 b8b4bfb37d4ec8bf1e8886b8721161111cf05770"
+///     .parse()
+///     .unwrap();
+/// assert_eq!(sshfp.algorithm_name(), Some("Ed25519"));
+/// assert_eq!(
+///     sshfp.to_babble(),
+///     "xovar-gyzir-fizyg-vedyr-zolem-mocor-masic-cemoc-calaz-byhol-boxox"
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Sshfp {
+    /// The public key algorithm number.
+    pub algorithm: u8,
+    /// The fingerprint type, 1 for SHA-1 and 2 for SHA-256.
+    pub fp_type: u8,
+    /// The fingerprint of the public key blob.
+    pub fingerprint: Vec<u8>,
+}
+
+impl Sshfp {
+    /// Return the name of the public key algorithm.
+    pub fn algorithm_name(&self) -> Option<&'static str> {
+        match self.algorithm {
+            1 => Some("RSA"),
+            2 => Some("DSA"),
+            3 => Some("ECDSA"),
+            4 => Some("Ed25519"),
+            6 => Some("Ed448"),
+            _ => None,
+        }
+    }
+
+    /// Return the name of the fingerprint hash algorithm.
+    pub fn fp_type_name(&self) -> Option<&'static str> {
+        match self.fp_type {
+            1 => Some("SHA-1"),
+            2 => Some("SHA-256"),
+            _ => None,
+        }
+    }
+
+    /// Return the record in zone file syntax with the Babble as comment.
+    pub fn to_record(&self, owner: &str) -> String {
+        format!("{} IN SSHFP {} ; {}", owner, self, self.to_babble())
+    }
+
+    /// Create the record of an OpenSSH public key with the fingerprint
+    /// type 1 (SHA-1) or 2 (SHA-256).
+    #[cfg(feature = "ssh")]
+    pub fn from_public_key(key: &ssh_key::PublicKey, fp_type: u8) -> Option<Self> {
+        use ssh_key::{Algorithm, HashAlg};
+
+        let algorithm = match key.algorithm() {
+            Algorithm::Rsa { .. } => 1,
+            Algorithm::Dsa => 2,
+            Algorithm::Ecdsa { .. } => 3,
+            Algorithm::Ed25519 => 4,
+            _ => return None,
+        };
+        let fingerprint = match fp_type {
+            1 => {
+                use sha1::{Digest, Sha1};
+                Sha1::digest(key.to_bytes().ok()?).to_vec()
+            }
+            2 => key.fingerprint(HashAlg::Sha256).as_bytes().to_vec(),
+            _ => return None,
+        };
+
+        Some(Sshfp {
+            algorithm,
+            fp_type,
+            fingerprint,
+        })
+    }
+}
+
+impl fmt::Display for Sshfp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.algorithm,
+            self.fp_type,
+            hex::encode(&self.fingerprint)
+        )
+    }
+}
+
+/// Error returned when parsing an invalid SSHFP record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SshfpParseError;
+
+impl fmt::Display for SshfpParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid SSHFP record syntax")
+    }
+}
+
+impl error::Error for SshfpParseError {}
+
+impl FromStr for Sshfp {
+    type Err = SshfpParseError;
+
+    /// Parse the RDATA `4 2 651c...` or a zone file record with it.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.split(';').next().unwrap_or_default();
+        let mut tokens = s.split_whitespace().peekable();
+
+        // Skip the owner, TTL, and class of a complete record
+        if s.split_whitespace()
+            .any(|t| t.eq_ignore_ascii_case("SSHFP"))
+        {
+            tokens.find(|t| t.eq_ignore_ascii_case("SSHFP"));
+        }
+        let algorithm = tokens.next().and_then(|t| t.parse().ok());
+        let fp_type = tokens.next().and_then(|t| t.parse().ok());
+        // The fingerprint can be split by whitespace
+        let fingerprint = hex::decode(&tokens.collect::<String>());
+
+        match (algorithm, fp_type, fingerprint) {
+            (Some(algorithm), Some(fp_type), Some(fingerprint)) if !fingerprint.is_empty() => {
+                Ok(Sshfp {
+                    algorithm,
+                    fp_type,
+                    fingerprint,
+                })
+            }
+            _ => Err(SshfpParseError),
+        }
+    }
+}
+
+impl ToBabble for Sshfp {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.fingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sshfp() {
+        let hex = "651cf303d3572d5b8a0fdd3978e2b8a56f07de180f183e770b86bbfb6794b48f";
+        let sshfp: Sshfp = format!("4 2 {}", hex).parse().unwrap();
+
+        assert_eq!(sshfp.fp_type_name(), Some("SHA-256"));
+        assert_eq!(sshfp.to_string(), format!("4 2 {}", hex));
+        assert_eq!(
+            format!(
+                "host.example. 3600 IN SSHFP 4 2 {} {}",
+                &hex[..32],
+                &hex[32..]
+            )
+            .parse(),
+            Ok(sshfp.clone())
+        );
+        assert_eq!(sshfp.to_record("host.example.").parse(), Ok(sshfp.clone()));
+        assert!(sshfp
+            .to_record("host.example.")
+            .ends_with(&sshfp.to_babble()));
+
+        for s in &["", "4 2", "4 x 651c", "4 2 651", "host. IN SSHFP 4 2 zz"] {
+            assert_eq!(s.parse::<Sshfp>(), Err(SshfpParseError));
+        }
+    }
+
+    #[cfg(feature = "ssh")]
+    #[test]
+    fn test_sshfp_from_public_key() {
+        let key = crate::ssh_parse_line(
+            "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIElCF5CJKP1yq1bs2GI1WNuTg4915cAp/jjEOU1P6kDX",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Sshfp::from_public_key(&key, 1).unwrap().to_string(),
+            "4 1 b8b4bfb37d4ec8bf1e8886b8721161111cf05770"
+        );
+        assert_eq!(
+            Sshfp::from_public_key(&key, 2).unwrap().to_string(),
+            "4 2 651cf303d3572d5b8a0fdd3978e2b8a56f07de180f183e770b86bbfb6794b48f"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Strategies to property-test code that handles Babble strings.
+//!
+//! The `proptest` strategies of this module and the `quickcheck`
+//! `Arbitrary` implementations of its types generate valid Babble
+//! strings with their bytes, corrupted strings that the decoder rejects,
+//! and formatted strings with random `Options`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::{debabble, strategy};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn decode(valid in strategy::babble(), corrupted in strategy::corrupted()) {
+//!         prop_assert_eq!(debabble(&valid.babble).unwrap(), valid.bytes);
+//!         prop_assert!(debabble(&corrupted.0).is_err());
+//!     }
+//! }
+//! decode();
+//! ```
+
+use crate::{bubblebabble, debabble, Alphabet, Options, CONSONANTS, VOWELS};
+use proptest::{collection, prelude::*};
+use quickcheck::{Arbitrary, Gen};
+
+/// The maximum number of bytes of the `proptest` strategies.
+pub const MAX_LEN: usize = 64;
+
+/// A valid Babble string and its bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidBabble {
+    pub bytes: Vec<u8>,
+    pub babble: String,
+}
+
+impl ValidBabble {
+    /// Encode `bytes`.
+    pub fn new(bytes: Vec<u8>) -> Self {
+        let babble = bubblebabble(&bytes);
+        ValidBabble { bytes, babble }
+    }
+}
+
+/// A Babble string with a changed, deleted, or swapped character.
+///
+/// The corruption is always detected by `debabble`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorruptedBabble(pub String);
+
+/// A Babble string that is formatted with random options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedBabble {
+    pub bytes: Vec<u8>,
+    pub options: Options,
+    pub babble: String,
+}
+
+impl FormattedBabble {
+    /// Encode `bytes` with `options`.
+    pub fn new(bytes: Vec<u8>, options: Options) -> Self {
+        let babble = options.encode(&bytes);
+        FormattedBabble {
+            bytes,
+            options,
+            babble,
+        }
+    }
+}
+
+/// Return a strategy of up to `MAX_LEN` random bytes.
+pub fn bytes() -> impl Strategy<Value = Vec<u8>> {
+    collection::vec(any::<u8>(), 0..=MAX_LEN)
+}
+
+/// Return a strategy of valid Babble strings.
+pub fn babble() -> impl Strategy<Value = ValidBabble> {
+    bytes().prop_map(ValidBabble::new)
+}
+
+/// Return a strategy of corrupted Babble strings.
+pub fn corrupted() -> impl Strategy<Value = CorruptedBabble> {
+    (babble(), any::<usize>(), any::<usize>(), any::<usize>())
+        .prop_map(|(valid, kind, index, letter)| corrupt(&valid.babble, kind, index, letter))
+        .prop_filter("corruption is valid Babble", |babble| {
+            debabble(babble).is_err()
+        })
+        .prop_map(CorruptedBabble)
+}
+
+/// Return a strategy of random permutations of the letters other than `w`.
+pub fn alphabet() -> impl Strategy<Value = Alphabet> {
+    Just(letters())
+        .prop_shuffle()
+        .prop_map(|letters| permutation(&letters))
+}
+
+/// Return a strategy of valid combinations of the formatting options.
+pub fn options() -> impl Strategy<Value = Options> {
+    (
+        any::<bool>(),
+        any::<char>(),
+        0..=16usize,
+        any::<bool>(),
+        alphabet(),
+    )
+        .prop_map(|(stable, separator, group, uppercase, alphabet)| {
+            Options::new()
+                .separator(separator)
+                .unwrap_or_default()
+                .stable(stable)
+                .group(group)
+                .uppercase(uppercase)
+                .alphabet(alphabet)
+        })
+}
+
+/// Return a strategy of Babble strings with random options.
+pub fn formatted() -> impl Strategy<Value = FormattedBabble> {
+    (bytes(), options()).prop_map(|(bytes, options)| FormattedBabble::new(bytes, options))
+}
+
+impl Arbitrary for ValidBabble {
+    fn arbitrary(g: &mut Gen) -> Self {
+        ValidBabble::new(Vec::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.bytes.shrink().map(ValidBabble::new))
+    }
+}
+
+impl Arbitrary for CorruptedBabble {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let valid = ValidBabble::arbitrary(g);
+        loop {
+            let babble = corrupt(
+                &valid.babble,
+                usize::arbitrary(g),
+                usize::arbitrary(g),
+                usize::arbitrary(g),
+            );
+            if debabble(&babble).is_err() {
+                return CorruptedBabble(babble);
+            }
+        }
+    }
+}
+
+impl Arbitrary for Alphabet {
+    /// Return a random permutation of the letters other than `w`.
+    fn arbitrary(g: &mut Gen) -> Self {
+        let mut letters = letters();
+        for i in (1..letters.len()).rev() {
+            letters.swap(i, usize::arbitrary(g) % (i + 1));
+        }
+        permutation(&letters)
+    }
+}
+
+impl Arbitrary for Options {
+    /// Return valid options, alphanumeric separators are replaced by `-`.
+    fn arbitrary(g: &mut Gen) -> Self {
+        Options::new()
+            .separator(char::arbitrary(g))
+            .unwrap_or_default()
+            .stable(bool::arbitrary(g))
+            .group(usize::arbitrary(g) % 17)
+            .uppercase(bool::arbitrary(g))
+            .alphabet(Alphabet::arbitrary(g))
+    }
+}
+
+impl Arbitrary for FormattedBabble {
+    fn arbitrary(g: &mut Gen) -> Self {
+        FormattedBabble::new(Vec::arbitrary(g), Options::arbitrary(g))
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        let options = self.options.clone();
+        Box::new(
+            self.bytes
+                .shrink()
+                .map(move |bytes| FormattedBabble::new(bytes, options.clone())),
+        )
+    }
+}
+
+/// Change the character at `index` to another letter, delete it, or
+/// swap it with the next one, depending on `kind`.
+fn corrupt(babble: &str, kind: usize, index: usize, letter: usize) -> String {
+    let mut chars = babble.chars().collect::<Vec<_>>();
+    let len = chars.len();
+    let i = index % len;
+    match kind % 3 {
+        0 => {
+            let letters = VOWELS
+                .iter()
+                .chain(CONSONANTS.iter())
+                .filter(|&&c| c != chars[i])
+                .collect::<Vec<_>>();
+            chars[i] = *letters[letter % letters.len()];
+        }
+        1 => {
+            chars.remove(i);
+        }
+        _ => chars.swap(i, (i + 1) % len),
+    }
+    chars.into_iter().collect()
+}
+
+/// Return the letters that can be used by an alphabet.
+fn letters() -> Vec<char> {
+    ('a'..='z').filter(|&c| c != 'w').collect()
+}
+
+/// Return the alphabet of the first 23 of the shuffled `letters`.
+fn permutation(letters: &[char]) -> Alphabet {
+    let vowels = letters[..6].iter().collect::<String>();
+    let consonants = letters[6..23].iter().collect::<String>();
+    Alphabet::new(&vowels, &consonants).expect("valid alphabet")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::{QuickCheck, TestResult};
+
+    proptest! {
+        #[test]
+        fn test_proptest(valid in babble(), formatted in formatted(), corrupted in corrupted()) {
+            prop_assert_eq!(debabble(&valid.babble).unwrap(), valid.bytes);
+            prop_assert_eq!(formatted.options.decode(&formatted.babble).unwrap(), formatted.bytes);
+            prop_assert!(debabble(&corrupted.0).is_err());
+        }
+    }
+
+    #[test]
+    fn test_quickcheck() {
+        fn prop(formatted: FormattedBabble, corrupted: CorruptedBabble) -> TestResult {
+            let decoded = formatted.options.decode(&formatted.babble);
+            TestResult::from_bool(
+                decoded.ok() == Some(formatted.bytes) && debabble(&corrupted.0).is_err(),
+            )
+        }
+        QuickCheck::new().quickcheck(prop as fn(_, _) -> TestResult);
+    }
+}
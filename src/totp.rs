@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Time-based Babble verification codes.
+
+use crate::{bubblebabble, compare::constant_time_eq};
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Time-based one-time codes as Babble.
+///
+/// The codes are derived like the TOTP codes of RFC 6238 with
+/// HMAC-SHA-1 and a 31-bit dynamic truncation, but the value is printed
+/// as three Babble words instead of digits.  A support desk can ask a
+/// user to read the current code of a shared secret to check their
+/// identity.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::Totp;
+///
+/// let totp = Totp::new(b"12345678901234567890");
+/// assert_eq!(totp.code_at(59), "xibef-nizuv-pyxux");
+/// assert!(totp.verify_at("xibef-nizuv-pyxux", 70));
+/// assert!(!totp.verify_at("xibef-nizuv-pyxux", 120));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Totp {
+    secret: Vec<u8>,
+    step: u64,
+    window: u64,
+}
+
+impl Totp {
+    /// Create codes for a shared secret with a time step of 30 seconds
+    /// that accept the previous and next code.
+    pub fn new(secret: &[u8]) -> Self {
+        Totp {
+            secret: secret.to_vec(),
+            step: 30,
+            window: 1,
+        }
+    }
+
+    /// Set the time step in seconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn step(mut self, step: u64) -> Self {
+        assert!(step > 0, "time step must not be zero");
+        self.step = step;
+        self
+    }
+
+    /// Set the number of adjacent time steps that are accepted before
+    /// and after the current one to allow for clock drift and delays.
+    pub fn window(mut self, window: u64) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Return the code of the time step with the given counter.
+    fn code_of(&self, counter: u64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.secret).expect("HMAC accepts any key");
+        mac.update(&counter.to_be_bytes());
+        let hash = mac.finalize().into_bytes();
+
+        let offset = usize::from(hash[hash.len() - 1] & 0xf);
+        let mut value = [0; 4];
+        value.copy_from_slice(&hash[offset..offset + 4]);
+        value[0] &= 0x7f;
+
+        bubblebabble(&value)
+    }
+
+    /// Return the code at the given Unix time in seconds.
+    pub fn code_at(&self, time: u64) -> String {
+        self.code_of(time / self.step)
+    }
+
+    /// Return the current code.
+    pub fn code(&self) -> String {
+        self.code_at(now())
+    }
+
+    /// Check the code at the given Unix time in seconds.
+    ///
+    /// Case and separators of the code are ignored.
+    pub fn verify_at(&self, code: &str, time: u64) -> bool {
+        let counter = time / self.step;
+        let first = counter.saturating_sub(self.window);
+        let last = counter.saturating_add(self.window);
+
+        // Check all steps to avoid leaking the matching step
+        (first..=last).fold(false, |ok, counter| {
+            constant_time_eq(code, &self.code_of(counter)) | ok
+        })
+    }
+
+    /// Check the code at the current time.
+    pub fn verify(&self, code: &str) -> bool {
+        self.verify_at(code, now())
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_totp() {
+        // The values of the SHA-1 test vectors of RFC 6238
+        let totp = Totp::new(b"12345678901234567890");
+        for (time, code) in [
+            (59, "xibef-nizuv-pyxux"),
+            (1111111109, "xetic-bavag-soxux"),
+            (1234567890, "xepec-cunuk-gaxix"),
+            (2000000000, "xivoh-kasof-taxix"),
+        ]
+        .iter()
+        {
+            assert_eq!(totp.code_at(*time), *code);
+            assert!(totp.verify_at(code, *time));
+            assert!(totp.verify_at(&code.to_uppercase(), *time + 30));
+            assert!(totp.verify_at(code, *time - 30));
+            assert!(!totp.verify_at(code, *time + 60));
+        }
+
+        let strict = totp.clone().window(0);
+        assert!(strict.verify_at("xibef-nizuv-pyxux", 30));
+        assert!(!strict.verify_at("xibef-nizuv-pyxux", 60));
+
+        let slow = totp.clone().step(60);
+        assert_eq!(slow.code_at(119), totp.code_at(59));
+        assert!(slow.verify(&slow.code()));
+    }
+}
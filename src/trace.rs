@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Babble fields for `tracing` subscribers.
+
+use crate::bubblebabble;
+use std::{error, fmt};
+use tracing_core::field::{Field, Visit};
+use tracing_subscriber::{
+    field::{MakeVisitor, VisitFmt, VisitOutput},
+    fmt::format::{DefaultVisitor, Writer},
+};
+
+/// Record bytes as a Babble field with the `%` or `?` sigils.
+///
+/// The wrapper prints the Babble in every subscriber, use
+/// [`BabbleFields`] to keep the raw bytes in machine-readable output.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::Babbled;
+///
+/// let key = [0x2a, 0x0a, 0xe5, 0xc0];
+/// tracing::info!(key = %Babbled(&key), "new key");
+/// assert_eq!(Babbled(&key).to_string(), "xepib-panus-buxux");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Babbled<T>(pub T);
+
+impl<T: AsRef<[u8]>> fmt::Display for Babbled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&bubblebabble(self.0.as_ref()))
+    }
+}
+
+impl<T: AsRef<[u8]>> fmt::Debug for Babbled<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Field formatter of `tracing_subscriber::fmt` that prints byte
+/// fields as Babble.
+///
+/// Fields are formatted like the default formatter, but values that
+/// are recorded as bytes, such as `key = &key[..]`, are printed as
+/// Babble instead of hex.  Formatters with their own fields, like the
+/// JSON formatter, keep the raw bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleFields;
+///
+/// let subscriber = tracing_subscriber::fmt()
+///     .fmt_fields(BabbleFields::new())
+///     .finish();
+/// tracing::subscriber::with_default(subscriber, || {
+///     tracing::info!(key = &b"Pineapple"[..], "new key");
+/// });
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BabbleFields {
+    _private: (),
+}
+
+impl BabbleFields {
+    /// Return a new Babble field formatter.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'a> MakeVisitor<Writer<'a>> for BabbleFields {
+    type Visitor = BabbleVisitor<'a>;
+
+    fn make_visitor(&self, target: Writer<'a>) -> Self::Visitor {
+        BabbleVisitor(DefaultVisitor::new(target, true))
+    }
+}
+
+/// The visitor of [`BabbleFields`].
+#[derive(Debug)]
+pub struct BabbleVisitor<'a>(DefaultVisitor<'a>);
+
+impl Visit for BabbleVisitor<'_> {
+    fn record_bytes(&mut self, field: &Field, value: &[u8]) {
+        self.0
+            .record_debug(field, &format_args!("{}", bubblebabble(value)))
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.record_str(field, value)
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn error::Error + 'static)) {
+        self.0.record_error(field, value)
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.record_debug(field, value)
+    }
+}
+
+impl VisitOutput<fmt::Result> for BabbleVisitor<'_> {
+    fn finish(self) -> fmt::Result {
+        self.0.finish()
+    }
+}
+
+impl VisitFmt for BabbleVisitor<'_> {
+    fn writer(&mut self) -> &mut dyn fmt::Write {
+        self.0.writer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_babble_fields() {
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .fmt_fields(BabbleFields::new())
+            .with_writer(move || writer.clone())
+            .with_ansi(false)
+            .without_time()
+            .finish();
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(
+                key = &b"Pineapple"[..],
+                id = %Babbled([1, 2, 3]),
+                port = 22,
+                "new key"
+            );
+        });
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.ends_with(&format!(
+            "new key key={} id={} port=22\n",
+            bubblebabble(b"Pineapple"),
+            bubblebabble(&[1, 2, 3])
+        )));
+    }
+}
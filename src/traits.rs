@@ -0,0 +1,286 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Conversion traits for types that have a Babble representation.
+
+use crate::{bubblebabble, debabble, stablebabble, BabbleError, Options};
+use std::{
+    borrow::Cow,
+    convert::TryFrom,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
+};
+
+/// A value that can be converted to a Babble string.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::ToBabble;
+///
+/// assert_eq!(b"Pineapple".to_babble(), "xigak-nyryk-humil-bosek-sonax");
+/// ```
+pub trait ToBabble {
+    /// Return the bytes that represent the value.
+    fn babble_bytes(&self) -> Cow<'_, [u8]>;
+
+    /// Convert the value to a Bubble Babble string.
+    fn to_babble(&self) -> String {
+        bubblebabble(&self.babble_bytes())
+    }
+
+    /// Convert the value to a stable Babble string.
+    fn to_stablebabble(&self) -> String {
+        stablebabble(&self.babble_bytes())
+    }
+
+    /// Convert the value to a Babble string with formatting options.
+    fn to_babble_with(&self, options: &Options) -> String {
+        options.encode(&self.babble_bytes())
+    }
+}
+
+/// A value that can be parsed from a Babble string.
+pub trait FromBabble: Sized {
+    /// Decode a Bubble Babble string to a value.
+    ///
+    /// A string of the wrong length for the value is returned as
+    /// `BabbleError::InvalidLength`.
+    fn from_babble(babble: &str) -> Result<Self, BabbleError>;
+}
+
+impl ToBabble for [u8] {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<const N: usize> ToBabble for [u8; N] {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl ToBabble for Vec<u8> {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<T: ToBabble + ?Sized> ToBabble for &T {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        (**self).babble_bytes()
+    }
+}
+
+impl FromBabble for Vec<u8> {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        debabble(babble)
+    }
+}
+
+impl<const N: usize> FromBabble for [u8; N] {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; N]>::try_from(debabble(babble)?).map_err(|_| BabbleError::InvalidLength(babble.len()))
+    }
+}
+
+impl ToBabble for Ipv4Addr {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.octets().to_vec())
+    }
+}
+
+impl ToBabble for Ipv6Addr {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.octets().to_vec())
+    }
+}
+
+impl ToBabble for IpAddr {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        match self {
+            IpAddr::V4(addr) => addr.babble_bytes(),
+            IpAddr::V6(addr) => addr.babble_bytes(),
+        }
+    }
+}
+
+/// The address is followed by the port in network byte order.
+impl ToBabble for SocketAddrV4 {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = self.ip().octets().to_vec();
+        bytes.extend_from_slice(&self.port().to_be_bytes());
+        Cow::Owned(bytes)
+    }
+}
+
+/// The address is followed by the port in network byte order.
+impl ToBabble for SocketAddrV6 {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        let mut bytes = self.ip().octets().to_vec();
+        bytes.extend_from_slice(&self.port().to_be_bytes());
+        Cow::Owned(bytes)
+    }
+}
+
+impl FromBabble for Ipv4Addr {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 4]>::from_babble(babble).map(Ipv4Addr::from)
+    }
+}
+
+impl FromBabble for Ipv6Addr {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 16]>::from_babble(babble).map(Ipv6Addr::from)
+    }
+}
+
+/// The family is selected by the length of 4 or 16 bytes.
+impl FromBabble for IpAddr {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        ip_from_bytes(debabble(babble)?).ok_or(BabbleError::InvalidLength(babble.len()))
+    }
+}
+
+pub(crate) fn ip_from_bytes(bytes: Vec<u8>) -> Option<IpAddr> {
+    if let Ok(octets) = <[u8; 4]>::try_from(bytes.as_slice()) {
+        Some(IpAddr::from(octets))
+    } else {
+        <[u8; 16]>::try_from(bytes).ok().map(IpAddr::from)
+    }
+}
+
+impl ToBabble for SocketAddr {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        match self {
+            SocketAddr::V4(addr) => addr.babble_bytes(),
+            SocketAddr::V6(addr) => addr.babble_bytes(),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl ToBabble for ::uuid::Uuid {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl FromBabble for ::uuid::Uuid {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 16]>::from_babble(babble).map(::uuid::Uuid::from_bytes)
+    }
+}
+
+/// The value is encoded as minimal big-endian bytes, zero as a single
+/// zero byte.
+#[cfg(feature = "bigint")]
+impl ToBabble for ::num_bigint::BigUint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.to_bytes_be())
+    }
+}
+
+/// Leading zero bytes, like the sign byte of SSH and DER integers, are
+/// accepted.
+#[cfg(feature = "bigint")]
+impl FromBabble for ::num_bigint::BigUint {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        debabble(babble).map(|bytes| ::num_bigint::BigUint::from_bytes_be(&bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_traits() {
+        let bytes = [0x2a, 0x0a, 0xe5, 0xc0];
+        assert_eq!(bytes.to_babble(), bubblebabble(&bytes));
+        assert_eq!(bytes.to_vec().to_stablebabble(), stablebabble(&bytes));
+        assert_eq!(<[u8; 4]>::from_babble(&bytes.to_babble()), Ok(bytes));
+        assert_eq!(
+            <[u8; 3]>::from_babble("xigak-nyryk-humil-bosek-sonax"),
+            Err(BabbleError::InvalidLength(29))
+        );
+    }
+
+    #[test]
+    fn test_net() {
+        let addr: Ipv6Addr = "2a0a:e5c0:2:5:5cf9:ccc8:7c48:97c0".parse().unwrap();
+        assert_eq!(
+            addr.to_babble(),
+            "xepib-panus-bubub-dubyb-hilyz-nefas-myzug-mihos-bexux"
+        );
+        assert_eq!(IpAddr::V6(addr).to_babble(), addr.to_babble());
+        assert_eq!(Ipv6Addr::LOCALHOST.to_stablebabble(), "xebab-7wa-caxax");
+        assert_eq!(
+            Ipv4Addr::new(192, 0, 2, 1).to_babble(),
+            bubblebabble(&[192, 0, 2, 1])
+        );
+
+        assert_eq!(Ipv6Addr::from_babble(&addr.to_babble()), Ok(addr));
+        assert_eq!(IpAddr::from_babble(&addr.to_babble()), Ok(IpAddr::V6(addr)));
+        let v4 = Ipv4Addr::new(192, 0, 2, 1);
+        assert_eq!(IpAddr::from_babble(&v4.to_babble()), Ok(IpAddr::V4(v4)));
+        assert!(IpAddr::from_babble(&bubblebabble(&[0; 5])).is_err());
+
+        let addr: SocketAddr = "192.0.2.1:22".parse().unwrap();
+        assert_eq!(addr.to_babble(), bubblebabble(&[192, 0, 2, 1, 0, 22]));
+        let addr: SocketAddr = "[::1]:443".parse().unwrap();
+        let mut bytes = Ipv6Addr::LOCALHOST.octets().to_vec();
+        bytes.extend_from_slice(&[1, 187]);
+        assert_eq!(addr.to_babble(), bubblebabble(&bytes));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bigint() {
+        use ::num_bigint::BigUint;
+
+        let modulus = BigUint::from(0xc0ff_ee00_0001_u64);
+        let babble = modulus.to_babble();
+        assert_eq!(babble, bubblebabble(&[0xc0, 0xff, 0xee, 0, 0, 1]));
+        assert_eq!(BigUint::from_babble(&babble), Ok(modulus.clone()));
+        assert_eq!(
+            BigUint::from_babble(&bubblebabble(&[0, 0xc0, 0xff, 0xee, 0, 0, 1])),
+            Ok(modulus)
+        );
+        assert_eq!(BigUint::from(0u8).to_babble(), bubblebabble(&[0]));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid() {
+        let uuid = ::uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8").unwrap();
+        let babble = uuid.to_babble();
+        assert_eq!(babble, bubblebabble(uuid.as_bytes()));
+        assert_eq!(::uuid::Uuid::from_babble(&babble), Ok(uuid));
+    }
+}
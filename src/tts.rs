@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Spoken output of Babble strings.
+//!
+//! Fingerprints are easier to compare by ear when the words are spoken
+//! with a clear pause between them and a longer pause between groups.
+//! `ssml` returns a Speech Synthesis Markup Language document that
+//! platform TTS engines read with the IPA pronunciation of each word,
+//! and `cues` returns the timing of each word to play recorded audio or
+//! to highlight the words while they are read.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::tts::{self, Pace};
+//! use std::time::Duration;
+//!
+//! let pace = Pace::new();
+//! let cues = tts::cues("xigak-nyryk", &pace);
+//! assert_eq!(cues[1].word, "nyryk");
+//! assert_eq!(cues[1].ipa, "/nɪ.rɪk/");
+//! assert_eq!(cues[1].start, Duration::from_millis(1000));
+//!
+//! assert_eq!(
+//!     tts::ssml("xigak-nyryk", &pace),
+//!     "<speak><phoneme alphabet=\"ipa\" ph=\"ksi.ɡak\">xigak</phoneme>\
+//!      <break time=\"300ms\"/>\
+//!      <phoneme alphabet=\"ipa\" ph=\"nɪ.rɪk\">nyryk</phoneme></speak>"
+//! );
+//! ```
+
+use crate::ipa;
+use std::time::Duration;
+
+/// Durations of the spoken words and the pauses between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pace {
+    word: Duration,
+    pause: Duration,
+    group_pause: Duration,
+}
+
+impl Pace {
+    /// Return the default pace of 700ms per word, 300ms pauses between
+    /// words, and 900ms pauses between groups.
+    pub fn new() -> Self {
+        Pace {
+            word: Duration::from_millis(700),
+            pause: Duration::from_millis(300),
+            group_pause: Duration::from_millis(900),
+        }
+    }
+
+    /// Set the time that is reserved for speaking a word.
+    pub fn word(mut self, word: Duration) -> Self {
+        self.word = word;
+        self
+    }
+
+    /// Set the pause between two words.
+    pub fn pause(mut self, pause: Duration) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// Set the pause between two groups of words that are separated by
+    /// a space, see `Options::group`.
+    pub fn group_pause(mut self, group_pause: Duration) -> Self {
+        self.group_pause = group_pause;
+        self
+    }
+}
+
+impl Default for Pace {
+    fn default() -> Self {
+        Pace::new()
+    }
+}
+
+/// A word with its pronunciation and position in the spoken output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+    pub word: String,
+    pub ipa: String,
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+/// Return the cues of the words of `babble`.
+///
+/// Words are separated by any characters other than letters and digits,
+/// a separator with a space ends a group.
+pub fn cues(babble: &str, pace: &Pace) -> Vec<Cue> {
+    let mut start = Duration::ZERO;
+    words(babble)
+        .into_iter()
+        .map(|(word, group)| {
+            let cue = Cue {
+                word: word.to_string(),
+                ipa: ipa::transcribe(word),
+                start,
+                duration: pace.word,
+            };
+            start += pace.word + if group { pace.group_pause } else { pace.pause };
+            cue
+        })
+        .collect()
+}
+
+/// Return an SSML document that reads `babble` aloud.
+///
+/// The counters of repeated words in the stable format are read as
+/// numbers before the word.
+pub fn ssml(babble: &str, pace: &Pace) -> String {
+    let words = words(babble);
+    let mut ssml = String::from("<speak>");
+
+    for (i, (word, group)) in words.iter().enumerate() {
+        let digits = word
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(word.len());
+        let (counter, word) = word.split_at(digits);
+        if !counter.is_empty() {
+            ssml.push_str(counter);
+            ssml.push(' ');
+        }
+        let ipa = ipa::transcribe(word);
+        ssml.push_str(&format!(
+            "<phoneme alphabet=\"ipa\" ph=\"{}\">{}</phoneme>",
+            ipa.trim_matches('/'),
+            word
+        ));
+        if i + 1 < words.len() {
+            let pause = if *group { pace.group_pause } else { pace.pause };
+            ssml.push_str(&format!("<break time=\"{}ms\"/>", pause.as_millis()));
+        }
+    }
+
+    ssml.push_str("</speak>");
+    ssml
+}
+
+/// Split `babble` into words and whether each one ends a group.
+fn words(babble: &str) -> Vec<(&str, bool)> {
+    let mut words = Vec::new();
+    let mut rest = babble.trim();
+
+    while !rest.is_empty() {
+        let end = rest
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(rest.len());
+        let (word, tail) = rest.split_at(end);
+        let next = tail.find(char::is_alphanumeric).unwrap_or(tail.len());
+        if !word.is_empty() {
+            words.push((word, tail[..next].contains(char::is_whitespace)));
+        }
+        rest = &tail[next..];
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Options;
+
+    #[test]
+    fn test_cues() {
+        let pace = Pace::new()
+            .word(Duration::from_millis(500))
+            .pause(Duration::from_millis(100))
+            .group_pause(Duration::from_secs(1));
+        let babble = Options::new()
+            .separator(':')
+            .unwrap()
+            .group(2)
+            .encode(b"Pineapple");
+        let starts = cues(&babble, &pace)
+            .iter()
+            .map(|cue| cue.start.as_millis())
+            .collect::<Vec<_>>();
+        assert_eq!(starts, [0, 600, 2100, 2700, 4200]);
+
+        let cues = cues("xebab-2wa-caxax", &pace);
+        assert_eq!(cues[1].ipa, "2 /wa/");
+        assert!(ssml("xebab-2wa-caxax", &pace).contains("2 <phoneme alphabet=\"ipa\" ph=\"wa\">"));
+    }
+}
@@ -0,0 +1,270 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Interactive verification widget for `ratatui`.
+
+use crate::{diff, BabbleDiff};
+use ratatui_core::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::StatefulWidget,
+};
+
+/// The operator's decision for one word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// The word was not checked yet.
+    Unchecked,
+    /// The operator confirmed that the words match.
+    Confirmed,
+    /// The operator found that the words do not match.
+    Mismatched,
+}
+
+/// The outcome of a manual verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Some words are not checked yet.
+    Pending,
+    /// All words were confirmed.
+    Verified,
+    /// At least one word was marked as mismatched.
+    Failed,
+}
+
+/// State of the `VerifyWidget` with the aligned words, the marks of the
+/// operator, and the selected word.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::tui::{Outcome, VerifyState};
+///
+/// let mut state = VerifyState::new(
+///     "xigak-nyryk-humil-bosek-sonax",
+///     "xigak-nyryk-humil-bosek-sonax",
+/// );
+/// for _ in 0..5 {
+///     state.key('y');
+/// }
+/// assert_eq!(state.outcome(), Outcome::Verified);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VerifyState {
+    diff: BabbleDiff,
+    marks: Vec<Mark>,
+    selected: usize,
+}
+
+impl VerifyState {
+    /// Create the state to compare the fingerprints `a` and `b`.
+    pub fn new(a: &str, b: &str) -> Self {
+        let diff = diff(a, b);
+        let marks = vec![Mark::Unchecked; diff.words().len()];
+        VerifyState {
+            diff,
+            marks,
+            selected: 0,
+        }
+    }
+
+    /// Return the marks of all words.
+    pub fn marks(&self) -> &[Mark] {
+        &self.marks
+    }
+
+    /// Return the index of the selected word.
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Select the next word.
+    pub fn next(&mut self) {
+        if self.selected + 1 < self.marks.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Select the previous word.
+    pub fn previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Mark the selected word and select the next one.
+    pub fn mark(&mut self, mark: Mark) {
+        if let Some(m) = self.marks.get_mut(self.selected) {
+            *m = mark;
+            self.next();
+        }
+    }
+
+    /// Handle a keystroke and return true if it was used.
+    ///
+    /// `y` or space confirm the selected word, `n` or `x` mark it as
+    /// mismatched, `u` clears the mark, and `j`/`k` select the next or
+    /// previous word.
+    pub fn key(&mut self, key: char) -> bool {
+        match key {
+            'y' | ' ' => self.mark(Mark::Confirmed),
+            'n' | 'x' => self.mark(Mark::Mismatched),
+            'u' => self.mark(Mark::Unchecked),
+            'j' => self.next(),
+            'k' => self.previous(),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Return the outcome of the verification.
+    ///
+    /// Fingerprints with a different number of words can never be
+    /// verified.
+    pub fn outcome(&self) -> Outcome {
+        if self.marks.contains(&Mark::Mismatched) {
+            Outcome::Failed
+        } else if self.marks.contains(&Mark::Unchecked) {
+            Outcome::Pending
+        } else if self
+            .diff
+            .words()
+            .iter()
+            .any(|w| w.a.is_none() || w.b.is_none())
+        {
+            Outcome::Failed
+        } else {
+            Outcome::Verified
+        }
+    }
+}
+
+/// A widget that shows two fingerprints word by word.
+///
+/// Each line shows the number of the word, both words with the
+/// differing letters highlighted, and the mark of the operator.  The
+/// last line shows the outcome.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VerifyWidget;
+
+impl StatefulWidget for VerifyWidget {
+    type State = VerifyState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut VerifyState) {
+        let rows = usize::from(area.height.saturating_sub(1));
+        let skip = (state.selected + 1).saturating_sub(rows);
+
+        for (y, (i, word)) in state
+            .diff
+            .words()
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(rows)
+            .enumerate()
+        {
+            let (symbol, color) = match state.marks[i] {
+                Mark::Unchecked => ("?", Color::Reset),
+                Mark::Confirmed => ("\u{2713}", Color::Green),
+                Mark::Mismatched => ("\u{2717}", Color::Red),
+            };
+            let mut spans = vec![Span::raw(format!("{:>3}  ", i + 1))];
+            for w in [&word.a, &word.b].iter() {
+                let w = w.as_deref().unwrap_or("");
+                spans.extend(w.chars().enumerate().map(|(j, c)| {
+                    let style = if word.positions.contains(&j) {
+                        Style::new().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::new()
+                    };
+                    Span::styled(c.to_string(), style)
+                }));
+                spans.push(Span::raw(
+                    " ".repeat(7usize.saturating_sub(w.chars().count())),
+                ));
+            }
+            spans.push(Span::styled(symbol, Style::new().fg(color)));
+
+            let mut line = Line::from(spans);
+            if i == state.selected {
+                line = line.style(Style::new().add_modifier(Modifier::REVERSED));
+            }
+            buf.set_line(area.x, area.y + y as u16, &line, area.width);
+        }
+
+        if area.height > 0 {
+            let (text, color) = match state.outcome() {
+                Outcome::Pending => ("pending", Color::Reset),
+                Outcome::Verified => ("verified", Color::Green),
+                Outcome::Failed => ("failed", Color::Red),
+            };
+            let line = Line::styled(text, Style::new().fg(color));
+            buf.set_line(area.x, area.bottom() - 1, &line, area.width);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_widget() {
+        let mut state = VerifyState::new("xigak-nyryk-humil", "xigak-nyrok-humil");
+        assert_eq!(state.outcome(), Outcome::Pending);
+
+        assert!(state.key('y'));
+        assert!(state.key('n'));
+        assert!(!state.key('q'));
+        assert_eq!(
+            state.marks(),
+            [Mark::Confirmed, Mark::Mismatched, Mark::Unchecked]
+        );
+        assert_eq!(state.selected(), 2);
+        assert_eq!(state.outcome(), Outcome::Failed);
+
+        state.key('k');
+        state.key('y');
+        state.key('y');
+        assert_eq!(state.outcome(), Outcome::Verified);
+
+        let area = Rect::new(0, 0, 24, 4);
+        let mut buf = Buffer::empty(area);
+        VerifyWidget.render(area, &mut buf, &mut state);
+        let lines = (0..4)
+            .map(|y| (0..24).map(|x| buf[(x, y)].symbol()).collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(lines[1].trim_end(), "  2  nyryk  nyrok  \u{2713}");
+        assert_eq!(lines[3].trim_end(), "verified");
+        assert_eq!(buf[(8, 1)].fg, Color::Yellow);
+
+        let mut state = VerifyState::new("xigak-nyryk", "xigak");
+        state.key('y');
+        state.key('y');
+        assert_eq!(state.outcome(), Outcome::Failed);
+    }
+}
@@ -0,0 +1,220 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Urbit-style `@p` phonemic names.
+//!
+//! Each byte is one of 256 three-letter syllables, so that a 16-bit
+//! value is a word like `~marzod` and a 32-bit value a name like
+//! `~palzod-doznec`.  Values below 256 are a single syllable and
+//! leading zero words are omitted, names of 128-bit values separate
+//! each group of four words with `--`.
+//!
+//! Unlike Urbit, the values are not scrambled before encoding, so
+//! names of values above 16 bits differ from Urbit planet names.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use bubblebabble::urbit;
+//!
+//! assert_eq!(urbit::encode(0), "~zod");
+//! assert_eq!(urbit::encode(256), "~marzod");
+//! assert_eq!(urbit::encode(0x7f00_0001), "~palzod-doznec");
+//! assert_eq!(urbit::decode("~palzod-doznec"), Ok(0x7f00_0001));
+//! ```
+
+use crate::BabbleError;
+
+/// Syllables of the high byte of each 16-bit word.
+const PREFIXES: &str = "\
+    dozmarbinwansamlitsighidfidlissogdirwacsabwissibrigsoldopmodfoglidhopdar\
+    dorlorhodfolrintogsilmirholpaslacrovlivdalsatlibtabhanticpidtorbolfosdot\
+    losdilforpilramtirwintadbicdifrocwidbisdasmidloprilnardapmolsanlocnovsit\
+    nidtipsicropwitnatpanminritpodmottamtolsavposnapnopsomfinfonbanmorworsip\
+    ronnorbotwicsocwatdolmagpicdavbidbaltimtasmalligsivtagpadsaldivdactansid\
+    fabtarmonranniswolmispallasdismaprabtobrollatlonnodnavfignomnibpagsopral\
+    bilhaddocridmocpacravripfaltodtiltinhapmicfanpattaclabmogsimsonpinlomric\
+    tapfirhasbosbatpochactidhavsaplindibhosdabbitbarracparloddosbortochilmac\
+    tomdigfilfasmithobharmighinradmashalraglagfadtopmophabnilnosmilfopfamdat\
+    noldinhatnacrisfotribhocnimlarfitwalrapsarnalmoslandondanladdovrivbacpol\
+    laptalpitnambonrostonfodponsovnocsorlavmatmipfip";
+
+/// Syllables of the low byte of each 16-bit word.
+const SUFFIXES: &str = "\
+    zodnecbudwessevpersutletfulpensytdurwepserwylsunrypsyxdyrnuphebpeglupdep\
+    dysputlughecryttyvsydnexlunmeplutseppesdelsulpedtemledtulmetwenbynhexfeb\
+    pyldulhetmevruttylwydtepbesdexsefwycburderneppurrysrebdennutsubpetrulsyn\
+    regtydsupsemwynrecmegnetsecmulnymtevwebsummutnyxrextebfushepbenmuswyxsym\
+    selrucdecwexsyrwetdylmynmesdetbetbeltuxtugmyrpelsyptermebsetdutdegtexsur\
+    feltudnuxruxrenwytnubmedlytdusnebrumtynseglyxpunresredfunrevrefmectedrus\
+    bexlebduxrynnumpyxrygryxfeptyrtustyclegnemfermertenlusnussyltecmexpubrym\
+    tucfyllepdebbermughuttunbylsudpemdevlurdefbusbeprunmelpexdytbyttyplevmyl\
+    wedducfurfexnulluclennerlexrupnedlecrydlydfenwelnydhusrelrudneshesfetdes\
+    retdunlernyrsebhulrylludremlysfynwerrycsugnysnyllyndyndemluxfedsedbecmun\
+    lyrtesmudnytbyrsenwegfyrmurtelreptegpecnelnevfes";
+
+fn syllable(table: &'static str, byte: u8) -> &'static str {
+    let i = usize::from(byte) * 3;
+    &table[i..i + 3]
+}
+
+fn find(table: &str, syllable: &str) -> Option<u16> {
+    (0..256)
+        .find(|i| table[i * 3..i * 3 + 3].eq_ignore_ascii_case(syllable))
+        .map(|i| i as u16)
+}
+
+/// Encode a value as a `@p` name.
+///
+/// 32-bit and 64-bit values can be converted with `u128::from`.
+pub fn encode(value: u128) -> String {
+    if value < 256 {
+        return format!("~{}", syllable(SUFFIXES, value as u8));
+    }
+
+    let mut words = vec![];
+    let mut value = value;
+    while value > 0 {
+        let word = value as u16;
+        words.push(format!(
+            "{}{}",
+            syllable(PREFIXES, (word >> 8) as u8),
+            syllable(SUFFIXES, word as u8)
+        ));
+        value >>= 16;
+    }
+
+    let mut result = String::from("~");
+    for (i, word) in words.iter().enumerate().rev() {
+        result.push_str(word);
+        if i > 0 {
+            result.push_str(if i % 4 == 0 { "--" } else { "-" });
+        }
+    }
+
+    result
+}
+
+/// Decode a `@p` name, with or without the leading `~`.
+///
+/// The syllables are case-insensitive.  Returns
+/// `BabbleError::InvalidLength` if the name has more than 8 words.
+pub fn decode(name: &str) -> Result<u128, BabbleError> {
+    let name = name.trim();
+    let offset = if name.starts_with('~') { 1 } else { 0 };
+
+    let mut value = 0u128;
+    let mut words = 0;
+    let mut start = offset;
+    for word in name[offset..].split('-') {
+        let end = start + word.len();
+        if word.is_empty() {
+            start = end + 1;
+            continue;
+        }
+        if !word.is_ascii() {
+            let (i, c) = word.char_indices().find(|(_, c)| !c.is_ascii()).unwrap();
+            return Err(BabbleError::InvalidCharacter(c, start + i));
+        }
+        let invalid =
+            |i: usize| BabbleError::InvalidCharacter(word[i..].chars().next().unwrap(), start + i);
+
+        let bits = match word.len() {
+            3 if name[offset..].len() == 3 => find(SUFFIXES, word).ok_or_else(|| invalid(0))?,
+            6 => {
+                let high = find(PREFIXES, &word[..3]).ok_or_else(|| invalid(0))?;
+                let low = find(SUFFIXES, &word[3..]).ok_or_else(|| invalid(3))?;
+                (high << 8) | low
+            }
+            _ => return Err(BabbleError::InvalidLength(name.len())),
+        };
+
+        words += 1;
+        if words > 8 {
+            return Err(BabbleError::InvalidLength(name.len()));
+        }
+        value = (value << 16) | u128::from(bits);
+        start = end + 1;
+    }
+
+    if words == 0 {
+        return Err(BabbleError::InvalidLength(name.len()));
+    }
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_urbit() {
+        let tests = [
+            (0, "~zod"),
+            (1, "~nec"),
+            (255, "~fes"),
+            (256, "~marzod"),
+            (65535, "~fipfes"),
+            (65536, "~doznec-dozzod"),
+            (u128::from(u64::MAX), "~fipfes-fipfes-fipfes-fipfes"),
+            (
+                u128::MAX,
+                "~fipfes-fipfes-fipfes-fipfes--fipfes-fipfes-fipfes-fipfes",
+            ),
+        ];
+
+        for (value, name) in tests.iter() {
+            assert_eq!(encode(*value), *name);
+            assert_eq!(decode(name), Ok(*value));
+        }
+
+        assert_eq!(decode("MARZOD"), Ok(256));
+        assert_eq!(
+            decode("~doznec-dozzod-doznec-dozzod-doznec"),
+            Ok(0x0001_0000_0001_0000_0001)
+        );
+        assert_eq!(decode(""), Err(BabbleError::InvalidLength(0)));
+        assert_eq!(decode("~zod-zod"), Err(BabbleError::InvalidLength(8)));
+        assert_eq!(
+            decode("~marzox"),
+            Err(BabbleError::InvalidCharacter('z', 4))
+        );
+        assert_eq!(
+            decode("~xarzod"),
+            Err(BabbleError::InvalidCharacter('x', 1))
+        );
+        assert_eq!(
+            decode("~marzöd"),
+            Err(BabbleError::InvalidCharacter('ö', 5))
+        );
+        assert_eq!(
+            decode("~fipfes-fipfes-fipfes-fipfes--fipfes-fipfes-fipfes-fipfes-fipfes"),
+            Err(BabbleError::InvalidLength(64))
+        );
+    }
+}
@@ -0,0 +1,277 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! The `babble:` URI scheme.
+
+use crate::{bubblebabble, debabble, BabbleFingerprint, ToBabble};
+use std::{borrow::Cow, error, fmt, str::FromStr};
+
+/// The scheme of Babble URIs.
+pub const SCHEME: &str = "babble";
+
+/// A Babble string with its hash algorithm and a label as a URI.
+///
+/// The URI has the form `babble:[algorithm;]babble[?label=label]`, so a
+/// fingerprint can be stored in QR codes, NFC tags, and links with its
+/// metadata.  The label is percent-encoded and unknown query parameters
+/// are ignored when parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleUri;
+///
+/// let uri = BabbleUri::new(b"Pineapple".to_vec())
+///     .algorithm("sha256")
+///     .label("host 1");
+/// assert_eq!(
+///     uri.to_string(),
+///     "babble:sha256;xigak-nyryk-humil-bosek-sonax?label=host%201"
+/// );
+/// assert_eq!(uri.to_string().parse(), Ok(uri));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BabbleUri {
+    algorithm: Option<String>,
+    digest: Vec<u8>,
+    babble: String,
+    label: Option<String>,
+}
+
+impl BabbleUri {
+    /// Create a URI of the data without algorithm and label.
+    pub fn new(digest: impl Into<Vec<u8>>) -> Self {
+        let digest = digest.into();
+        BabbleUri {
+            algorithm: None,
+            babble: bubblebabble(&digest),
+            digest,
+            label: None,
+        }
+    }
+
+    /// Set the name of the hash algorithm.
+    ///
+    /// The name may only contain ASCII letters, digits, `-`, and `_`,
+    /// other characters are removed.
+    pub fn algorithm(mut self, algorithm: &str) -> Self {
+        let algorithm = algorithm
+            .chars()
+            .filter(|&c| is_algorithm_char(c))
+            .collect::<String>();
+        self.algorithm = Some(algorithm).filter(|algorithm| !algorithm.is_empty());
+        self
+    }
+
+    /// Set a label like the name of the host or key.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Return the name of the hash algorithm.
+    pub fn get_algorithm(&self) -> Option<&str> {
+        self.algorithm.as_deref()
+    }
+
+    /// Return the label.
+    pub fn get_label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Return the encoded data.
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    /// Return the Babble of the data.
+    pub fn babble(&self) -> &str {
+        &self.babble
+    }
+
+    /// Return the fingerprint if the URI has an algorithm.
+    pub fn fingerprint(&self) -> Option<BabbleFingerprint> {
+        let algorithm = self.algorithm.as_deref()?;
+        Some(BabbleFingerprint::new(algorithm, self.digest.clone()))
+    }
+}
+
+impl From<BabbleFingerprint> for BabbleUri {
+    fn from(fpr: BabbleFingerprint) -> Self {
+        BabbleUri::new(fpr.digest()).algorithm(fpr.algorithm())
+    }
+}
+
+impl fmt::Display for BabbleUri {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:", SCHEME)?;
+        if let Some(algorithm) = &self.algorithm {
+            write!(f, "{};", algorithm)?;
+        }
+        f.write_str(&self.babble)?;
+        if let Some(label) = &self.label {
+            f.write_str("?label=")?;
+            for &b in label.as_bytes() {
+                if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+                    write!(f, "{}", b as char)?;
+                } else {
+                    write!(f, "%{:02X}", b)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing an invalid Babble URI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BabbleUriParseError;
+
+impl fmt::Display for BabbleUriParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid Babble URI syntax")
+    }
+}
+
+impl error::Error for BabbleUriParseError {}
+
+impl FromStr for BabbleUri {
+    type Err = BabbleUriParseError;
+
+    /// Parse a `babble:` URI, the scheme is case-insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (scheme, rest) = s.split_once(':').ok_or(BabbleUriParseError)?;
+        if !scheme.eq_ignore_ascii_case(SCHEME) {
+            return Err(BabbleUriParseError);
+        }
+
+        let (path, query) = match rest.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (rest, None),
+        };
+        let (algorithm, babble) = match path.split_once(';') {
+            Some((algorithm, _))
+                if algorithm.is_empty() || !algorithm.chars().all(is_algorithm_char) =>
+            {
+                return Err(BabbleUriParseError)
+            }
+            Some((algorithm, babble)) => (Some(algorithm.to_string()), babble),
+            None => (None, path),
+        };
+        let digest = debabble(babble).map_err(|_| BabbleUriParseError)?;
+
+        let mut label = None;
+        for param in query.into_iter().flat_map(|query| query.split('&')) {
+            if let Some(value) = param.strip_prefix("label=") {
+                label = Some(percent_decode(value).ok_or(BabbleUriParseError)?);
+            }
+        }
+
+        Ok(BabbleUri {
+            algorithm,
+            digest,
+            babble: babble.to_string(),
+            label,
+        })
+    }
+}
+
+impl ToBabble for BabbleUri {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.digest)
+    }
+}
+
+fn is_algorithm_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hex = [iter.next()?, iter.next()?];
+                let hex = std::str::from_utf8(&hex).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            }
+            b'+' => bytes.push(b' '),
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_babble_uri() {
+        let uri: BabbleUri = "babble:xigak-nyryk-humil-bosek-sonax".parse().unwrap();
+        assert_eq!(uri, BabbleUri::new(b"Pineapple".to_vec()));
+        assert_eq!(uri.get_algorithm(), None);
+        assert_eq!(uri.fingerprint(), None);
+
+        let uri: BabbleUri = "BABBLE:SHA256;xigak-nyryk-humil-bosek-sonax?v=1&label=h%C3%B6st+1"
+            .parse()
+            .unwrap();
+        assert_eq!(uri.get_algorithm(), Some("SHA256"));
+        assert_eq!(uri.get_label(), Some("höst 1"));
+        assert_eq!(
+            uri.to_string(),
+            "babble:SHA256;xigak-nyryk-humil-bosek-sonax?label=h%C3%B6st%201"
+        );
+        assert_eq!(
+            uri.fingerprint().unwrap().to_string(),
+            "SHA256/xigak-nyryk-humil-bosek-sonax"
+        );
+        assert_eq!(
+            BabbleUri::from(uri.fingerprint().unwrap()),
+            BabbleUri::new(b"Pineapple".to_vec()).algorithm("SHA256")
+        );
+        assert_eq!(
+            BabbleUri::new(vec![]).algorithm("sha/256").to_string(),
+            "babble:sha256;xexax"
+        );
+
+        for s in &[
+            "",
+            "xigak-nyryk-humil-bosek-sonax",
+            "bubble:xigak-nyryk-humil-bosek-sonax",
+            "babble:;xigak-nyryk-humil-bosek-sonax",
+            "babble:sha 256;xigak-nyryk-humil-bosek-sonax",
+            "babble:sha256;xigak-nyryk-humil-bosek-sonox",
+            "babble:xigak-nyryk-humil-bosek-sonax?label=%C3",
+            "babble:xigak-nyryk-humil-bosek-sonax?label=%4",
+        ] {
+            assert_eq!(s.parse::<BabbleUri>(), Err(BabbleUriParseError));
+        }
+    }
+}
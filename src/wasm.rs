@@ -0,0 +1,65 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! WebAssembly bindings for JavaScript.
+//!
+//! The functions are exported with `wasm-bindgen` and use `Uint8Array`
+//! for bytes and `string` for Babble:
+//!
+//! ```js
+//! import { encode, encodeStable, decode } from "bubblebabble";
+//!
+//! encode(new TextEncoder().encode("Pineapple")); // "xigak-nyryk-humil-bosek-sonax"
+//! decode("xigak-nyryk-humil-bosek-sonax");       // Uint8Array
+//! ```
+
+use wasm_bindgen::prelude::*;
+
+/// Convert bytes to Babble.
+#[wasm_bindgen]
+pub fn encode(bytes: &[u8]) -> String {
+    crate::bubblebabble(bytes)
+}
+
+/// Convert bytes to stable Babble.
+#[wasm_bindgen(js_name = encodeStable)]
+pub fn encode_stable(bytes: &[u8]) -> String {
+    crate::stablebabble(bytes)
+}
+
+/// Convert Babble back to bytes, throwing an `Error` if it is invalid.
+#[wasm_bindgen]
+pub fn decode(babble: &str) -> Result<Vec<u8>, JsError> {
+    crate::debabble(babble).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Convert stable Babble back to bytes, throwing an `Error` if it is
+/// invalid.
+#[wasm_bindgen(js_name = decodeStable)]
+pub fn decode_stable(babble: &str) -> Result<Vec<u8>, JsError> {
+    crate::debabble_stable(babble).map_err(|err| JsError::new(&err.to_string()))
+}
@@ -0,0 +1,131 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! WireGuard public keys.
+
+use crate::{base64, short, BabbleError, FromBabble, ToBabble, ELLIPSIS};
+use std::{borrow::Cow, convert::TryFrom, error, fmt, str::FromStr};
+
+/// Number of words of the short form.
+const SHORT_WORDS: usize = 4;
+
+/// A WireGuard public key, a 32-byte Curve25519 point.
+///
+/// Keys are parsed from and displayed in the base64 form that is used
+/// by `wg` and the configuration files.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::WireGuardKey;
+///
+/// let key: WireGuardKey = "mdOCxeOQxNVCPwanqyfoyGPRLiUtcRZ2PbXpNTVBmRA=".parse().unwrap();
+/// assert_eq!(key.short_babble(), "xoket-fybas-homan-bicot");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WireGuardKey(pub [u8; 32]);
+
+impl WireGuardKey {
+    /// Return the first four words of the Babble to identify the key,
+    /// like `short` but without the `...` marker.
+    pub fn short_babble(&self) -> String {
+        let babble = short(&self.0, SHORT_WORDS);
+        match babble.strip_suffix(ELLIPSIS) {
+            Some(babble) => babble.to_string(),
+            None => babble,
+        }
+    }
+}
+
+impl fmt::Display for WireGuardKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&base64::encode(&self.0))
+    }
+}
+
+/// Error returned when parsing an invalid WireGuard key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WireGuardKeyParseError;
+
+impl fmt::Display for WireGuardKeyParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid WireGuard key syntax")
+    }
+}
+
+impl error::Error for WireGuardKeyParseError {}
+
+impl FromStr for WireGuardKey {
+    type Err = WireGuardKeyParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.len() != 44 {
+            return Err(WireGuardKeyParseError);
+        }
+        base64::decode(s)
+            .and_then(|key| <[u8; 32]>::try_from(key).ok())
+            .map(WireGuardKey)
+            .ok_or(WireGuardKeyParseError)
+    }
+}
+
+impl ToBabble for WireGuardKey {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.0)
+    }
+}
+
+impl FromBabble for WireGuardKey {
+    fn from_babble(babble: &str) -> Result<Self, BabbleError> {
+        <[u8; 32]>::from_babble(babble).map(WireGuardKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wireguard_key() {
+        let base64 = "mdOCxeOQxNVCPwanqyfoyGPRLiUtcRZ2PbXpNTVBmRA=";
+        let key: WireGuardKey = base64.parse().unwrap();
+
+        assert_eq!(key.to_string(), base64);
+        assert!(key.to_babble().starts_with(&key.short_babble()));
+        assert_eq!(key.to_babble().split('-').count(), 17);
+        assert_eq!(WireGuardKey::from_babble(&key.to_babble()), Ok(key));
+
+        for s in &[
+            "",
+            "mdOCxeOQxNVCPwanqyfoyGPRLiUtcRZ2PbXpNTVBmR",
+            "UGluZWFwcGxl",
+        ] {
+            assert_eq!(s.parse::<WireGuardKey>(), Err(WireGuardKeyParseError));
+        }
+    }
+}
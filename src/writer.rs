@@ -0,0 +1,221 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! Streaming encoder for `io::Write` sinks.
+
+use crate::Encoder;
+use std::{
+    io::{self, Write},
+    mem,
+};
+
+/// An `io::Write` adapter that encodes all written bytes as Babble and
+/// writes the text to the inner writer.
+///
+/// The last word depends on the end of the data, so it is only written
+/// by `finish`, which must be called once all bytes have been written.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::BabbleWriter;
+/// use std::io::Write;
+///
+/// let mut writer = BabbleWriter::new(Vec::new()).wrap(12);
+/// writer.write_all(b"Pine").unwrap();
+/// writer.write_all(b"apple").unwrap();
+/// let babble = writer.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(babble).unwrap(),
+///     "xigak-nyryk-\nhumil-bosek-\nsonax"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct BabbleWriter<W: Write> {
+    inner: W,
+    encoder: Encoder,
+    babble: String,
+    text: WrapBuf,
+}
+
+impl<W: Write> BabbleWriter<W> {
+    /// Return a writer that encodes to the Bubble Babble format.
+    pub fn new(inner: W) -> Self {
+        Self::with_encoder(inner, Encoder::new())
+    }
+
+    /// Return a writer that encodes to the stable Babble format.
+    pub fn stable(inner: W) -> Self {
+        Self::with_encoder(inner, Encoder::stable())
+    }
+
+    fn with_encoder(inner: W, encoder: Encoder) -> Self {
+        BabbleWriter {
+            inner,
+            encoder,
+            babble: String::new(),
+            text: WrapBuf::new(),
+        }
+    }
+
+    /// Wrap the text with a newline after `width` characters, 0
+    /// disables wrapping.
+    pub fn wrap(mut self, width: usize) -> Self {
+        self.text.width = width;
+        self
+    }
+
+    /// Return a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Return a mutable reference to the inner writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Write the final word, flush, and return the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.drain()?;
+        mem::take(&mut self.encoder).finish(&mut self.babble);
+        self.text.push_str(&self.babble);
+        self.drain()?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    // Write the buffered text and keep the rest on error
+    fn drain(&mut self) -> io::Result<()> {
+        while !self.text.is_empty() {
+            match self.inner.write(self.text.pending()) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(len) => self.text.consume(len),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for BabbleWriter<W> {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        // Text of a previous write is retried before accepting new bytes
+        self.drain()?;
+        self.encoder.update(bytes, &mut self.babble);
+        self.text.push_str(&self.babble);
+        self.babble.clear();
+
+        // The bytes are consumed, an error is reported by the next call
+        let _ = self.drain();
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.drain()?;
+        self.inner.flush()
+    }
+}
+
+/// Encoded text that is waiting to be written, with newlines inserted
+/// after `width` characters.
+#[derive(Debug, Default)]
+pub(crate) struct WrapBuf {
+    pub(crate) width: usize,
+    column: usize,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl WrapBuf {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn push_str(&mut self, s: &str) {
+        let mut s = s.as_bytes();
+        if self.width == 0 {
+            self.buf.extend_from_slice(s);
+            return;
+        }
+
+        while !s.is_empty() {
+            if self.column == self.width {
+                self.buf.push(b'\n');
+                self.column = 0;
+            }
+            let len = s.len().min(self.width - self.column);
+            self.buf.extend_from_slice(&s[..len]);
+            self.column += len;
+            s = &s[len..];
+        }
+    }
+
+    pub(crate) fn pending(&self) -> &[u8] {
+        &self.buf[self.pos..]
+    }
+
+    pub(crate) fn consume(&mut self, len: usize) {
+        self.pos += len;
+        if self.pos == self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos == self.buf.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bubblebabble, stablebabble};
+
+    #[test]
+    fn test_babble_writer() {
+        let data = (0..=255).collect::<Vec<u8>>();
+
+        let mut writer = BabbleWriter::new(Vec::new());
+        for chunk in data.chunks(7) {
+            writer.write_all(chunk).unwrap();
+        }
+        let babble = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert_eq!(babble, bubblebabble(&data));
+
+        let mut writer = BabbleWriter::stable(Vec::new()).wrap(64);
+        io::copy(&mut &data[..], &mut writer).unwrap();
+        let babble = String::from_utf8(writer.finish().unwrap()).unwrap();
+        assert!(babble.lines().all(|line| line.len() <= 64));
+        assert_eq!(babble.replace('\n', ""), stablebabble(&data));
+
+        let writer = BabbleWriter::new(Vec::new()).wrap(5);
+        assert_eq!(writer.finish().unwrap(), b"xexax");
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * Copyright (c) 2019 Reyk Floeter. All rights reserved.
+ * Copyright (c) 2000, 2001 Markus Friedl.  All rights reserved.
+ * Copyright (c) 2008 Alexander von Gernler.  All rights reserved.
+ * Copyright (c) 2010,2011 Damien Miller.  All rights reserved.
+ *
+ * Redistribution and use in source and binary forms, with or without
+ * modification, are permitted provided that the following conditions
+ * are met:
+ * 1. Redistributions of source code must retain the above copyright
+ *    notice, this list of conditions and the following disclaimer.
+ * 2. Redistributions in binary form must reproduce the above copyright
+ *    notice, this list of conditions and the following disclaimer in the
+ *    documentation and/or other materials provided with the distribution.
+ *
+ * THIS SOFTWARE IS PROVIDED BY THE AUTHOR ``AS IS'' AND ANY EXPRESS OR
+ * IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES
+ * OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE DISCLAIMED.
+ * IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR ANY DIRECT, INDIRECT,
+ * INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING, BUT
+ * NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE,
+ * DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY
+ * THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT
+ * (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE OF
+ * THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+ */
+
+//! X.509 certificate fingerprints and public key pins.
+
+use crate::{base64, ToBabble};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use std::{borrow::Cow, error, fmt};
+use x509_parser::{
+    certificate::X509Certificate, pem, prelude::FromDer, x509::SubjectPublicKeyInfo,
+};
+
+/// The fingerprint of an X.509 certificate.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{CertificateFingerprint, ToBabble};
+///
+/// # let pem = include_bytes!("../tests/data/cert.pem");
+/// let fingerprint = CertificateFingerprint::sha256(pem).unwrap();
+/// assert_eq!(fingerprint.subject, "CN=example.com, O=Example");
+/// println!("{}: {}", fingerprint.algorithm, fingerprint.to_babble());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CertificateFingerprint {
+    /// The subject of the certificate.
+    pub subject: String,
+    /// The name of the hash algorithm, `SHA-256` or `SHA-1`.
+    pub algorithm: &'static str,
+    /// The digest of the DER-encoded certificate.
+    pub digest: Vec<u8>,
+}
+
+impl CertificateFingerprint {
+    /// Return the SHA-256 fingerprint of a DER or PEM certificate.
+    pub fn sha256(cert: &[u8]) -> Result<Self, CertificateError> {
+        Self::new::<Sha256>(cert, "SHA-256")
+    }
+
+    /// Return the legacy SHA-1 fingerprint of a DER or PEM certificate.
+    pub fn sha1(cert: &[u8]) -> Result<Self, CertificateError> {
+        Self::new::<Sha1>(cert, "SHA-1")
+    }
+
+    fn new<D: Digest>(cert: &[u8], algorithm: &'static str) -> Result<Self, CertificateError> {
+        let der = der(cert)?;
+        let (_, x509) =
+            X509Certificate::from_der(&der).map_err(|err| CertificateError(err.to_string()))?;
+
+        Ok(CertificateFingerprint {
+            subject: x509.subject().to_string(),
+            algorithm,
+            digest: D::digest(&der).to_vec(),
+        })
+    }
+}
+
+impl fmt::Display for CertificateFingerprint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            self.algorithm,
+            self.to_babble(),
+            self.subject
+        )
+    }
+}
+
+impl ToBabble for CertificateFingerprint {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.digest)
+    }
+}
+
+/// A SHA-256 pin of the SubjectPublicKeyInfo of a certificate or key.
+///
+/// The pin is used for certificate pinning as in HPKP (RFC 7469) and is
+/// displayed both as Babble and in the standard base64 form.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::SpkiPin;
+///
+/// # let pem = include_bytes!("../tests/data/cert.pem");
+/// let pin = SpkiPin::from_certificate(pem).unwrap();
+/// assert_eq!(pin.base64(), "x0pfqWSLX4raFTXu6ZgkN8ZIxYtbGSFU7yq3BdUlO2o=");
+/// println!("{}", pin);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpkiPin {
+    /// The SHA-256 digest of the DER-encoded SubjectPublicKeyInfo.
+    pub digest: Vec<u8>,
+}
+
+impl SpkiPin {
+    /// Return the pin of the public key of a DER or PEM certificate.
+    pub fn from_certificate(cert: &[u8]) -> Result<Self, CertificateError> {
+        let der = der(cert)?;
+        let (_, x509) =
+            X509Certificate::from_der(&der).map_err(|err| CertificateError(err.to_string()))?;
+        Ok(Self::from_spki(x509.public_key().raw))
+    }
+
+    /// Return the pin of a DER or PEM `PUBLIC KEY`.
+    pub fn from_public_key(key: &[u8]) -> Result<Self, CertificateError> {
+        let der = der(key)?;
+        let (_, spki) = SubjectPublicKeyInfo::from_der(&der)
+            .map_err(|err| CertificateError(err.to_string()))?;
+        Ok(Self::from_spki(spki.raw))
+    }
+
+    fn from_spki(spki: &[u8]) -> Self {
+        SpkiPin {
+            digest: Sha256::digest(spki).to_vec(),
+        }
+    }
+
+    /// Return the pin in the standard base64 form.
+    pub fn base64(&self) -> String {
+        base64::encode(&self.digest)
+    }
+}
+
+impl fmt::Display for SpkiPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pin-sha256=\"{}\" {}", self.base64(), self.to_babble())
+    }
+}
+
+impl ToBabble for SpkiPin {
+    fn babble_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(&self.digest)
+    }
+}
+
+/// Return the IKEv2 hash of the public key of a certificate or key.
+///
+/// IKEv2 identifies public keys by the SHA-1 digest of the DER-encoded
+/// SubjectPublicKeyInfo, as in the list of trusted CAs of a certificate
+/// request (RFC 7296, section 3.7).  The input is a DER or PEM
+/// certificate or `PUBLIC KEY`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bubblebabble::{bubblebabble, ikev2_key_hash};
+///
+/// # let pem = include_bytes!("../tests/data/cert.pem");
+/// let hash = ikev2_key_hash(pem).unwrap();
+/// println!("{}", bubblebabble(&hash));
+/// ```
+pub fn ikev2_key_hash(input: &[u8]) -> Result<[u8; 20], CertificateError> {
+    let der = der(input)?;
+    let digest = match X509Certificate::from_der(&der) {
+        Ok((_, x509)) => Sha1::digest(x509.public_key().raw),
+        Err(_) => {
+            let (_, spki) = SubjectPublicKeyInfo::from_der(&der)
+                .map_err(|err| CertificateError(err.to_string()))?;
+            Sha1::digest(spki.raw)
+        }
+    };
+    Ok(digest.into())
+}
+
+/// Error returned for invalid certificates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateError(String);
+
+impl fmt::Display for CertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid certificate: {}", self.0)
+    }
+}
+
+impl error::Error for CertificateError {}
+
+/// Return the DER of the first PEM block or the input if it is DER.
+pub(crate) fn der(input: &[u8]) -> Result<Cow<'_, [u8]>, CertificateError> {
+    if input.trim_ascii_start().starts_with(b"-----BEGIN") {
+        let (_, pem) =
+            pem::parse_x509_pem(input).map_err(|err| CertificateError(err.to_string()))?;
+        Ok(Cow::Owned(pem.contents))
+    } else {
+        Ok(Cow::Borrowed(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_certificate_fingerprint() {
+        let pem = include_bytes!("../tests/data/cert.pem");
+        let der = der(pem).unwrap();
+
+        // openssl x509 -noout -fingerprint -sha256
+        let sha256 = CertificateFingerprint::sha256(&der).unwrap();
+        assert_eq!(
+            sha256.digest,
+            crate::hex::decode("f1ea524c8a6d527e3669f200cf89545dddd0fffd3c74e6e1711d95a3de9f3dfb")
+                .unwrap()
+        );
+        assert_eq!(CertificateFingerprint::sha256(pem), Ok(sha256.clone()));
+        assert_eq!(
+            sha256.to_string(),
+            format!("SHA-256 {} CN=example.com, O=Example", sha256.to_babble())
+        );
+
+        let sha1 = CertificateFingerprint::sha1(pem).unwrap();
+        assert_eq!(sha1.algorithm, "SHA-1");
+        assert_eq!(sha1.digest.len(), 20);
+
+        assert!(CertificateFingerprint::sha256(b"Pineapple").is_err());
+    }
+
+    #[test]
+    fn test_spki_pin() {
+        let pin = SpkiPin::from_certificate(include_bytes!("../tests/data/cert.pem")).unwrap();
+        let key = SpkiPin::from_public_key(include_bytes!("../tests/data/pub.pem")).unwrap();
+
+        assert_eq!(pin, key);
+        assert_eq!(pin.base64(), "x0pfqWSLX4raFTXu6ZgkN8ZIxYtbGSFU7yq3BdUlO2o=");
+        assert_eq!(
+            pin.to_string(),
+            format!("pin-sha256=\"{}\" {}", pin.base64(), pin.to_babble())
+        );
+        assert!(SpkiPin::from_public_key(b"Pineapple").is_err());
+    }
+
+    #[test]
+    fn test_ikev2_key_hash() {
+        // openssl pkey -pubin -outform der | sha1sum
+        let hash = ikev2_key_hash(include_bytes!("../tests/data/pub.pem")).unwrap();
+        assert_eq!(
+            hash[..],
+            crate::hex::decode("2205e8195c5e7d5c7e82ee3ecad305667c9d281e").unwrap()[..]
+        );
+        assert_eq!(
+            ikev2_key_hash(include_bytes!("../tests/data/cert.pem")),
+            Ok(hash)
+        );
+        assert!(ikev2_key_hash(b"Pineapple").is_err());
+    }
+}